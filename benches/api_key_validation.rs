@@ -0,0 +1,66 @@
+//! Compara la validación de ApiKeys con y sin la caché de verificaciones:
+//! `cargo bench --bench api_key_validation`
+
+use std::{sync::Arc, time::Duration};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use library_api::{
+    api_keys::{
+        api_keys_application::ApiKeyCache,
+        api_keys_infrastructure::{ApiKeySqliteRepository, ApiKeysState},
+    },
+    audit::{
+        audit_application::AuditLog, audit_domain::Actor,
+        audit_infrastructure::MemoryAuditRepository,
+    },
+};
+use sqlx::SqlitePool;
+use tokio::runtime::Runtime;
+
+fn api_key_validation(c: &mut Criterion) {
+    let runtime = Runtime::new().expect("Error al crear el runtime");
+
+    let repo = runtime.block_on(async {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .expect("Error al crear base de datos en memoria");
+        let repo = ApiKeySqliteRepository::new(pool);
+        repo.init_table().await.expect("Error al crear la tabla");
+        Arc::new(repo)
+    });
+
+    let audit = Arc::new(AuditLog {
+        repo: Arc::new(MemoryAuditRepository::default()),
+    });
+
+    let mut group = c.benchmark_group("api_key_validation");
+    // Cada verificación sin caché calcula un hash lento, basta con pocas muestras
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for (name, cache) in [
+        ("sin_cache", ApiKeyCache::disabled()),
+        ("con_cache", ApiKeyCache::default()),
+    ] {
+        let api_keys = ApiKeysState::with_cache(repo.clone(), audit.clone(), cache);
+        let api_key = runtime
+            .block_on(api_keys.creation.create(&Actor::cli()))
+            .expect("Error al generar API Key");
+        let header = format!("ApiKey {api_key}");
+
+        group.bench_function(name, |b| {
+            b.to_async(&runtime).iter(|| async {
+                api_keys
+                    .auth
+                    .auth_from_header(Some(&header))
+                    .await
+                    .expect("ApiKey válida")
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, api_key_validation);
+criterion_main!(benches);
@@ -1,14 +1,53 @@
+#[cfg(not(feature = "cli"))]
+mod args {
+    use clap::{Parser, Subcommand};
+    use library_api::config::{ConfigArgs, ConfigCommand};
+
+    /// API RESTful de la biblioteca
+    #[derive(Debug, Parser)]
+    #[command(version)]
+    pub struct Args {
+        #[command(flatten)]
+        pub config: ConfigArgs,
+
+        #[command(subcommand)]
+        pub command: Option<Command>,
+    }
+
+    #[derive(Debug, Subcommand)]
+    pub enum Command {
+        /// Consulta la configuración de la API
+        Config {
+            #[command(subcommand)]
+            command: ConfigCommand,
+        },
+    }
+}
+
 #[cfg(not(feature = "cli"))]
 #[tokio::main]
 async fn main() {
-    use library_api::config::Config;
+    use args::{Args, Command};
+    use clap::Parser;
+    use library_api::config::EffectiveConfig;
+    use library_api::i18n::set_default_language;
     use library_api::init::{init_logger, run};
 
-    // Cargar configuración
-    let config = Config::get_instance();
+    // Cargar configuración: valores por defecto, archivo, entorno y opciones
+    let args = Args::parse();
+    let config = EffectiveConfig::load(&args.config).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    set_default_language(config.config.default_language);
+
+    if let Some(Command::Config { command }) = args.command {
+        command.run(&config);
+        return;
+    }
 
-    init_logger(&config.log_level);
-    run(config.api_port)
+    init_logger(&config.config.log_level, config.config.log_format);
+    run(&config.config)
         .await
         .expect("Error al iniciar el servidor");
 }
@@ -0,0 +1,28 @@
+use std::{path::Path, process::Command};
+
+/// Expone el commit compilado como `LIBRARY_API_GIT_HASH` para el endpoint `/info`.
+/// `GIT_HASH` permite indicarlo al compilar sin el repositorio, por ejemplo en una imagen.
+fn main() {
+    let hash = std::env::var("GIT_HASH")
+        .ok()
+        .or_else(|| {
+            Command::new("git")
+                .args(["rev-parse", "--short=12", "HEAD"])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .and_then(|output| String::from_utf8(output.stdout).ok())
+        })
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=LIBRARY_API_GIT_HASH={hash}");
+    println!("cargo:rerun-if-env-changed=GIT_HASH");
+
+    for path in [".git/HEAD", ".git/refs/heads"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+}
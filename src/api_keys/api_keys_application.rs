@@ -1,11 +1,25 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
 
 use email_pass::Password;
 use prefixed_api_key::PrefixedApiKeyController;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::audit::{
+    audit_application::AuditLog,
+    audit_domain::{Actor, AuditAction},
+};
 
 use super::{
     ApiKeyError,
-    api_keys_domain::{_ApiKey, ApiKey, ApiKeyRepository},
+    api_keys_domain::{_ApiKey, ApiKey, ApiKeyRepository, ApiKeyScope, StoredApiKey},
 };
 
 pub struct ApiKeyGenerator;
@@ -32,21 +46,160 @@ impl ApiKeyGenerator {
     }
 }
 
+/// Tiempo que se recuerda una ApiKey verificada
+pub const DEFAULT_API_KEY_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Cantidad máxima de ApiKeys verificadas que se recuerdan
+pub const API_KEY_CACHE_CAPACITY: usize = 10_000;
+
+/// Verificaciones exitosas recientes, para no repetir el hash lento de la ApiKey en cada
+/// petición. Solo guarda el SHA-256 de la ApiKey completa, nunca el token, y el del token
+/// encriptado contra el que se verificó: si la ApiKey se revoca o se rota, aunque sea desde
+/// otro proceso, la verificación guardada deja de coincidir.
+pub struct ApiKeyCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<[u8; 32], CachedApiKey>>,
+    /// Aumenta con cada invalidación, ver [`ApiKeyCache::insert`]
+    generation: AtomicU64,
+}
+
+struct CachedApiKey {
+    id: String,
+    stored: [u8; 32],
+    expires_at: Instant,
+}
+
+impl ApiKeyCache {
+    /// Un `ttl` de cero desactiva la caché
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(Duration::ZERO, 0)
+    }
+
+    fn digest(value: &str) -> [u8; 32] {
+        Sha256::digest(value.as_bytes()).into()
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<[u8; 32], CachedApiKey>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// La ApiKey se verificó hace menos de `ttl` contra el mismo token guardado
+    pub fn contains(&self, api_key: &ApiKey, stored: &StoredApiKey) -> bool {
+        if self.ttl.is_zero() {
+            return false;
+        }
+
+        let digest = Self::digest(&api_key.to_string());
+        let mut entries = self.entries();
+        match entries.get(&digest) {
+            Some(entry) if entry.expires_at <= Instant::now() => {
+                entries.remove(&digest);
+                false
+            }
+            Some(entry) => entry.stored == Self::digest(&stored.token),
+            None => false,
+        }
+    }
+
+    /// Generación actual; se obtiene antes de verificar una ApiKey para pasarla a
+    /// [`ApiKeyCache::insert`]
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Recuerda una ApiKey verificada contra `stored`. Si hubo una invalidación desde
+    /// `generation` no se guarda, porque la verificación pudo leer una ApiKey que ya se revocó.
+    pub fn insert(&self, api_key: &ApiKey, stored: &StoredApiKey, generation: u64) {
+        if self.ttl.is_zero() || self.capacity == 0 {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut entries = self.entries();
+        if self.generation() != generation {
+            return;
+        }
+
+        if entries.len() >= self.capacity {
+            entries.retain(|_, entry| entry.expires_at > now);
+        }
+        if entries.len() >= self.capacity {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.expires_at)
+                .map(|(digest, _)| *digest);
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(
+            Self::digest(&api_key.to_string()),
+            CachedApiKey {
+                id: api_key.id().clone(),
+                stored: Self::digest(&stored.token),
+                expires_at: now + self.ttl,
+            },
+        );
+    }
+
+    /// Olvida las verificaciones de la ApiKey `id`
+    pub fn invalidate(&self, id: &str) {
+        let mut entries = self.entries();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        entries.retain(|_, entry| entry.id != id);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for ApiKeyCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_API_KEY_CACHE_TTL, API_KEY_CACHE_CAPACITY)
+    }
+}
+
 #[derive(Clone)]
 pub struct ApiKeyValidationService {
     pub repo: Arc<dyn ApiKeyRepository>,
+    pub cache: Arc<ApiKeyCache>,
 }
 
 impl ApiKeyValidationService {
-    /// Trata de obtener la ApiKey con base en el id y verificar si es válida
-    pub async fn validate(&self, api_key: &ApiKey) -> Result<(), ApiKeyError> {
-        let raw_encrypted = self
+    /// Trata de obtener la ApiKey con base en el id y verificar si es válida; devuelve su
+    /// alcance. La consulta se hace siempre, para notar las revocaciones hechas desde otro
+    /// proceso como la CLI; las verificaciones exitosas se recuerdan en la caché para no
+    /// repetir el hash.
+    pub async fn validate(&self, api_key: &ApiKey) -> Result<ApiKeyScope, ApiKeyError> {
+        let generation = self.cache.generation();
+
+        let stored = self
             .repo
             .find_encrypted(api_key.id())
             .await?
             .ok_or(ApiKeyError::NotFound)?;
 
-        let encrypted = Password::from_encrypt(&raw_encrypted)
+        if self.cache.contains(api_key, &stored) {
+            return Ok(stored.scope);
+        }
+
+        let encrypted = Password::from_encrypt(&stored.token)
             .map_err(|e| ApiKeyError::BadEncryption(e.to_string()))?;
 
         let is_valid = encrypted
@@ -57,22 +210,46 @@ impl ApiKeyValidationService {
             return Err(ApiKeyError::Invalid);
         }
 
-        Ok(())
+        self.cache.insert(api_key, &stored, generation);
+        Ok(stored.scope)
     }
 }
 
 #[derive(Clone)]
 pub struct ApiKeyCreationService {
     pub repo: Arc<dyn ApiKeyRepository>,
+    pub cache: Arc<ApiKeyCache>,
+    pub audit: Arc<AuditLog>,
 }
 
 impl ApiKeyCreationService {
-    /// Crea una nueva ApiKey y la guarda en la base de datos
-    pub async fn create(&self) -> Result<ApiKey, ApiKeyError> {
+    /// Crea una nueva ApiKey sin limitarla a una sucursal y la guarda en la base de datos
+    pub async fn create(&self, actor: &Actor) -> Result<ApiKey, ApiKeyError> {
+        self.create_scoped(ApiKeyScope::All, actor).await
+    }
+
+    /// Crea una nueva ApiKey con el alcance indicado y la guarda en la base de datos
+    pub async fn create_scoped(
+        &self,
+        scope: ApiKeyScope,
+        actor: &Actor,
+    ) -> Result<ApiKey, ApiKeyError> {
         let api_key = ApiKeyGenerator::generate()?;
         let encrypted = api_key.encrypt_token()?;
 
-        self.repo.save(api_key.id(), &encrypted).await?;
+        self.repo.save(api_key.id(), &encrypted, scope).await?;
+        // Si el id ya existía su token cambió, las verificaciones anteriores dejan de valer
+        self.cache.invalidate(api_key.id());
+        // Solo se registra el identificador, nunca el token
+        self.audit
+            .record(
+                actor,
+                AuditAction::ApiKeyCreated,
+                api_key.id(),
+                None,
+                Some(json!({ "id": api_key.id(), "location": scope.location() })),
+            )
+            .await;
 
         Ok(api_key)
     }
@@ -81,13 +258,287 @@ impl ApiKeyCreationService {
 #[derive(Clone)]
 pub struct ApiKeyDeletionService {
     pub repo: Arc<dyn ApiKeyRepository>,
+    pub cache: Arc<ApiKeyCache>,
+    pub audit: Arc<AuditLog>,
 }
 
 impl ApiKeyDeletionService {
-    /// Elimina una ApiKey de la base de datos
-    pub async fn delete(&self, id: &str) -> Result<(), ApiKeyError> {
+    /// Elimina una ApiKey de la base de datos; deja de aceptarse de inmediato
+    pub async fn delete(&self, id: &str, actor: &Actor) -> Result<(), ApiKeyError> {
+        let existed = self.repo.find_encrypted(id).await?.is_some();
+
         self.repo.delete(id).await?;
+        self.cache.invalidate(id);
+
+        if existed {
+            self.audit
+                .record(
+                    actor,
+                    AuditAction::ApiKeyRevoked,
+                    id,
+                    Some(json!({ "id": id })),
+                    None,
+                )
+                .await;
+        }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api_keys::api_keys_domain::EncryptedApiKey,
+        audit::{
+            audit_domain::{AuditFilter, AuditRepository},
+            audit_infrastructure::MemoryAuditRepository,
+        },
+    };
+
+    /// Repositorio en memoria
+    #[derive(Default)]
+    struct MemoryRepository {
+        keys: Mutex<HashMap<String, StoredApiKey>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ApiKeyRepository for MemoryRepository {
+        async fn find_encrypted(&self, id: &str) -> Result<Option<StoredApiKey>, ApiKeyError> {
+            Ok(self.keys.lock().unwrap().get(id).cloned())
+        }
+
+        async fn save(
+            &self,
+            id: &str,
+            key: &EncryptedApiKey,
+            scope: ApiKeyScope,
+        ) -> Result<(), ApiKeyError> {
+            self.keys.lock().unwrap().insert(
+                id.to_string(),
+                StoredApiKey {
+                    token: key.to_string(),
+                    scope,
+                },
+            );
+            Ok(())
+        }
+
+        async fn delete(&self, id: &str) -> Result<(), ApiKeyError> {
+            self.keys.lock().unwrap().remove(id);
+            Ok(())
+        }
+    }
+
+    type Services = (
+        Arc<MemoryRepository>,
+        ApiKeyValidationService,
+        ApiKeyCreationService,
+        ApiKeyDeletionService,
+    );
+
+    fn services(cache: ApiKeyCache) -> Services {
+        services_with_audit(cache, Arc::new(MemoryAuditRepository::default()))
+    }
+
+    fn services_with_audit(cache: ApiKeyCache, audit: Arc<MemoryAuditRepository>) -> Services {
+        let repo = Arc::new(MemoryRepository::default());
+        let cache = Arc::new(cache);
+        let audit = Arc::new(AuditLog { repo: audit });
+
+        (
+            repo.clone(),
+            ApiKeyValidationService {
+                repo: repo.clone(),
+                cache: cache.clone(),
+            },
+            ApiKeyCreationService {
+                repo: repo.clone(),
+                cache: cache.clone(),
+                audit: audit.clone(),
+            },
+            ApiKeyDeletionService { repo, cache, audit },
+        )
+    }
+
+    #[tokio::test]
+    async fn verified_keys_are_cached() {
+        let (repo, validation, creation, _) = services(ApiKeyCache::default());
+        let api_key = creation.create(&Actor::cli()).await.unwrap();
+
+        validation.validate(&api_key).await.unwrap();
+        validation.validate(&api_key).await.unwrap();
+        let stored = repo.find_encrypted(api_key.id()).await.unwrap().unwrap();
+        assert!(validation.cache.contains(&api_key, &stored));
+
+        // Un token incorrecto para el mismo id no se acepta desde la caché
+        let wrong = ApiKey::from(_ApiKey {
+            id: api_key.id().clone(),
+            token: "otro".to_string(),
+        });
+        assert!(matches!(
+            validation.validate(&wrong).await,
+            Err(ApiKeyError::Invalid)
+        ));
+    }
+
+    #[tokio::test]
+    async fn scope_is_returned_and_cached() {
+        let (repo, validation, creation, _) = services(ApiKeyCache::default());
+        let scoped = creation
+            .create_scoped(ApiKeyScope::Location(7), &Actor::cli())
+            .await
+            .unwrap();
+        let unscoped = creation.create(&Actor::cli()).await.unwrap();
+
+        for _ in 0..2 {
+            assert_eq!(
+                validation.validate(&scoped).await.unwrap(),
+                ApiKeyScope::Location(7)
+            );
+        }
+        assert_eq!(
+            validation.validate(&unscoped).await.unwrap(),
+            ApiKeyScope::All
+        );
+
+        // El alcance se lee de la base de datos aunque la verificación esté en la caché
+        let mut stored = repo.find_encrypted(scoped.id()).await.unwrap().unwrap();
+        stored.scope = ApiKeyScope::Location(8);
+        repo.keys
+            .lock()
+            .unwrap()
+            .insert(scoped.id().clone(), stored);
+        assert_eq!(
+            validation.validate(&scoped).await.unwrap(),
+            ApiKeyScope::Location(8)
+        );
+    }
+
+    #[tokio::test]
+    async fn changes_from_other_processes_are_noticed() {
+        let (repo, validation, creation, _) = services(ApiKeyCache::default());
+        let revoked = creation.create(&Actor::cli()).await.unwrap();
+        let rotated = creation.create(&Actor::cli()).await.unwrap();
+        validation.validate(&revoked).await.unwrap();
+        validation.validate(&rotated).await.unwrap();
+
+        // Cambios directos en el repositorio, sin pasar por la caché de este proceso
+        repo.delete(revoked.id()).await.unwrap();
+        let new_token = ApiKey::from(_ApiKey {
+            id: rotated.id().clone(),
+            token: "nuevo".to_string(),
+        });
+        repo.save(
+            rotated.id(),
+            &new_token.encrypt_token().unwrap(),
+            ApiKeyScope::All,
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            validation.validate(&revoked).await,
+            Err(ApiKeyError::NotFound)
+        ));
+        assert!(matches!(
+            validation.validate(&rotated).await,
+            Err(ApiKeyError::Invalid)
+        ));
+        validation.validate(&new_token).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn deletion_invalidates_cache() {
+        let (_, validation, creation, deletion) = services(ApiKeyCache::default());
+        let api_key = creation.create(&Actor::cli()).await.unwrap();
+
+        validation.validate(&api_key).await.unwrap();
+        deletion.delete(api_key.id(), &Actor::cli()).await.unwrap();
+
+        assert!(matches!(
+            validation.validate(&api_key).await,
+            Err(ApiKeyError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn changes_are_audited_without_tokens() {
+        let audit = Arc::new(MemoryAuditRepository::default());
+        let (_, _, creation, deletion) = services_with_audit(ApiKeyCache::default(), audit.clone());
+        let actor = Actor::api_key("admin");
+
+        let api_key = creation.create(&actor).await.unwrap();
+        deletion.delete(api_key.id(), &actor).await.unwrap();
+        // Revocar una ApiKey inexistente no agrega entradas
+        deletion.delete("inexistente", &actor).await.unwrap();
+
+        let log = audit.find(AuditFilter::default(), 1, 10).await.unwrap();
+        let actions: Vec<_> = log.entries.iter().map(|entry| entry.action).collect();
+        assert_eq!(
+            actions,
+            [AuditAction::ApiKeyRevoked, AuditAction::ApiKeyCreated]
+        );
+        assert!(
+            log.entries
+                .iter()
+                .all(|entry| entry.actor == actor && entry.entity_id == *api_key.id())
+        );
+        assert!(
+            !serde_json::to_string(&log.entries)
+                .unwrap()
+                .contains(&api_key.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn disabled_cache_always_verifies() {
+        let (_, validation, creation, _) = services(ApiKeyCache::disabled());
+        let api_key = creation.create(&Actor::cli()).await.unwrap();
+
+        validation.validate(&api_key).await.unwrap();
+        validation.validate(&api_key).await.unwrap();
+        assert!(validation.cache.is_empty());
+    }
+
+    #[test]
+    fn cache_is_bounded_and_expires() {
+        let key = |id: &str| {
+            ApiKey::from(_ApiKey {
+                id: id.to_string(),
+                token: "token".to_string(),
+            })
+        };
+        let stored = StoredApiKey {
+            token: "encriptado".to_string(),
+            scope: ApiKeyScope::All,
+        };
+
+        let cache = ApiKeyCache::new(Duration::from_secs(60), 2);
+        for id in ["a", "b", "c"] {
+            cache.insert(&key(id), &stored, cache.generation());
+        }
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains(&key("a"), &stored));
+        assert!(cache.contains(&key("c"), &stored));
+
+        // Otro token guardado para el mismo id no coincide con la verificación
+        let rotated = StoredApiKey {
+            token: "otro".to_string(),
+            ..stored.clone()
+        };
+        assert!(!cache.contains(&key("c"), &rotated));
+
+        // Una invalidación posterior a la verificación impide guardarla
+        let generation = cache.generation();
+        cache.invalidate("d");
+        cache.insert(&key("d"), &stored, generation);
+        assert!(!cache.contains(&key("d"), &stored));
+
+        let cache = ApiKeyCache::new(Duration::from_nanos(1), 2);
+        cache.insert(&key("a"), &stored, cache.generation());
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(!cache.contains(&key("a"), &stored));
+    }
+}
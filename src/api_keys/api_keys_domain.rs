@@ -23,10 +23,49 @@ impl ApiKey {
     }
 }
 
+/// Sucursales sobre las que una ApiKey puede operar el inventario
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ApiKeyScope {
+    /// Todas las sucursales
+    #[default]
+    All,
+
+    /// Solo la sucursal con el identificador indicado
+    Location(i64),
+}
+
+impl ApiKeyScope {
+    /// Sucursal a la que está limitada la ApiKey, si lo está
+    pub fn location(self) -> Option<i64> {
+        match self {
+            ApiKeyScope::All => None,
+            ApiKeyScope::Location(id) => Some(id),
+        }
+    }
+}
+
+impl From<Option<i64>> for ApiKeyScope {
+    fn from(value: Option<i64>) -> Self {
+        value.map_or(ApiKeyScope::All, ApiKeyScope::Location)
+    }
+}
+
+/// ApiKey guardada: el token encriptado y su alcance
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredApiKey {
+    pub token: String,
+    pub scope: ApiKeyScope,
+}
+
 #[async_trait::async_trait]
 pub trait ApiKeyRepository: Send + Sync {
-    async fn find_encrypted(&self, id: &str) -> Result<Option<String>, ApiKeyError>;
-    async fn save(&self, id: &str, key: &EncryptedApiKey) -> Result<(), ApiKeyError>;
+    async fn find_encrypted(&self, id: &str) -> Result<Option<StoredApiKey>, ApiKeyError>;
+    async fn save(
+        &self,
+        id: &str,
+        key: &EncryptedApiKey,
+        scope: ApiKeyScope,
+    ) -> Result<(), ApiKeyError>;
     async fn delete(&self, id: &str) -> Result<(), ApiKeyError>;
 }
 
@@ -1,9 +1,10 @@
 mod api_key_sqlite_repository;
 mod middlewares;
-mod singletons;
+mod state;
 
 #[cfg(feature = "cli")]
 pub mod cli;
 
 pub use api_key_sqlite_repository::*;
 pub use middlewares::*;
+pub use state::*;
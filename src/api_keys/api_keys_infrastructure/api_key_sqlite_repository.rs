@@ -2,7 +2,7 @@ use sqlx::{Pool, Sqlite, SqlitePool, query, query_as};
 
 use crate::api_keys::{
     ApiKeyError,
-    api_keys_domain::{ApiKey, ApiKeyRepository, EncryptedApiKey},
+    api_keys_domain::{ApiKeyRepository, ApiKeyScope, EncryptedApiKey, StoredApiKey},
 };
 
 #[derive(Clone)]
@@ -20,7 +20,8 @@ impl ApiKeySqliteRepository {
             r#"
             CREATE TABLE IF NOT EXISTS api_keys (
                 id TEXT PRIMARY KEY,
-                token TEXT NOT NULL
+                token TEXT NOT NULL,
+                location_id INTEGER
             )
             "#,
         )
@@ -28,35 +29,68 @@ impl ApiKeySqliteRepository {
         .await
         .map_err(|e| ApiKeyError::DatabaseError(format!("Error al crear tabla api_keys: {}", e)))?;
 
+        // Las ApiKeys creadas antes de las sucursales pueden operar sobre todas
+        let (has_location,) = query_as::<_, (bool,)>(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('api_keys') WHERE name = 'location_id'",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            ApiKeyError::DatabaseError(format!("Error al consultar columnas de api_keys: {}", e))
+        })?;
+
+        if !has_location {
+            query("ALTER TABLE api_keys ADD COLUMN location_id INTEGER")
+                .execute(&self.pool)
+                .await
+                .map_err(|e| {
+                    ApiKeyError::DatabaseError(format!(
+                        "Error al agregar columna location_id: {}",
+                        e
+                    ))
+                })?;
+        }
+
         Ok(())
     }
 }
 
 #[async_trait::async_trait]
 impl ApiKeyRepository for ApiKeySqliteRepository {
-    async fn find_encrypted(&self, id: &str) -> Result<Option<String>, ApiKeyError> {
-        let result = query_as::<_, ApiKey>("SELECT id, token FROM api_keys WHERE id = ?")
-            .bind(id)
-            .fetch_optional(&self.pool)
-            .await
-            .map_err(|e| ApiKeyError::DatabaseError(format!("Error al buscar ApiKey: {}", e)))?;
+    async fn find_encrypted(&self, id: &str) -> Result<Option<StoredApiKey>, ApiKeyError> {
+        let result = query_as::<_, (String, Option<i64>)>(
+            "SELECT token, location_id FROM api_keys WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| ApiKeyError::DatabaseError(format!("Error al buscar ApiKey: {}", e)))?;
 
-        let encrypted_token = result.map(|api_key| api_key.token().clone());
+        let stored = result.map(|(token, location)| StoredApiKey {
+            token,
+            scope: ApiKeyScope::from(location),
+        });
 
-        Ok(encrypted_token)
+        Ok(stored)
     }
 
-    async fn save(&self, id: &str, key: &EncryptedApiKey) -> Result<(), ApiKeyError> {
+    async fn save(
+        &self,
+        id: &str,
+        key: &EncryptedApiKey,
+        scope: ApiKeyScope,
+    ) -> Result<(), ApiKeyError> {
         let encrypted = key.as_str();
 
         query(
             r#"
-            INSERT INTO api_keys (id, token)
-            VALUES (?, ?)
+            INSERT INTO api_keys (id, token, location_id)
+            VALUES (?, ?, ?)
             "#,
         )
         .bind(id)
         .bind(encrypted)
+        .bind(scope.location())
         .execute(&self.pool)
         .await
         .map_err(|e| ApiKeyError::DatabaseError(format!("Error al guardar ApiKey: {}", e)))?;
@@ -78,7 +112,7 @@ impl ApiKeyRepository for ApiKeySqliteRepository {
 #[cfg(test)]
 mod tests {
 
-    use crate::api_keys::api_keys_domain::_ApiKey;
+    use crate::api_keys::api_keys_domain::{_ApiKey, ApiKey};
 
     use super::*;
     use email_pass::Password;
@@ -108,7 +142,7 @@ mod tests {
         let encrypted_token = key.encrypt_token().expect("Failed to encrypt token");
 
         // Act
-        repo.save(id, &encrypted_token)
+        repo.save(id, &encrypted_token, ApiKeyScope::Location(3))
             .await
             .expect("Error al guardar API key");
         let result = repo
@@ -117,9 +151,10 @@ mod tests {
             .expect("Error al encontrar API key");
 
         // Assert
-        assert!(result.is_some(), "API key not found");
+        let stored = result.expect("API key not found");
+        assert_eq!(stored.scope, ApiKeyScope::Location(3));
         let encrypted_token_from_db =
-            Password::from_encrypt(&result.unwrap()).expect("El token no está encriptado");
+            Password::from_encrypt(&stored.token).expect("El token no está encriptado");
 
         let is_valid = encrypted_token_from_db
             .verify_from_raw(key.token())
@@ -141,7 +176,7 @@ mod tests {
         let encrypted_token = key.encrypt_token().expect("Failed to encrypt token");
 
         // Act
-        repo.save(id, &encrypted_token)
+        repo.save(id, &encrypted_token, ApiKeyScope::All)
             .await
             .expect("Failed to save API key");
         repo.delete(id).await.expect("Failed to delete API key");
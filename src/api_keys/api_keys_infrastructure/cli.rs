@@ -1,40 +1,81 @@
-use clap::Parser;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
 
 use crate::{
-    api_keys::api_keys_application::{ApiKeyCreationService, ApiKeyDeletionService},
+    api_keys::api_keys_domain::ApiKeyScope,
+    audit::audit_domain::Actor,
+    config::{ConfigArgs, ConfigCommand, EffectiveConfig},
+    i18n::{set_default_language, tr},
     init,
+    state::AppState,
 };
 
+/// Administración de las API keys de la biblioteca
 #[derive(Debug, Parser)]
 #[command(version)]
 pub struct Cli {
+    #[command(flatten)]
+    config: ConfigArgs,
+
     #[command(subcommand)]
-    action: Action,
+    command: Command,
 }
 
-impl Cli {
-    pub async fn run(self) -> Result<(), Box<dyn std::error::Error>> {
-        match self.action {
-            Action::Gen => {
-                let api_key = ApiKeyCreationService::get_instance().create().await?;
+#[derive(Debug, Subcommand)]
+enum Command {
+    #[command(flatten)]
+    ApiKey(Action),
+
+    /// Consulta la configuración de la API y la CLI
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+
+    /// Borra definitivamente los libros eliminados hace más de los días indicados
+    PurgeBooks {
+        #[arg(long, value_name = "DAYS", default_value_t = 30)]
+        older_than_days: u64,
+    },
+}
+
+impl Action {
+    pub async fn run(self, state: &AppState) -> Result<(), Box<dyn std::error::Error>> {
+        let api_keys = &state.api_keys;
+        match self {
+            Action::Gen { location } => {
+                // La sucursal debe existir para que la ApiKey pueda usarse
+                if let Some(location) = location {
+                    state.books.get_locations.get_location(location).await?;
+                }
+
+                let api_key = api_keys
+                    .creation
+                    .create_scoped(ApiKeyScope::from(location), &Actor::cli())
+                    .await?;
 
                 println!("{api_key}");
             }
             Action::Delete { id } => {
-                ApiKeyDeletionService::get_instance().delete(&id).await?;
+                api_keys.deletion.delete(&id, &Actor::cli()).await?;
 
-                println!("Deleted API key with ID: {}", id);
+                println!("{}", tr!("cli.api_key_deleted", id = id));
             }
         }
         Ok(())
     }
 }
 
-#[derive(Debug, Parser)]
+#[derive(Debug, Subcommand)]
 pub enum Action {
     /// Crea una nueva API key y la muestra en pantalla (alias --gen)
     #[command(alias = "--gen")]
-    Gen,
+    Gen {
+        /// Limita el inventario que puede operar la API key a la sucursal indicada
+        #[arg(long, value_name = "LOCATION-ID")]
+        location: Option<i64>,
+    },
 
     /// Elimina una API key existente con base en su ID (alias --del)
     #[command(alias = "--del")]
@@ -45,7 +86,30 @@ pub enum Action {
 }
 
 pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    init::init_db_services().await;
     let cli = Cli::parse();
-    cli.run().await
+    let config = EffectiveConfig::load(&cli.config)?;
+    set_default_language(config.config.default_language);
+
+    match cli.command {
+        Command::Config { command } => {
+            command.run(&config);
+            Ok(())
+        }
+        Command::ApiKey(action) => {
+            let state = init::init_state(&config.config).await;
+            action.run(&state).await
+        }
+        Command::PurgeBooks { older_than_days } => {
+            let state = init::init_state(&config.config).await;
+            let retention = Duration::from_secs(older_than_days * 24 * 60 * 60);
+            let purged = state
+                .books
+                .purge_books
+                .purge_books(retention, &Actor::cli())
+                .await?;
+
+            println!("{}", tr!("cli.books_purged", count = purged));
+            Ok(())
+        }
+    }
 }
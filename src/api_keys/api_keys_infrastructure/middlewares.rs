@@ -1,15 +1,28 @@
-use std::{borrow::Cow, str::FromStr, sync::Arc};
+use std::{str::FromStr, sync::Arc};
 
 use axum::{
-    Json,
-    extract::Request,
+    extract::{Request, State},
     middleware::Next,
     response::{IntoResponse, Response},
 };
-use http::{StatusCode, header::AUTHORIZATION};
-use serde_json::json;
+use http::{
+    HeaderValue, StatusCode,
+    header::{AUTHORIZATION, WWW_AUTHENTICATE},
+};
+
+use crate::{
+    AppErrorKind, ProblemDetails,
+    api_keys::{
+        ApiKeyError,
+        api_keys_application::ApiKeyValidationService,
+        api_keys_domain::{ApiKey, ApiKeyScope},
+    },
+    i18n::{Language, Localize, request_language, tr_lang},
+    metrics::Metrics,
+    rate_limit::RateLimits,
+};
 
-use crate::api_keys::{api_keys_application::ApiKeyValidationService, api_keys_domain::ApiKey};
+use super::ApiKeysState;
 
 #[derive(Clone)]
 pub struct AuthMiddleware {
@@ -17,13 +30,14 @@ pub struct AuthMiddleware {
 }
 
 impl AuthMiddleware {
-    pub async fn auth_from_header(&self, auth_header: Option<&str>) -> Result<(), AuthError> {
+    /// Valida las credenciales y devuelve el identificador y el alcance de la ApiKey
+    pub async fn auth_from_header(
+        &self,
+        auth_header: Option<&str>,
+    ) -> Result<AuthenticatedKey, AuthError> {
         match AuthHeader::from_header_value(auth_header) {
             AuthHeader::ApiKey(raw_key) => self.api_key_strategy.validate(raw_key).await,
-            AuthHeader::None => Err(AuthError {
-                message: "Se requiere autenticación. Usar formato 'ApiKey YOURKEY'".into(),
-                code: StatusCode::UNAUTHORIZED,
-            }),
+            AuthHeader::None => Err(AuthError::MissingCredentials),
         }
     }
 }
@@ -50,51 +64,171 @@ pub struct ApiKeyValidationStrategy {
 }
 
 impl ApiKeyValidationStrategy {
-    pub async fn validate(&self, raw_api_key: &str) -> Result<(), AuthError> {
-        let key = ApiKey::from_str(raw_api_key).map_err(|e| AuthError {
-            message: format!("ApiKey inválida: {}", e).into(),
-            code: StatusCode::UNPROCESSABLE_ENTITY,
-        })?;
-
-        self.validator.validate(&key).await.map_err(|e| AuthError {
-            message: format!("Error al validar ApiKey: {e}").into(),
-            code: StatusCode::from(e.kind()),
-        })
+    pub async fn validate(&self, raw_api_key: &str) -> Result<AuthenticatedKey, AuthError> {
+        let key =
+            ApiKey::from_str(raw_api_key).map_err(|e| AuthError::MalformedApiKey(e.to_string()))?;
+
+        let scope = self
+            .validator
+            .validate(&key)
+            .await
+            .map_err(AuthError::Validation)?;
+
+        Ok(AuthenticatedKey { id: key.id, scope })
     }
 }
 
-pub struct AuthError {
-    message: Cow<'static, str>,
-    code: StatusCode,
+#[derive(Debug)]
+pub enum AuthError {
+    /// No se envió la cabecera `Authorization` con el formato esperado
+    MissingCredentials,
+
+    /// La ApiKey no tiene el formato `LibraryApi_{id}_{token}`
+    MalformedApiKey(String),
+
+    /// La ApiKey no pudo validarse
+    Validation(ApiKeyError),
+
+    /// La ApiKey está limitada a una sucursal y la ruta no admite ApiKeys limitadas
+    ScopeForbidden,
+}
+
+impl AuthError {
+    pub fn kind(&self) -> AppErrorKind {
+        match self {
+            AuthError::MissingCredentials | AuthError::MalformedApiKey(_) => {
+                AppErrorKind::Authentication
+            }
+            AuthError::Validation(e) => e.kind(),
+            AuthError::ScopeForbidden => AppErrorKind::Forbidden,
+        }
+    }
+
+    /// Código estable que identifica el error, independiente del mensaje
+    pub fn code(&self) -> &'static str {
+        match self {
+            AuthError::MissingCredentials => "auth.missing_credentials",
+            AuthError::MalformedApiKey(_) => "auth.malformed_api_key",
+            AuthError::Validation(e) => e.code(),
+            AuthError::ScopeForbidden => "auth.scope_forbidden",
+        }
+    }
+}
+
+impl Localize for AuthError {
+    fn localize(&self, lang: Language) -> String {
+        match self {
+            AuthError::MissingCredentials => tr_lang!(lang, "auth.missing_credentials"),
+            AuthError::MalformedApiKey(detail) => {
+                tr_lang!(lang, "auth.malformed_api_key", detail = detail)
+            }
+            AuthError::Validation(e) => {
+                tr_lang!(lang, "auth.validation", source = e.localize(lang))
+            }
+            AuthError::ScopeForbidden => tr_lang!(lang, "auth.scope_forbidden"),
+        }
+    }
 }
 
 impl IntoResponse for AuthError {
     fn into_response(self) -> Response {
-        let body = json!({
-            "message": self.message,
-        });
+        let kind = self.kind();
+        let status = StatusCode::from(kind);
+        let detail = self.localize(request_language());
+        let mut response = ProblemDetails::new(status, kind, self.code(), detail).into_response();
 
-        (self.code, Json(body)).into_response()
+        // Indica al cliente el esquema de autenticación esperado
+        if status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(WWW_AUTHENTICATE, HeaderValue::from_static("ApiKey"));
+        }
+
+        response
     }
 }
 
-pub async fn api_key_middleware(req: Request, next: Next) -> Result<Response, Response> {
+/// ApiKey con la que se autenticó la petición, disponible como extensión para los controladores
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedKey {
+    pub id: String,
+
+    /// Sucursales sobre las que la ApiKey puede operar el inventario
+    pub scope: ApiKeyScope,
+}
+
+/// Autentica la petición con una ApiKey sin límite de sucursal. Las ApiKeys limitadas a una
+/// sucursal se rechazan con `403`; solo pueden usar las rutas protegidas con
+/// [`location_api_key_middleware`].
+pub async fn api_key_middleware(
+    State(api_keys): State<ApiKeysState>,
+    State(metrics): State<Metrics>,
+    State(limits): State<RateLimits>,
+    req: Request,
+    next: Next,
+) -> Result<Response, Response> {
+    authenticate(api_keys, metrics, limits, req, next, false).await
+}
+
+/// Autentica la petición aceptando también ApiKeys limitadas a una sucursal, para las rutas
+/// de inventario cuyos controladores respetan el alcance de [`AuthenticatedKey`]
+pub async fn location_api_key_middleware(
+    State(api_keys): State<ApiKeysState>,
+    State(metrics): State<Metrics>,
+    State(limits): State<RateLimits>,
+    req: Request,
+    next: Next,
+) -> Result<Response, Response> {
+    authenticate(api_keys, metrics, limits, req, next, true).await
+}
+
+async fn authenticate(
+    api_keys: ApiKeysState,
+    metrics: Metrics,
+    limits: RateLimits,
+    mut req: Request,
+    next: Next,
+    allow_scoped: bool,
+) -> Result<Response, Response> {
+    // Los clientes que agotaron sus intentos fallidos se rechazan antes de verificar la ApiKey
+    let client_ip = limits.client_ip(&req);
+    if let Some(decision) = client_ip.and_then(|ip| limits.auth_blocked(ip)) {
+        return Err(decision.reject());
+    }
+
     // Extraer el header de autorización
     let auth_header = req
         .headers()
         .get(AUTHORIZATION)
         .and_then(|header| header.to_str().ok());
 
-    let auth_middleware = AuthMiddleware::get_instance();
-
     // Validar la API key
-    match auth_middleware.auth_from_header(auth_header).await {
-        Ok(()) => {
-            // Si la autenticación es exitosa, continuar con la solicitud
-            Ok(next.run(req).await)
+    match api_keys.auth.auth_from_header(auth_header).await {
+        Ok(key) => {
+            if !allow_scoped && key.scope != ApiKeyScope::All {
+                return Err(AuthError::ScopeForbidden.into_response());
+            }
+
+            // Si la autenticación es exitosa, aplicar el límite de la ApiKey y continuar
+            let decision = limits.check_api_key(&key.id);
+            req.extensions_mut().insert(key);
+            let Some(decision) = decision else {
+                return Ok(next.run(req).await);
+            };
+            if !decision.allowed {
+                return Err(decision.reject());
+            }
+
+            let mut response = next.run(req).await;
+            decision.apply(response.headers_mut());
+            Ok(response)
         }
         Err(err) => {
             // Si la autenticación falla, devolver el error
+            metrics.auth_failed(err.code());
+            if let Some(ip) = client_ip.filter(|_| err.kind() == AppErrorKind::Authentication) {
+                limits.auth_failed(ip);
+            }
             Err(err.into_response())
         }
     }
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use crate::{
+    api_keys::{
+        api_keys_application::{
+            ApiKeyCache, ApiKeyCreationService, ApiKeyDeletionService, ApiKeyValidationService,
+        },
+        api_keys_domain::ApiKeyRepository,
+    },
+    audit::audit_application::AuditLog,
+};
+
+use super::{ApiKeyValidationStrategy, AuthMiddleware};
+
+/// Servicios de ApiKeys compartidos por el middleware de autenticación y la CLI
+#[derive(Clone)]
+pub struct ApiKeysState {
+    pub auth: Arc<AuthMiddleware>,
+    pub creation: Arc<ApiKeyCreationService>,
+    pub deletion: Arc<ApiKeyDeletionService>,
+    pub repo: Arc<dyn ApiKeyRepository>,
+    pub audit: Arc<AuditLog>,
+}
+
+impl ApiKeysState {
+    /// Construye todos los servicios sobre el mismo repositorio, con la caché por defecto
+    pub fn new(repo: Arc<dyn ApiKeyRepository>, audit: Arc<AuditLog>) -> Self {
+        Self::with_cache(repo, audit, ApiKeyCache::default())
+    }
+
+    /// Construye todos los servicios sobre el mismo repositorio y la misma caché de
+    /// verificaciones, para que las revocaciones la invaliden
+    pub fn with_cache(
+        repo: Arc<dyn ApiKeyRepository>,
+        audit: Arc<AuditLog>,
+        cache: ApiKeyCache,
+    ) -> Self {
+        let cache = Arc::new(cache);
+        let validator = Arc::new(ApiKeyValidationService {
+            repo: repo.clone(),
+            cache: cache.clone(),
+        });
+
+        Self {
+            auth: Arc::new(AuthMiddleware {
+                api_key_strategy: Arc::new(ApiKeyValidationStrategy { validator }),
+            }),
+            creation: Arc::new(ApiKeyCreationService {
+                repo: repo.clone(),
+                cache: cache.clone(),
+                audit: audit.clone(),
+            }),
+            deletion: Arc::new(ApiKeyDeletionService {
+                repo: repo.clone(),
+                cache,
+                audit: audit.clone(),
+            }),
+            repo,
+            audit,
+        }
+    }
+}
@@ -1,37 +1,70 @@
-use crate::AppErrorKind;
+use std::fmt;
+
+use crate::{
+    AppErrorKind,
+    i18n::{Language, Localize, default_language, tr_lang},
+};
 
 #[derive(Debug, Clone, thiserror::Error, derive_error_kind::ErrorKind)]
 #[error_kind(ApiKeyErrorKind)]
 pub enum ApiKeyError {
-    #[error("Error al encriptar ApiKey: {0}")]
     #[error_kind(AppErrorKind, Application)]
     Encryption(String),
 
-    #[error("Error al configurar ApiKey: {0}")]
     #[error_kind(AppErrorKind, Application)]
     Configuration(#[from] prefixed_api_key::BuilderError),
 
-    #[error("Error al generar ApiKey: {0}")]
     #[error_kind(AppErrorKind, Application)]
     Generation(String),
 
-    #[error("ApiKey no encontrado")]
-    #[error_kind(AppErrorKind, NotFound)]
+    #[error_kind(AppErrorKind, Authentication)]
     NotFound,
 
-    #[error("La ApiKey no fue correctamente encriptada: {0}")]
     #[error_kind(AppErrorKind, Infrastructure)]
     BadEncryption(String),
 
-    #[error("Error al verificar hash de la ApiKey: {0}")]
     #[error_kind(AppErrorKind, Application)]
     HashVerification(String),
 
-    #[error("ApiKey no válida")]
-    #[error_kind(AppErrorKind, Application)]
+    #[error_kind(AppErrorKind, Authentication)]
     Invalid,
 
-    #[error("Error al interactuar con la base de datos: {0}")]
     #[error_kind(AppErrorKind, Infrastructure)]
     DatabaseError(String),
 }
+
+impl ApiKeyError {
+    /// Código estable que identifica el error, independiente del mensaje
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiKeyError::Encryption(_) => "api_key.encryption",
+            ApiKeyError::Configuration(_) => "api_key.configuration",
+            ApiKeyError::Generation(_) => "api_key.generation",
+            ApiKeyError::NotFound => "api_key.not_found",
+            ApiKeyError::BadEncryption(_) => "api_key.bad_encryption",
+            ApiKeyError::HashVerification(_) => "api_key.hash_verification",
+            ApiKeyError::Invalid => "api_key.invalid",
+            ApiKeyError::DatabaseError(_) => "api_key.database_error",
+        }
+    }
+}
+
+impl Localize for ApiKeyError {
+    fn localize(&self, lang: Language) -> String {
+        match self {
+            ApiKeyError::Encryption(detail)
+            | ApiKeyError::Generation(detail)
+            | ApiKeyError::BadEncryption(detail)
+            | ApiKeyError::HashVerification(detail)
+            | ApiKeyError::DatabaseError(detail) => tr_lang!(lang, self.code(), detail = detail),
+            ApiKeyError::Configuration(e) => tr_lang!(lang, self.code(), detail = e),
+            ApiKeyError::NotFound | ApiKeyError::Invalid => tr_lang!(lang, self.code()),
+        }
+    }
+}
+
+impl fmt::Display for ApiKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.localize(default_language()))
+    }
+}
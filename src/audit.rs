@@ -0,0 +1,6 @@
+pub mod audit_application;
+pub mod audit_domain;
+pub mod audit_infrastructure;
+mod errors;
+
+pub use errors::*;
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::OffsetDateTime;
+use tracing::error;
+use utoipa::IntoParams;
+
+use crate::{i18n::tr, request_id::current_request_id};
+
+use super::{
+    AuditError,
+    audit_domain::{
+        Actor, AuditAction, AuditFilter, AuditRepository, EntityType, NewAuditEntry,
+        PaginatedAuditEntries,
+    },
+};
+
+/// Registro de auditoría compartido por los casos de uso que modifican datos
+pub struct AuditLog {
+    pub repo: Arc<dyn AuditRepository>,
+}
+
+impl AuditLog {
+    /// Agrega una entrada con el estado de la entidad antes y después del cambio.
+    /// El cambio ya se aplicó, por lo que un fallo al registrarlo solo se informa en los logs.
+    pub async fn record(
+        &self,
+        actor: &Actor,
+        action: AuditAction,
+        entity_id: impl ToString,
+        before: Option<Value>,
+        after: Option<Value>,
+    ) {
+        let entity_id = entity_id.to_string();
+        let entry = NewAuditEntry {
+            timestamp: OffsetDateTime::now_utc(),
+            actor: actor.clone(),
+            action,
+            entity_id: entity_id.clone(),
+            before,
+            after,
+            request_id: current_request_id(),
+        };
+
+        if let Err(e) = self.repo.append(entry).await {
+            error!(
+                "{}",
+                tr!(
+                    "log.audit_failed",
+                    action = action,
+                    id = entity_id,
+                    error = e
+                )
+            );
+        }
+    }
+}
+
+/// Estado serializado de una entidad para guardarlo en la auditoría
+pub fn snapshot<T: Serialize>(value: &T) -> Option<Value> {
+    serde_json::to_value(value).ok()
+}
+
+#[derive(Clone)]
+pub struct ListAuditEntriesUseCase {
+    pub repo: Arc<dyn AuditRepository>,
+}
+
+impl ListAuditEntriesUseCase {
+    /// Consulta la auditoría, de la entrada más reciente a la más antigua
+    pub async fn list_entries(
+        &self,
+        dto: ListAuditEntriesDto,
+    ) -> Result<PaginatedAuditEntries, AuditError> {
+        let filter = AuditFilter {
+            actor: dto.actor,
+            action: dto.action,
+            entity_type: dto.entity_type,
+            entity_id: dto.entity_id,
+            since: dto.since,
+            until: dto.until,
+        };
+
+        self.repo.find(filter, dto.page, dto.page_size).await
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListAuditEntriesDto {
+    /// Quién realizó el cambio, por ejemplo `api_key:Ab12Cd` o `cli`
+    pub actor: Option<String>,
+
+    /// Tipo de cambio
+    pub action: Option<AuditAction>,
+
+    /// Tipo de entidad afectada
+    pub entity_type: Option<EntityType>,
+
+    /// Identificador de la entidad afectada
+    pub entity_id: Option<String>,
+
+    /// Desde esta fecha, inclusive (RFC 3339)
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub since: Option<OffsetDateTime>,
+
+    /// Hasta esta fecha, sin incluirla (RFC 3339)
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub until: Option<OffsetDateTime>,
+
+    /// Número de página, comenzando en 1
+    #[serde(default = "default_page")]
+    #[param(minimum = 1, default = 1)]
+    pub page: u32,
+
+    /// Cantidad de entradas por página
+    #[serde(default = "default_page_size")]
+    #[param(minimum = 1, maximum = 255, default = 20)]
+    pub page_size: u8,
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+fn default_page_size() -> u8 {
+    20
+}
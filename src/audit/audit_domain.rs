@@ -0,0 +1,185 @@
+use std::fmt;
+
+use parse_display::{Display, FromStr};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+
+use super::AuditError;
+
+/// Quién realizó un cambio: `api_key:{id}` para peticiones autenticadas o `cli`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+#[schema(value_type = String, example = "api_key:Ab12Cd")]
+pub struct Actor(String);
+
+impl Actor {
+    pub fn api_key(id: &str) -> Self {
+        Self(format!("api_key:{id}"))
+    }
+
+    pub fn cli() -> Self {
+        Self("cli".to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Actor {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for Actor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Tipo de entidad afectada por un cambio
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Display, FromStr, Serialize, Deserialize, ToSchema,
+)]
+#[display(style = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum EntityType {
+    Book,
+    Author,
+    Publisher,
+    Subject,
+    Location,
+    ApiKey,
+}
+
+/// Cambio registrado en la auditoría
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Display, FromStr, Serialize, Deserialize, ToSchema,
+)]
+#[display(style = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    BookCreated,
+    BookUpdated,
+    BookDeleted,
+    BookRestored,
+    BookPurged,
+    StockAdjusted,
+    StockTransferred,
+    AuthorRenamed,
+    AuthorsMerged,
+    PublishersMerged,
+    SubjectCreated,
+    LocationCreated,
+    ApiKeyCreated,
+    ApiKeyRevoked,
+}
+
+impl AuditAction {
+    pub fn entity_type(self) -> EntityType {
+        match self {
+            AuditAction::BookCreated
+            | AuditAction::BookUpdated
+            | AuditAction::BookDeleted
+            | AuditAction::BookRestored
+            | AuditAction::BookPurged
+            | AuditAction::StockAdjusted
+            | AuditAction::StockTransferred => EntityType::Book,
+            AuditAction::AuthorRenamed | AuditAction::AuthorsMerged => EntityType::Author,
+            AuditAction::PublishersMerged => EntityType::Publisher,
+            AuditAction::SubjectCreated => EntityType::Subject,
+            AuditAction::LocationCreated => EntityType::Location,
+            AuditAction::ApiKeyCreated | AuditAction::ApiKeyRevoked => EntityType::ApiKey,
+        }
+    }
+}
+
+/// Cambio por registrar; el repositorio le asigna el identificador
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewAuditEntry {
+    pub timestamp: OffsetDateTime,
+    pub actor: Actor,
+    pub action: AuditAction,
+    pub entity_id: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub request_id: Option<String>,
+}
+
+/// Entrada de la auditoría. Las entradas solo se agregan, nunca se modifican ni eliminan.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AuditEntry {
+    pub id: i64,
+
+    #[serde(with = "time::serde::rfc3339")]
+    #[schema(value_type = String, format = DateTime)]
+    pub timestamp: OffsetDateTime,
+
+    pub actor: Actor,
+    pub action: AuditAction,
+    pub entity_type: EntityType,
+    pub entity_id: String,
+
+    /// Estado de la entidad antes del cambio
+    #[schema(value_type = Option<Object>)]
+    pub before: Option<Value>,
+
+    /// Estado de la entidad después del cambio
+    #[schema(value_type = Option<Object>)]
+    pub after: Option<Value>,
+
+    /// Identificador de la petición que originó el cambio, ver `X-Request-Id`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// Filtros de consulta; los campos vacíos no filtran
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditFilter {
+    pub actor: Option<String>,
+    pub action: Option<AuditAction>,
+    pub entity_type: Option<EntityType>,
+    pub entity_id: Option<String>,
+    pub since: Option<OffsetDateTime>,
+    pub until: Option<OffsetDateTime>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.actor
+            .as_ref()
+            .is_none_or(|a| a == entry.actor.as_str())
+            && self.action.is_none_or(|a| a == entry.action)
+            && self.entity_type.is_none_or(|t| t == entry.entity_type)
+            && self
+                .entity_id
+                .as_ref()
+                .is_none_or(|id| *id == entry.entity_id)
+            && self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.until.is_none_or(|until| entry.timestamp < until)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaginatedAuditEntries {
+    pub entries: Vec<AuditEntry>,
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u8,
+}
+
+#[async_trait::async_trait]
+pub trait AuditRepository: Send + Sync {
+    async fn append(&self, entry: NewAuditEntry) -> Result<(), AuditError>;
+
+    /// Entradas que cumplen el filtro, de la más reciente a la más antigua
+    async fn find(
+        &self,
+        filter: AuditFilter,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedAuditEntries, AuditError>;
+}
@@ -0,0 +1,9 @@
+mod audit_sqlite_repository;
+mod memory_audit_repository;
+mod state;
+
+pub mod controllers;
+
+pub use audit_sqlite_repository::*;
+pub use memory_audit_repository::*;
+pub use state::*;
@@ -0,0 +1,319 @@
+use std::cmp::max;
+
+use serde_json::Value;
+use sqlx::{FromRow, Pool, QueryBuilder, Sqlite, SqlitePool, query};
+use time::OffsetDateTime;
+
+use crate::audit::{
+    AuditError,
+    audit_domain::{
+        AuditEntry, AuditFilter, AuditRepository, NewAuditEntry, PaginatedAuditEntries,
+    },
+};
+
+#[derive(Clone)]
+pub struct AuditSqliteRepository {
+    pub pool: Pool<Sqlite>,
+}
+
+impl AuditSqliteRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn init_table(&self) -> Result<(), AuditError> {
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp INTEGER NOT NULL,
+                actor TEXT NOT NULL,
+                action TEXT NOT NULL,
+                entity_type TEXT NOT NULL,
+                entity_id TEXT NOT NULL,
+                before_state TEXT,
+                after_state TEXT,
+                request_id TEXT
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AuditError::DatabaseError(format!("Error al crear tabla audit_log: {}", e)))?;
+
+        query("CREATE INDEX IF NOT EXISTS audit_log_entity ON audit_log (entity_type, entity_id)")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AuditError::DatabaseError(format!("Error al crear índice de audit_log: {}", e))
+            })?;
+
+        // La auditoría solo admite inserciones
+        for operation in ["UPDATE", "DELETE"] {
+            query(&format!(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS audit_log_no_{name}
+                BEFORE {operation} ON audit_log
+                BEGIN
+                    SELECT RAISE(ABORT, 'audit_log solo admite inserciones');
+                END
+                "#,
+                name = operation.to_lowercase(),
+            ))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AuditError::DatabaseError(format!("Error al crear trigger de audit_log: {}", e))
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Agrega las condiciones del filtro a una consulta que ya incluye `WHERE 1 = 1`
+fn push_filter(builder: &mut QueryBuilder<'_, Sqlite>, filter: &AuditFilter) {
+    if let Some(actor) = &filter.actor {
+        builder.push(" AND actor = ").push_bind(actor.clone());
+    }
+    if let Some(action) = filter.action {
+        builder.push(" AND action = ").push_bind(action.to_string());
+    }
+    if let Some(entity_type) = filter.entity_type {
+        builder
+            .push(" AND entity_type = ")
+            .push_bind(entity_type.to_string());
+    }
+    if let Some(entity_id) = &filter.entity_id {
+        builder
+            .push(" AND entity_id = ")
+            .push_bind(entity_id.clone());
+    }
+    if let Some(since) = filter.since {
+        builder
+            .push(" AND timestamp >= ")
+            .push_bind(unix_millis(since));
+    }
+    if let Some(until) = filter.until {
+        builder
+            .push(" AND timestamp < ")
+            .push_bind(unix_millis(until));
+    }
+}
+
+fn unix_millis(timestamp: OffsetDateTime) -> i64 {
+    (timestamp.unix_timestamp_nanos() / 1_000_000) as i64
+}
+
+fn to_json(value: Option<Value>) -> Option<String> {
+    value.map(|value| value.to_string())
+}
+
+/// DTO para la tabla audit_log
+#[derive(Debug, FromRow)]
+struct DbAuditEntry {
+    id: i64,
+    timestamp: i64,
+    actor: String,
+    action: String,
+    entity_type: String,
+    entity_id: String,
+    before_state: Option<String>,
+    after_state: Option<String>,
+    request_id: Option<String>,
+}
+
+impl DbAuditEntry {
+    fn into_domain(self) -> Result<AuditEntry, AuditError> {
+        let invalid = |field: &str, e: &dyn std::fmt::Display| {
+            AuditError::Serialization(format!("Error al leer {field} de la auditoría: {e}"))
+        };
+        let json = |field: &str, value: Option<String>| {
+            value
+                .map(|value| serde_json::from_str(&value).map_err(|e| invalid(field, &e)))
+                .transpose()
+        };
+
+        Ok(AuditEntry {
+            id: self.id,
+            timestamp: OffsetDateTime::from_unix_timestamp_nanos(
+                i128::from(self.timestamp) * 1_000_000,
+            )
+            .map_err(|e| invalid("timestamp", &e))?,
+            actor: self.actor.into(),
+            action: self.action.parse().map_err(|e| invalid("action", &e))?,
+            entity_type: self
+                .entity_type
+                .parse()
+                .map_err(|e| invalid("entity_type", &e))?,
+            entity_id: self.entity_id,
+            before: json("before_state", self.before_state)?,
+            after: json("after_state", self.after_state)?,
+            request_id: self.request_id,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditRepository for AuditSqliteRepository {
+    async fn append(&self, entry: NewAuditEntry) -> Result<(), AuditError> {
+        query(
+            r#"
+            INSERT INTO audit_log
+                (timestamp, actor, action, entity_type, entity_id, before_state, after_state, request_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(unix_millis(entry.timestamp))
+        .bind(entry.actor.as_str())
+        .bind(entry.action.to_string())
+        .bind(entry.action.entity_type().to_string())
+        .bind(entry.entity_id)
+        .bind(to_json(entry.before))
+        .bind(to_json(entry.after))
+        .bind(entry.request_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AuditError::DatabaseError(format!("Error al registrar auditoría: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find(
+        &self,
+        filter: AuditFilter,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedAuditEntries, AuditError> {
+        let page = max(page, 1); // prevenimos un substract-overflow
+        let offset = (page - 1) as i64 * page_size as i64;
+
+        let mut builder = QueryBuilder::new("SELECT * FROM audit_log WHERE 1 = 1");
+        push_filter(&mut builder, &filter);
+        builder
+            .push(" ORDER BY id DESC LIMIT ")
+            .push_bind(page_size as i64)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let rows = builder
+            .build_query_as::<DbAuditEntry>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AuditError::DatabaseError(format!("Error al buscar auditoría: {}", e)))?;
+
+        let mut builder = QueryBuilder::new("SELECT COUNT(*) FROM audit_log WHERE 1 = 1");
+        push_filter(&mut builder, &filter);
+        let (total,) = builder
+            .build_query_as::<(i64,)>()
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AuditError::DatabaseError(format!("Error al contar auditoría: {}", e)))?;
+
+        Ok(PaginatedAuditEntries {
+            entries: rows
+                .into_iter()
+                .map(DbAuditEntry::into_domain)
+                .collect::<Result<_, _>>()?,
+            total: total as u64,
+            page,
+            page_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use time::Duration;
+
+    use super::*;
+    use crate::audit::audit_domain::{Actor, AuditAction, EntityType};
+
+    async fn setup_test_db() -> AuditSqliteRepository {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .expect("Failed to create in-memory SQLite database");
+
+        let repo = AuditSqliteRepository::new(pool);
+        repo.init_table().await.expect("Failed to initialize table");
+
+        repo
+    }
+
+    fn entry(action: AuditAction, entity_id: &str, timestamp: OffsetDateTime) -> NewAuditEntry {
+        NewAuditEntry {
+            timestamp,
+            actor: Actor::api_key("abc"),
+            action,
+            entity_id: entity_id.to_string(),
+            before: None,
+            after: Some(json!({ "id": entity_id })),
+            request_id: Some("req-1".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn entries_are_filtered_newest_first() {
+        let repo = setup_test_db().await;
+        let now = OffsetDateTime::now_utc();
+
+        repo.append(entry(
+            AuditAction::BookCreated,
+            "1",
+            now - Duration::hours(2),
+        ))
+        .await
+        .unwrap();
+        repo.append(entry(AuditAction::BookDeleted, "1", now))
+            .await
+            .unwrap();
+        repo.append(entry(AuditAction::ApiKeyCreated, "abc", now))
+            .await
+            .unwrap();
+
+        let all = repo.find(AuditFilter::default(), 1, 10).await.unwrap();
+        assert_eq!(all.total, 3);
+        assert_eq!(all.entries[0].action, AuditAction::ApiKeyCreated);
+        assert_eq!(all.entries[0].entity_type, EntityType::ApiKey);
+        assert_eq!(all.entries[2].after, Some(json!({ "id": "1" })));
+
+        let filter = AuditFilter {
+            entity_type: Some(EntityType::Book),
+            since: Some(now - Duration::hours(1)),
+            ..Default::default()
+        };
+        let recent_books = repo.find(filter, 1, 10).await.unwrap();
+        assert_eq!(recent_books.total, 1);
+        assert_eq!(recent_books.entries[0].action, AuditAction::BookDeleted);
+
+        let page = repo.find(AuditFilter::default(), 2, 2).await.unwrap();
+        assert_eq!(page.entries.len(), 1);
+        assert_eq!(page.total, 3);
+    }
+
+    #[tokio::test]
+    async fn entries_cannot_be_modified() {
+        let repo = setup_test_db().await;
+        repo.append(entry(
+            AuditAction::BookCreated,
+            "1",
+            OffsetDateTime::now_utc(),
+        ))
+        .await
+        .unwrap();
+
+        assert!(
+            query("UPDATE audit_log SET actor = 'otro'")
+                .execute(&repo.pool)
+                .await
+                .is_err()
+        );
+        assert!(
+            query("DELETE FROM audit_log")
+                .execute(&repo.pool)
+                .await
+                .is_err()
+        );
+    }
+}
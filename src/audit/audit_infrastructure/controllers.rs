@@ -0,0 +1,48 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+
+use crate::{
+    AppError, ProblemDetails,
+    audit::{audit_application::ListAuditEntriesDto, audit_domain::PaginatedAuditEntries},
+};
+
+use super::AuditState;
+
+/// Etiqueta de la documentación OpenAPI para la auditoría
+pub const AUDIT_TAG: &str = "audit";
+
+/// Consulta los cambios en el catálogo y las ApiKeys, del más reciente al más antiguo
+#[utoipa::path(
+    get,
+    path = "/audit",
+    tag = AUDIT_TAG,
+    params(ListAuditEntriesDto),
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Página de entradas de auditoría", body = PaginatedAuditEntries),
+        (
+            status = 400, description = "Parámetros inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn list_audit_entries_controller(
+    State(audit): State<AuditState>,
+    Query(dto): Query<ListAuditEntriesDto>,
+) -> Result<Json<PaginatedAuditEntries>, AppError> {
+    let use_case = &audit.list_entries;
+
+    let entries = use_case.list_entries(dto).await?;
+
+    Ok(Json(entries))
+}
@@ -0,0 +1,64 @@
+use std::{cmp::max, sync::Mutex};
+
+use crate::audit::{
+    AuditError,
+    audit_domain::{
+        AuditEntry, AuditFilter, AuditRepository, NewAuditEntry, PaginatedAuditEntries,
+    },
+};
+
+/// Auditoría en memoria, para repositorios propios sin una base de datos donde guardarla.
+/// Las entradas se pierden al reiniciar la aplicación.
+#[derive(Default)]
+pub struct MemoryAuditRepository {
+    entries: Mutex<Vec<AuditEntry>>,
+}
+
+#[async_trait::async_trait]
+impl AuditRepository for MemoryAuditRepository {
+    async fn append(&self, entry: NewAuditEntry) -> Result<(), AuditError> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let id = entries.len() as i64 + 1;
+
+        entries.push(AuditEntry {
+            id,
+            timestamp: entry.timestamp,
+            actor: entry.actor,
+            action: entry.action,
+            entity_type: entry.action.entity_type(),
+            entity_id: entry.entity_id,
+            before: entry.before,
+            after: entry.after,
+            request_id: entry.request_id,
+        });
+
+        Ok(())
+    }
+
+    async fn find(
+        &self,
+        filter: AuditFilter,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedAuditEntries, AuditError> {
+        let page = max(page, 1);
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let matching: Vec<_> = entries
+            .iter()
+            .rev()
+            .filter(|entry| filter.matches(entry))
+            .collect();
+
+        Ok(PaginatedAuditEntries {
+            total: matching.len() as u64,
+            entries: matching
+                .into_iter()
+                .skip((page - 1) as usize * page_size as usize)
+                .take(page_size as usize)
+                .cloned()
+                .collect(),
+            page,
+            page_size,
+        })
+    }
+}
@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use crate::audit::{
+    audit_application::{AuditLog, ListAuditEntriesUseCase},
+    audit_domain::AuditRepository,
+};
+
+/// Registro de auditoría y consulta de sus entradas
+#[derive(Clone)]
+pub struct AuditState {
+    pub log: Arc<AuditLog>,
+    pub list_entries: Arc<ListAuditEntriesUseCase>,
+}
+
+impl AuditState {
+    pub fn new(repo: Arc<dyn AuditRepository>) -> Self {
+        Self {
+            log: Arc::new(AuditLog { repo: repo.clone() }),
+            list_entries: Arc::new(ListAuditEntriesUseCase { repo }),
+        }
+    }
+}
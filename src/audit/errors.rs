@@ -0,0 +1,42 @@
+use std::fmt;
+
+use crate::{
+    AppErrorKind,
+    i18n::{Language, Localize, default_language, tr_lang},
+};
+
+#[derive(Debug, Clone, thiserror::Error, derive_error_kind::ErrorKind)]
+#[error_kind(AuditErrorKind)]
+pub enum AuditError {
+    #[error_kind(AppErrorKind, Infrastructure)]
+    DatabaseError(String),
+
+    #[error_kind(AppErrorKind, Infrastructure)]
+    Serialization(String),
+}
+
+impl AuditError {
+    /// Código estable que identifica el error, independiente del mensaje
+    pub fn code(&self) -> &'static str {
+        match self {
+            AuditError::DatabaseError(_) => "audit.database_error",
+            AuditError::Serialization(_) => "audit.serialization",
+        }
+    }
+}
+
+impl Localize for AuditError {
+    fn localize(&self, lang: Language) -> String {
+        match self {
+            AuditError::DatabaseError(detail) | AuditError::Serialization(detail) => {
+                tr_lang!(lang, self.code(), detail = detail)
+            }
+        }
+    }
+}
+
+impl fmt::Display for AuditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.localize(default_language()))
+    }
+}
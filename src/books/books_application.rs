@@ -1,6 +1,26 @@
+pub mod browse_shelf_use_case;
 pub mod create_book_use_case;
+pub mod create_location_use_case;
+pub mod create_subject_use_case;
 pub mod delete_book_use_case;
+pub mod get_authors_use_case;
+pub mod get_book_availability_use_case;
 pub mod get_book_by_id_use_case;
+pub mod get_book_history_use_case;
 pub mod get_books_use_case;
+pub mod get_catalog_version_use_case;
+pub mod get_locations_use_case;
+pub mod get_publishers_use_case;
+pub mod get_stock_movements_use_case;
+pub mod get_subjects_use_case;
+pub mod get_tags_use_case;
+pub mod merge_authors_use_case;
+pub mod merge_publishers_use_case;
+pub mod purge_books_use_case;
+pub mod rename_author_use_case;
+pub mod restore_book_use_case;
+pub mod revert_book_use_case;
 pub mod search_books_use_case;
+pub mod set_stock_use_case;
+pub mod transfer_stock_use_case;
 pub mod update_book_use_case;
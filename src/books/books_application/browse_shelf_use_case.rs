@@ -0,0 +1,89 @@
+use std::{str::FromStr, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::IntoParams;
+
+use crate::{
+    books::{
+        BookError,
+        books_domain::{
+            Book, BookRepository, CallNumber, DeletedFilter, ReadMultipleBooksCriteria,
+            ShelfBrowse, ShelfDirection,
+        },
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct BrowseShelfUseCase {
+    pub repo: Arc<dyn BookRepository>,
+}
+
+impl BrowseShelfUseCase {
+    /// Obtiene los `dto.count` libros anteriores y posteriores a una signatura, en el orden
+    /// de la estantería. Los libros sin signatura no aparecen.
+    pub async fn browse_shelf(&self, dto: BrowseShelfDto) -> Result<ShelfBrowse, BookError> {
+        let call_number = CallNumber::from_str(&dto.call_number)?;
+        info!(
+            "{}",
+            tr!(
+                "log.shelf_browsing",
+                call_number = call_number.as_str(),
+                count = dto.count
+            )
+        );
+
+        let mut before = self
+            .side(&call_number, ShelfDirection::Before, dto.count)
+            .await?;
+        // Se obtienen del más cercano al más lejano, pero se muestran en orden de estantería
+        before.reverse();
+        let after = self
+            .side(&call_number, ShelfDirection::After, dto.count)
+            .await?;
+
+        Ok(ShelfBrowse {
+            call_number,
+            before,
+            after,
+        })
+    }
+
+    #[inline]
+    async fn side(
+        &self,
+        call_number: &CallNumber,
+        direction: ShelfDirection,
+        count: u8,
+    ) -> Result<Vec<Book>, BookError> {
+        let page = self
+            .repo
+            .find_multiple(
+                ReadMultipleBooksCriteria::ByShelf(call_number.clone(), direction),
+                DeletedFilter::Exclude,
+                1,
+                count,
+            )
+            .await?;
+
+        Ok(page.books)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BrowseShelfDto {
+    /// Signatura desde la que se recorre la estantería; no es necesario que exista
+    #[param(example = "863.64 G216c")]
+    pub call_number: String,
+
+    /// Cantidad de libros a cada lado de la signatura
+    #[serde(default = "default_count")]
+    #[param(minimum = 1, maximum = 255, default = 5)]
+    pub count: u8,
+}
+
+fn default_count() -> u8 {
+    5
+}
@@ -1,28 +1,45 @@
 use std::{str::FromStr, sync::Arc};
 
-use log::info;
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use tracing::info;
+use utoipa::ToSchema;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationErrors};
 
-use crate::books::{
-    BookError,
-    books_domain::{
-        Book, BookAuthor, BookPublisher, BookRepository, BookTitle, Isbn, ReadBookCriteria,
+use crate::{
+    audit::{
+        audit_application::{AuditLog, snapshot},
+        audit_domain::{Actor, AuditAction},
     },
+    books::{
+        BookError,
+        books_domain::{
+            Book, BookAuthor, BookContributor, BookEdition, BookFormat, BookPublisher,
+            BookRepository, BookSeries, BookSubject, BookTag, BookTitle, CallNumber,
+            Classification, ClassificationScheme, ContributorRole, DeletedFilter, Isbn,
+            LanguageCode, ReadBookCriteria,
+        },
+    },
+    i18n::tr,
 };
 
 #[derive(Clone)]
 pub struct CreateBookUseCase {
     pub repo: Arc<dyn BookRepository>,
+    pub audit: Arc<AuditLog>,
 }
 
 impl CreateBookUseCase {
+    /// Los libros eliminados conservan su ISBN hasta que se purgan
     #[inline]
     async fn check_collition(&self, isbn: Isbn) -> Result<(), BookError> {
         let saved_book = self
             .repo
-            .find(ReadBookCriteria::ByIsbn(isbn.canonical()))
+            .find(
+                ReadBookCriteria::ByIsbn(isbn.canonical()),
+                DeletedFilter::Include,
+            )
             .await?;
 
         if saved_book.is_some() {
@@ -33,47 +50,227 @@ impl CreateBookUseCase {
     }
 
     /// Guarda un libro en la base de datos, previene el uso de ISBN duplicado
-    pub async fn create_book(&self, dto: CreateBookDto) -> Result<Uuid, BookError> {
+    pub async fn create_book(&self, dto: CreateBookDto, actor: &Actor) -> Result<Uuid, BookError> {
         let book_id = Uuid::new_v4();
         let book = Book::try_from((book_id, dto))?;
 
         self.check_collition(book.isbn.clone()).await?;
-        info!("Creando nuevo libro: {}", book.isbn.canonical());
+        info!("{}", tr!("log.book_creating", isbn = book.isbn.canonical()));
 
-        self.repo.create(book).await?;
+        self.repo
+            .create_with_revision(book.clone(), actor, OffsetDateTime::now_utc())
+            .await?;
+        self.audit
+            .record(
+                actor,
+                AuditAction::BookCreated,
+                book_id,
+                None,
+                snapshot(&book),
+            )
+            .await;
 
         Ok(book_id)
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateBookDto {
+    #[schema(min_length = 1)]
     pub title: String,
-    pub authors: Vec<String>,
+
+    /// Colaboradores en el orden en que deben mostrarse
+    #[schema(min_items = 1)]
+    pub authors: Vec<ContributorDto>,
+
+    #[schema(min_length = 1)]
     pub publisher: String,
+
+    /// Temas existentes en el vocabulario, ver `/subjects`
+    #[serde(default)]
+    pub subjects: Vec<String>,
+
+    /// Etiquetas libres; las que no existen se crean
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    #[schema(minimum = 1900, maximum = 2100)]
     pub year: u16,
+
+    #[schema(example = "978-0-306-40615-7")]
     pub isbn: String,
+
+    #[schema(minimum = 1, maximum = 1000)]
     pub stored_quantity: u16,
+
+    #[serde(default)]
+    #[schema(min_length = 1)]
+    pub subtitle: Option<String>,
+
+    #[serde(default)]
+    #[schema(min_length = 1)]
+    pub edition: Option<String>,
+
+    /// Código ISO 639 de dos o tres letras
+    #[serde(default)]
+    #[schema(example = "es")]
+    pub language: Option<String>,
+
+    #[serde(default)]
+    #[schema(minimum = 1, maximum = 10000)]
+    pub page_count: Option<u16>,
+
+    #[serde(default)]
+    pub format: Option<BookFormat>,
+
+    #[serde(default)]
+    #[schema(min_length = 1)]
+    pub series: Option<String>,
+
+    #[serde(default)]
+    #[schema(minimum = 1, maximum = 1000)]
+    pub series_number: Option<u16>,
+
+    #[serde(default)]
+    #[schema(min_length = 1, max_length = 4000)]
+    pub description: Option<String>,
+
+    #[serde(default)]
+    #[schema(example = "https://example.com/portada.jpg")]
+    pub cover_url: Option<String>,
+
+    #[serde(default)]
+    pub classification: Option<ClassificationDto>,
+
+    #[serde(default)]
+    #[schema(example = "863.64 G216c")]
+    pub call_number: Option<String>,
+}
+
+/// Nombre de un autor, o nombre y papel de cualquier colaborador
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum ContributorDto {
+    Name(String),
+    Contributor {
+        name: String,
+        #[serde(default)]
+        role: ContributorRole,
+    },
+}
+
+/// Número de clasificación; se valida según el sistema
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ClassificationDto {
+    pub scheme: ClassificationScheme,
+
+    #[schema(example = "863.64")]
+    pub number: String,
+}
+
+impl ContributorDto {
+    fn parse(&self) -> Result<BookContributor, BookError> {
+        let (name, role) = match self {
+            ContributorDto::Name(name) => (name, ContributorRole::Author),
+            ContributorDto::Contributor { name, role } => (name, *role),
+        };
+
+        Ok(BookContributor::new(BookAuthor::from_str(name)?, role))
+    }
 }
 
 impl TryFrom<(Uuid, CreateBookDto)> for Book {
     type Error = BookError;
 
     fn try_from((id, dto): (Uuid, CreateBookDto)) -> Result<Self, Self::Error> {
+        // Se acumulan los errores de cada campo para reportarlos juntos
+        let mut errors = ValidationErrors::new();
+
+        let title = parse_field(&mut errors, "title", BookTitle::from_str(&dto.title));
+        let publisher = parse_field(
+            &mut errors,
+            "publisher",
+            BookPublisher::from_str(&dto.publisher),
+        );
+        let isbn = parse_field(&mut errors, "isbn", Isbn::from_str(&dto.isbn));
         let authors = dto
             .authors
-            .into_iter()
-            .map(|author| BookAuthor::from_str(&author))
-            .collect::<Result<Vec<BookAuthor>, _>>()?;
+            .iter()
+            .filter_map(|author| parse_field(&mut errors, "authors", author.parse()))
+            .collect::<Vec<BookContributor>>();
+        let subjects = dto
+            .subjects
+            .iter()
+            .filter_map(|subject| {
+                parse_field(&mut errors, "subjects", BookSubject::from_str(subject))
+            })
+            .collect::<Vec<BookSubject>>();
+        let tags = dto
+            .tags
+            .iter()
+            .filter_map(|tag| parse_field(&mut errors, "tags", BookTag::from_str(tag)))
+            .collect::<Vec<BookTag>>();
+        let subtitle = dto.subtitle.as_deref().and_then(|subtitle| {
+            parse_field(&mut errors, "subtitle", BookTitle::from_str(subtitle))
+        });
+        let edition = dto.edition.as_deref().and_then(|edition| {
+            parse_field(&mut errors, "edition", BookEdition::from_str(edition))
+        });
+        let language = dto.language.as_deref().and_then(|language| {
+            parse_field(&mut errors, "language", LanguageCode::from_str(language))
+        });
+        let series = dto
+            .series
+            .as_deref()
+            .and_then(|series| parse_field(&mut errors, "series", BookSeries::from_str(series)));
+
+        let classification = dto.classification.as_ref().and_then(|classification| {
+            parse_field(
+                &mut errors,
+                "classification",
+                Classification::new(classification.scheme, &classification.number),
+            )
+        });
+        let call_number = dto.call_number.as_deref().and_then(|call_number| {
+            parse_field(
+                &mut errors,
+                "call_number",
+                CallNumber::from_str(call_number),
+            )
+        });
+
+        let (Some(title), Some(publisher), Some(isbn)) = (title, publisher, isbn) else {
+            return Err(BookError::Validation(errors));
+        };
+
+        if !errors.is_empty() {
+            return Err(BookError::Validation(errors));
+        }
 
         let book = Book {
             id,
-            title: BookTitle::from_str(&dto.title)?,
+            title,
             authors,
-            publisher: BookPublisher::from_str(&dto.publisher)?,
+            publisher,
+            subjects,
+            tags,
             year: dto.year,
-            isbn: Isbn::from_str(&dto.isbn)?,
+            isbn,
             stored_quantity: dto.stored_quantity,
+            subtitle,
+            edition,
+            language,
+            page_count: dto.page_count,
+            format: dto.format,
+            series,
+            series_number: dto.series_number,
+            description: dto
+                .description
+                .map(|description| description.trim().to_string()),
+            cover_url: dto.cover_url.map(|url| url.trim().to_string()),
+            classification,
+            call_number,
+            deleted_at: None,
         };
 
         book.validate()?;
@@ -81,3 +278,127 @@ impl TryFrom<(Uuid, CreateBookDto)> for Book {
         Ok(book)
     }
 }
+
+/// Registra el error de conversión de un campo, si existe
+#[inline]
+fn parse_field<T>(
+    errors: &mut ValidationErrors,
+    field: &'static str,
+    result: Result<T, BookError>,
+) -> Option<T> {
+    result
+        .map_err(|e| errors.add(field, e.to_validation_error()))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dto() -> CreateBookDto {
+        CreateBookDto {
+            title: "Cien años de soledad".to_string(),
+            authors: vec![
+                ContributorDto::Name("Gabriel García Márquez".to_string()),
+                ContributorDto::Contributor {
+                    name: "Gregory Rabassa".to_string(),
+                    role: ContributorRole::Translator,
+                },
+            ],
+            publisher: "Editorial Sudamericana".to_string(),
+            subjects: Vec::new(),
+            tags: Vec::new(),
+            year: 1967,
+            isbn: "978-0-306-40615-7".to_string(),
+            stored_quantity: 3,
+            subtitle: None,
+            edition: None,
+            language: None,
+            page_count: None,
+            format: None,
+            series: None,
+            series_number: None,
+            description: None,
+            cover_url: None,
+            classification: None,
+            call_number: None,
+        }
+    }
+
+    #[test]
+    fn dto_conversion_works() {
+        let book = Book::try_from((Uuid::new_v4(), dto())).expect("Error al convertir DTO");
+
+        assert_eq!(book.title.as_str(), "Cien años de soledad");
+        assert_eq!(book.authors.len(), 2);
+        assert_eq!(book.authors[0].role, ContributorRole::Author);
+        assert_eq!(book.authors[1].role, ContributorRole::Translator);
+    }
+
+    #[test]
+    fn dto_conversion_reports_every_invalid_field() {
+        let dto = CreateBookDto {
+            title: "".to_string(),
+            isbn: "no es un isbn".to_string(),
+            authors: vec![ContributorDto::Name("Autor 📚".to_string())],
+            ..dto()
+        };
+
+        let Err(BookError::Validation(errors)) = Book::try_from((Uuid::new_v4(), dto)) else {
+            panic!("Se esperaba un error de validación");
+        };
+
+        let fields = errors.field_errors();
+        assert_eq!(fields["title"][0].code, "empty_string");
+        assert_eq!(fields["isbn"][0].code, "invalid_isbn");
+        assert_eq!(fields["authors"][0].code, "invalid_characters");
+    }
+
+    #[test]
+    fn dto_conversion_keeps_bibliographic_details() {
+        let dto = CreateBookDto {
+            subtitle: Some("Edición conmemorativa".to_string()),
+            language: Some("ES".to_string()),
+            page_count: Some(471),
+            format: Some(BookFormat::Hardcover),
+            cover_url: Some("https://example.com/portada.jpg".to_string()),
+            ..dto()
+        };
+
+        let book = Book::try_from((Uuid::new_v4(), dto)).expect("Error al convertir DTO");
+
+        assert_eq!(book.language.unwrap().as_str(), "es");
+        assert_eq!(book.page_count, Some(471));
+        assert_eq!(book.format, Some(BookFormat::Hardcover));
+        assert!(book.edition.is_none());
+    }
+
+    #[test]
+    fn dto_conversion_validates_bibliographic_details() {
+        let invalid_language = CreateBookDto {
+            language: Some("español".to_string()),
+            ..dto()
+        };
+        let Err(BookError::Validation(errors)) = Book::try_from((Uuid::new_v4(), invalid_language))
+        else {
+            panic!("Se esperaba un error de validación");
+        };
+        assert_eq!(
+            errors.field_errors()["language"][0].code,
+            "invalid_language"
+        );
+
+        let out_of_range = CreateBookDto {
+            page_count: Some(0),
+            cover_url: Some("javascript:alert(1)".to_string()),
+            ..dto()
+        };
+        let Err(BookError::Validation(errors)) = Book::try_from((Uuid::new_v4(), out_of_range))
+        else {
+            panic!("Se esperaba un error de validación");
+        };
+        let fields = errors.field_errors();
+        assert_eq!(fields["page_count"][0].code, "range");
+        assert_eq!(fields["cover_url"][0].code, "url");
+    }
+}
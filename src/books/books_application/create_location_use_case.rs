@@ -0,0 +1,61 @@
+use std::{str::FromStr, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::{
+    audit::{
+        audit_application::{AuditLog, snapshot},
+        audit_domain::{Actor, AuditAction},
+    },
+    books::{
+        BookError,
+        books_domain::{Location, LocationName, LocationRepository},
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct CreateLocationUseCase {
+    pub repo: Arc<dyn LocationRepository>,
+    pub audit: Arc<AuditLog>,
+}
+
+impl CreateLocationUseCase {
+    /// Agrega una sucursal. Falla si ya existe una que solo difiere en mayúsculas, acentos
+    /// o espacios, o si la clave API está limitada a una sucursal (`scope`).
+    pub async fn create_location(
+        &self,
+        dto: CreateLocationDto,
+        actor: &Actor,
+        scope: Option<i64>,
+    ) -> Result<Location, BookError> {
+        if scope.is_some() {
+            return Err(BookError::LocationForbidden);
+        }
+
+        let name = LocationName::from_str(&dto.name)?;
+
+        info!("{}", tr!("log.location_creating", name = name.as_str()));
+        let location = self.repo.create_location(&name).await?;
+
+        self.audit
+            .record(
+                actor,
+                AuditAction::LocationCreated,
+                location.id,
+                None,
+                snapshot(&location),
+            )
+            .await;
+
+        Ok(location)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateLocationDto {
+    #[schema(min_length = 1)]
+    pub name: String,
+}
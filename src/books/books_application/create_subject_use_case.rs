@@ -0,0 +1,56 @@
+use std::{str::FromStr, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::{
+    audit::{
+        audit_application::{AuditLog, snapshot},
+        audit_domain::{Actor, AuditAction},
+    },
+    books::{
+        BookError,
+        books_domain::{BookSubject, Subject, TermRepository},
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct CreateSubjectUseCase {
+    pub repo: Arc<dyn TermRepository>,
+    pub audit: Arc<AuditLog>,
+}
+
+impl CreateSubjectUseCase {
+    /// Agrega un tema al vocabulario controlado. Falla si ya existe uno que solo difiere
+    /// en mayúsculas, acentos o espacios.
+    pub async fn create_subject(
+        &self,
+        dto: CreateSubjectDto,
+        actor: &Actor,
+    ) -> Result<Subject, BookError> {
+        let name = BookSubject::from_str(&dto.name)?;
+
+        info!("{}", tr!("log.subject_creating", name = name.as_str()));
+        let subject = self.repo.create_subject(&name).await?;
+
+        self.audit
+            .record(
+                actor,
+                AuditAction::SubjectCreated,
+                subject.id,
+                None,
+                snapshot(&subject),
+            )
+            .await;
+
+        Ok(subject)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateSubjectDto {
+    #[schema(min_length = 1)]
+    pub name: String,
+}
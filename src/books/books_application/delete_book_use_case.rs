@@ -1,36 +1,59 @@
 use std::sync::Arc;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
-use log::info;
+use tracing::info;
 
-use crate::books::{
-    BookError,
-    books_domain::{BookRepository, ReadBookCriteria},
+use crate::{
+    audit::{
+        audit_application::{AuditLog, snapshot},
+        audit_domain::{Actor, AuditAction},
+    },
+    books::{
+        BookError,
+        books_domain::{Book, BookRepository, DeletedFilter, ReadBookCriteria},
+    },
+    i18n::tr,
 };
 
 #[derive(Clone)]
 pub struct DeleteBookUseCase {
     pub repo: Arc<dyn BookRepository>,
+    pub audit: Arc<AuditLog>,
 }
 
 impl DeleteBookUseCase {
     #[inline]
-    async fn check_exists(&self, id: Uuid) -> Result<(), BookError> {
-        let _book = self
-            .repo
-            .find(ReadBookCriteria::ById(id))
+    async fn find_existing(&self, id: Uuid) -> Result<Book, BookError> {
+        self.repo
+            .find(ReadBookCriteria::ById(id), DeletedFilter::Exclude)
             .await?
-            .ok_or(BookError::NotFound)?;
-
-        Ok(())
+            .ok_or(BookError::NotFound)
     }
 
-    /// Elimina un libro de la base de datos por su ID
-    pub async fn delete_book(&self, id: Uuid) -> Result<(), BookError> {
+    /// Marca un libro como eliminado; puede restaurarse hasta que se purgue
+    pub async fn delete_book(&self, id: Uuid, actor: &Actor) -> Result<(), BookError> {
         // Verificar que el libro exista antes de eliminarlo
-        self.check_exists(id).await?;
+        let book = self.find_existing(id).await?;
+        let deleted_at = OffsetDateTime::now_utc();
+
+        info!("{}", tr!("log.book_deleting", id = id));
+        self.repo.delete(&id, deleted_at).await?;
 
-        info!("Eliminando libro con ID: {}", &id);
-        self.repo.delete(&id).await
+        let deleted = Book {
+            deleted_at: Some(deleted_at),
+            ..book.clone()
+        };
+        self.audit
+            .record(
+                actor,
+                AuditAction::BookDeleted,
+                id,
+                snapshot(&book),
+                snapshot(&deleted),
+            )
+            .await;
+
+        Ok(())
     }
 }
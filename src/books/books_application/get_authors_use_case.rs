@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::IntoParams;
+
+use crate::{
+    books::{
+        BookError,
+        books_domain::{AuthorRepository, AuthorWithBooks, PaginatedAuthors, ReadAuthorCriteria},
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct GetAuthorsUseCase {
+    pub repo: Arc<dyn AuthorRepository>,
+}
+
+impl GetAuthorsUseCase {
+    /// Lista los autores con la cantidad de libros de cada uno, opcionalmente filtrados
+    /// por nombre
+    pub async fn get_authors(&self, dto: GetAuthorsDto) -> Result<PaginatedAuthors, BookError> {
+        let name = dto.name.filter(|name| !name.trim().is_empty());
+        info!(
+            "{}",
+            tr!(
+                "log.authors_listing",
+                name = name.as_deref().unwrap_or_default(),
+                page = dto.page,
+                page_size = dto.page_size
+            )
+        );
+
+        self.repo.find_authors(name, dto.page, dto.page_size).await
+    }
+
+    /// Obtiene un autor junto con sus libros no eliminados
+    pub async fn get_author(&self, id: i64) -> Result<AuthorWithBooks, BookError> {
+        info!("{}", tr!("log.author_fetching", id = id));
+
+        let author = self
+            .repo
+            .find_author(ReadAuthorCriteria::ById(id))
+            .await?
+            .ok_or(BookError::AuthorNotFound)?;
+        let books = self.repo.author_books(id).await?;
+
+        Ok(AuthorWithBooks { author, books })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetAuthorsDto {
+    /// Texto a buscar en el nombre
+    pub name: Option<String>,
+
+    /// Número de página, comenzando en 1
+    #[serde(default = "default_page")]
+    #[param(minimum = 1, default = 1)]
+    pub page: u32,
+
+    /// Cantidad de autores por página
+    #[serde(default = "default_page_size")]
+    #[param(minimum = 1, maximum = 255, default = 10)]
+    pub page_size: u8,
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+fn default_page_size() -> u8 {
+    10
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use tracing::info;
+
+use crate::{
+    books::{
+        BookError,
+        books_domain::{
+            BookAvailability, BookRepository, DeletedFilter, LocationRepository, ReadBookCriteria,
+        },
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct GetBookAvailabilityUseCase {
+    pub repo: Arc<dyn BookRepository>,
+    pub locations: Arc<dyn LocationRepository>,
+}
+
+impl GetBookAvailabilityUseCase {
+    /// Obtiene los ejemplares de un libro vigente en cada sucursal y los que aún no se
+    /// asignaron a ninguna
+    pub async fn get_availability(&self, id: Uuid) -> Result<BookAvailability, BookError> {
+        info!("{}", tr!("log.book_availability_fetching", id = id));
+
+        let book = self
+            .repo
+            .find(ReadBookCriteria::ById(id), DeletedFilter::Exclude)
+            .await?
+            .ok_or(BookError::NotFound)?;
+
+        let stock = self.locations.stock(&id).await?;
+
+        Ok(BookAvailability::new(id, book.stored_quantity, stock))
+    }
+}
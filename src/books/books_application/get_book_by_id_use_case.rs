@@ -1,11 +1,16 @@
 use std::sync::Arc;
 use uuid::Uuid;
 
-use log::info;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::IntoParams;
 
-use crate::books::{
-    BookError,
-    books_domain::{Book, BookRepository, ReadBookCriteria},
+use crate::{
+    books::{
+        BookError,
+        books_domain::{Book, BookRepository, DeletedFilter, ReadBookCriteria},
+    },
+    i18n::tr,
 };
 
 #[derive(Clone)]
@@ -14,16 +19,39 @@ pub struct GetBookByIdUseCase {
 }
 
 impl GetBookByIdUseCase {
-    /// Obtiene un libro por su ID
-    pub async fn get_book_by_id(&self, id: Uuid) -> Result<Book, BookError> {
-        info!("Buscando libro con ID: {}", id);
+    /// Obtiene un libro por su ID; los eliminados solo se devuelven si `deleted` los incluye
+    pub async fn get_book_by_id(
+        &self,
+        id: Uuid,
+        deleted: DeletedFilter,
+    ) -> Result<Book, BookError> {
+        info!("{}", tr!("log.book_fetching", id = id));
 
         let book = self
             .repo
-            .find(ReadBookCriteria::ById(id))
+            .find(ReadBookCriteria::ById(id), deleted)
             .await?
             .ok_or(BookError::NotFound)?;
 
         Ok(book)
     }
 }
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetBookDto {
+    /// Devuelve el libro aunque esté eliminado
+    #[serde(default)]
+    #[param(default = false)]
+    pub include_deleted: bool,
+}
+
+impl GetBookDto {
+    pub fn deleted_filter(&self) -> DeletedFilter {
+        if self.include_deleted {
+            DeletedFilter::Include
+        } else {
+            DeletedFilter::Exclude
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use tracing::info;
+
+use crate::{
+    books::{
+        BookError,
+        books_domain::{
+            BookHistory, BookHistoryEntry, BookRepository, DeletedFilter, FieldChange,
+            ReadBookCriteria,
+        },
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct GetBookHistoryUseCase {
+    pub repo: Arc<dyn BookRepository>,
+}
+
+impl GetBookHistoryUseCase {
+    /// Obtiene las revisiones de un libro, incluso si está eliminado, con los cambios
+    /// entre cada revisión y la anterior
+    pub async fn get_book_history(&self, id: Uuid) -> Result<BookHistory, BookError> {
+        info!("{}", tr!("log.book_history_fetching", id = id));
+
+        self.repo
+            .find(ReadBookCriteria::ById(id), DeletedFilter::Include)
+            .await?
+            .ok_or(BookError::NotFound)?;
+
+        let revisions = self.repo.revisions(&id).await?;
+
+        let entries = revisions
+            .iter()
+            .enumerate()
+            .map(|(index, revision)| BookHistoryEntry {
+                changes: index
+                    .checked_sub(1)
+                    .map(|previous| FieldChange::between(&revisions[previous].book, &revision.book))
+                    .unwrap_or_default(),
+                revision: revision.clone(),
+            })
+            .collect();
+
+        Ok(BookHistory {
+            book_id: id,
+            revisions: entries,
+        })
+    }
+}
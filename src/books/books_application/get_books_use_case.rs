@@ -1,11 +1,15 @@
 use std::sync::Arc;
 
-use log::info;
 use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::IntoParams;
 
-use crate::books::{
-    BookError,
-    books_domain::{BookRepository, PaginatedBooks, ReadMultipleBooksCriteria},
+use crate::{
+    books::{
+        BookError,
+        books_domain::{BookRepository, DeletedFilter, PaginatedBooks, ReadMultipleBooksCriteria},
+    },
+    i18n::tr,
 };
 
 #[derive(Clone)]
@@ -14,26 +18,69 @@ pub struct GetBooksUseCase {
 }
 
 impl GetBooksUseCase {
-    /// Obtiene todos los libros de la base de datos con paginación
+    /// Obtiene todos los libros de la base de datos con paginación, opcionalmente solo los
+    /// que tienen ejemplares en una sucursal
     pub async fn get_all_books(&self, dto: GetBooksDto) -> Result<PaginatedBooks, BookError> {
         info!(
-            "Obteniendo libros, página: {}, tamaño: {}",
-            dto.page, dto.page_size
+            "{}",
+            tr!(
+                "log.books_listing",
+                page = dto.page,
+                page_size = dto.page_size
+            )
         );
 
         self.repo
-            .find_multiple(ReadMultipleBooksCriteria::All, dto.page, dto.page_size)
+            .find_multiple(
+                dto.criteria(),
+                DeletedFilter::Exclude,
+                dto.page,
+                dto.page_size,
+            )
+            .await
+    }
+
+    /// Obtiene los libros eliminados que aún no se purgaron, con paginación
+    pub async fn get_deleted_books(&self, dto: GetBooksDto) -> Result<PaginatedBooks, BookError> {
+        info!(
+            "{}",
+            tr!(
+                "log.books_listing_deleted",
+                page = dto.page,
+                page_size = dto.page_size
+            )
+        );
+
+        self.repo
+            .find_multiple(dto.criteria(), DeletedFilter::Only, dto.page, dto.page_size)
             .await
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GetBooksDto {
+    /// Número de página, comenzando en 1
     #[serde(default = "default_page")]
+    #[param(minimum = 1, default = 1)]
     pub page: u32,
 
+    /// Cantidad de libros por página
     #[serde(default = "default_page_size")]
+    #[param(minimum = 1, maximum = 255, default = 10)]
     pub page_size: u8,
+
+    /// Identificador de la sucursal en la que el libro tiene ejemplares
+    pub location: Option<i64>,
+}
+
+impl GetBooksDto {
+    fn criteria(&self) -> ReadMultipleBooksCriteria {
+        self.location.map_or(
+            ReadMultipleBooksCriteria::All,
+            ReadMultipleBooksCriteria::ByLocation,
+        )
+    }
 }
 
 fn default_page() -> u32 {
@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use crate::books::{
+    BookError,
+    books_domain::{BookRepository, CatalogVersion},
+};
+
+#[derive(Clone)]
+pub struct GetCatalogVersionUseCase {
+    pub repo: Arc<dyn BookRepository>,
+}
+
+impl GetCatalogVersionUseCase {
+    /// Obtiene la versión actual del catálogo, útil para validar respuestas cacheadas
+    pub async fn get_catalog_version(&self) -> Result<CatalogVersion, BookError> {
+        self.repo.catalog_version().await
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::IntoParams;
+
+use crate::{
+    books::{
+        BookError,
+        books_domain::{Location, LocationRepository, PaginatedLocations},
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct GetLocationsUseCase {
+    pub repo: Arc<dyn LocationRepository>,
+}
+
+impl GetLocationsUseCase {
+    /// Lista las sucursales con la cantidad de ejemplares de cada una
+    pub async fn get_locations(
+        &self,
+        dto: GetLocationsDto,
+    ) -> Result<PaginatedLocations, BookError> {
+        info!(
+            "{}",
+            tr!(
+                "log.locations_listing",
+                page = dto.page,
+                page_size = dto.page_size
+            )
+        );
+
+        self.repo.find_locations(dto.page, dto.page_size).await
+    }
+
+    /// Obtiene una sucursal con la cantidad de ejemplares
+    pub async fn get_location(&self, id: i64) -> Result<Location, BookError> {
+        self.repo
+            .find_location(id)
+            .await?
+            .ok_or(BookError::LocationNotFound)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetLocationsDto {
+    /// Número de página, comenzando en 1
+    #[serde(default = "default_page")]
+    #[param(minimum = 1, default = 1)]
+    pub page: u32,
+
+    /// Cantidad de sucursales por página
+    #[serde(default = "default_page_size")]
+    #[param(minimum = 1, maximum = 255, default = 10)]
+    pub page_size: u8,
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+fn default_page_size() -> u8 {
+    10
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use tracing::info;
+
+use crate::{
+    books::{
+        BookError,
+        books_domain::{
+            BookRepository, DeletedFilter, LocationRepository, ReadBookCriteria, StockMovement,
+        },
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct GetStockMovementsUseCase {
+    pub repo: Arc<dyn BookRepository>,
+    pub locations: Arc<dyn LocationRepository>,
+}
+
+impl GetStockMovementsUseCase {
+    /// Obtiene los traslados de un libro entre sucursales, incluso si está eliminado, del
+    /// más reciente al más antiguo. Con una sucursal (`scope`) solo los que salen o llegan a ella.
+    pub async fn get_movements(
+        &self,
+        id: Uuid,
+        scope: Option<i64>,
+    ) -> Result<Vec<StockMovement>, BookError> {
+        info!("{}", tr!("log.stock_movements_fetching", id = id));
+
+        self.repo
+            .find(ReadBookCriteria::ById(id), DeletedFilter::Include)
+            .await?
+            .ok_or(BookError::NotFound)?;
+
+        let movements = self.locations.movements(&id).await?;
+
+        Ok(match scope {
+            Some(location) => movements
+                .into_iter()
+                .filter(|movement| {
+                    movement.from_location_id == location || movement.to_location_id == location
+                })
+                .collect(),
+            None => movements,
+        })
+    }
+}
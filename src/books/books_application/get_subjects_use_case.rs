@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::IntoParams;
+
+use crate::{
+    books::{
+        BookError,
+        books_domain::{PaginatedSubjects, TermRepository},
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct GetSubjectsUseCase {
+    pub repo: Arc<dyn TermRepository>,
+}
+
+impl GetSubjectsUseCase {
+    /// Lista el vocabulario de temas con la cantidad de libros de cada uno, opcionalmente
+    /// filtrado por nombre
+    pub async fn get_subjects(&self, dto: GetSubjectsDto) -> Result<PaginatedSubjects, BookError> {
+        let name = dto.name.filter(|name| !name.trim().is_empty());
+        info!(
+            "{}",
+            tr!(
+                "log.subjects_listing",
+                name = name.as_deref().unwrap_or_default(),
+                page = dto.page,
+                page_size = dto.page_size
+            )
+        );
+
+        self.repo.find_subjects(name, dto.page, dto.page_size).await
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetSubjectsDto {
+    /// Texto a buscar en el nombre, sin distinguir mayúsculas ni acentos
+    pub name: Option<String>,
+
+    /// Número de página, comenzando en 1
+    #[serde(default = "default_page")]
+    #[param(minimum = 1, default = 1)]
+    pub page: u32,
+
+    /// Cantidad de temas por página
+    #[serde(default = "default_page_size")]
+    #[param(minimum = 1, maximum = 255, default = 10)]
+    pub page_size: u8,
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+fn default_page_size() -> u8 {
+    10
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::IntoParams;
+
+use crate::{
+    books::{
+        BookError,
+        books_domain::{PaginatedTags, TermRepository},
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct GetTagsUseCase {
+    pub repo: Arc<dyn TermRepository>,
+}
+
+impl GetTagsUseCase {
+    /// Lista las etiquetas con la cantidad de libros de cada una, opcionalmente
+    /// filtradas por nombre
+    pub async fn get_tags(&self, dto: GetTagsDto) -> Result<PaginatedTags, BookError> {
+        let name = dto.name.filter(|name| !name.trim().is_empty());
+        info!(
+            "{}",
+            tr!(
+                "log.tags_listing",
+                name = name.as_deref().unwrap_or_default(),
+                page = dto.page,
+                page_size = dto.page_size
+            )
+        );
+
+        self.repo.find_tags(name, dto.page, dto.page_size).await
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetTagsDto {
+    /// Texto a buscar en el nombre, sin distinguir mayúsculas ni acentos
+    pub name: Option<String>,
+
+    /// Número de página, comenzando en 1
+    #[serde(default = "default_page")]
+    #[param(minimum = 1, default = 1)]
+    pub page: u32,
+
+    /// Cantidad de etiquetas por página
+    #[serde(default = "default_page_size")]
+    #[param(minimum = 1, maximum = 255, default = 10)]
+    pub page_size: u8,
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+fn default_page_size() -> u8 {
+    10
+}
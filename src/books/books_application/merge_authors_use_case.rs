@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::{
+    audit::{
+        audit_application::{AuditLog, snapshot},
+        audit_domain::{Actor, AuditAction},
+    },
+    books::{
+        BookError,
+        books_domain::{Author, AuthorRepository, ReadAuthorCriteria},
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct MergeAuthorsUseCase {
+    pub repo: Arc<dyn AuthorRepository>,
+    pub audit: Arc<AuditLog>,
+}
+
+impl MergeAuthorsUseCase {
+    /// Fusiona un autor duplicado con `dto.into`: sus libros pasan al otro autor y el
+    /// duplicado se elimina. Devuelve el autor resultante.
+    pub async fn merge_authors(
+        &self,
+        source: i64,
+        dto: MergeAuthorsDto,
+        actor: &Actor,
+    ) -> Result<Author, BookError> {
+        if source == dto.into {
+            return Err(BookError::SelfMerge);
+        }
+
+        let duplicate = self.find(source).await?;
+        let target = self.find(dto.into).await?;
+
+        info!(
+            "{}",
+            tr!("log.authors_merging", source = source, target = target.id)
+        );
+        self.repo.merge_authors(source, target.id).await?;
+
+        let merged = self.find(target.id).await?;
+        self.audit
+            .record(
+                actor,
+                AuditAction::AuthorsMerged,
+                target.id,
+                snapshot(&[&duplicate, &target]),
+                snapshot(&merged),
+            )
+            .await;
+
+        Ok(merged)
+    }
+
+    #[inline]
+    async fn find(&self, id: i64) -> Result<Author, BookError> {
+        self.repo
+            .find_author(ReadAuthorCriteria::ById(id))
+            .await?
+            .ok_or(BookError::AuthorNotFound)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MergeAuthorsDto {
+    /// Autor que conserva los libros de ambos
+    pub into: i64,
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::{
+    audit::{
+        audit_application::{AuditLog, snapshot},
+        audit_domain::{Actor, AuditAction},
+    },
+    books::{
+        BookError,
+        books_domain::{Publisher, PublisherRepository},
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct MergePublishersUseCase {
+    pub repo: Arc<dyn PublisherRepository>,
+    pub audit: Arc<AuditLog>,
+}
+
+impl MergePublishersUseCase {
+    /// Fusiona una editorial duplicada con `dto.into`: sus libros pasan a la otra editorial
+    /// y la duplicada se elimina. Devuelve la editorial resultante.
+    pub async fn merge_publishers(
+        &self,
+        source: i64,
+        dto: MergePublishersDto,
+        actor: &Actor,
+    ) -> Result<Publisher, BookError> {
+        if source == dto.into {
+            return Err(BookError::SelfMerge);
+        }
+
+        let duplicate = self.find(source).await?;
+        let target = self.find(dto.into).await?;
+
+        info!(
+            "{}",
+            tr!(
+                "log.publishers_merging",
+                source = source,
+                target = target.id
+            )
+        );
+        self.repo.merge_publishers(source, target.id).await?;
+
+        let merged = self.find(target.id).await?;
+        self.audit
+            .record(
+                actor,
+                AuditAction::PublishersMerged,
+                target.id,
+                snapshot(&[&duplicate, &target]),
+                snapshot(&merged),
+            )
+            .await;
+
+        Ok(merged)
+    }
+
+    #[inline]
+    async fn find(&self, id: i64) -> Result<Publisher, BookError> {
+        self.repo
+            .find_publisher(id)
+            .await?
+            .ok_or(BookError::PublisherNotFound)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MergePublishersDto {
+    /// Editorial que conserva los libros de ambas
+    pub into: i64,
+}
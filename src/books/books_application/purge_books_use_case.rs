@@ -0,0 +1,48 @@
+use std::{sync::Arc, time::Duration};
+
+use time::OffsetDateTime;
+use tracing::info;
+
+use crate::{
+    audit::{
+        audit_application::{AuditLog, snapshot},
+        audit_domain::{Actor, AuditAction},
+    },
+    books::{BookError, books_domain::BookRepository},
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct PurgeBooksUseCase {
+    pub repo: Arc<dyn BookRepository>,
+    pub audit: Arc<AuditLog>,
+}
+
+impl PurgeBooksUseCase {
+    /// Borra definitivamente los libros eliminados hace más de `retention`;
+    /// devuelve la cantidad de libros borrados
+    pub async fn purge_books(
+        &self,
+        retention: Duration,
+        actor: &Actor,
+    ) -> Result<usize, BookError> {
+        let deleted_before = OffsetDateTime::now_utc() - retention;
+
+        info!("{}", tr!("log.books_purging", before = deleted_before));
+        let purged = self.repo.purge(deleted_before).await?;
+
+        for book in &purged {
+            self.audit
+                .record(
+                    actor,
+                    AuditAction::BookPurged,
+                    book.id,
+                    snapshot(book),
+                    None,
+                )
+                .await;
+        }
+
+        Ok(purged.len())
+    }
+}
@@ -0,0 +1,79 @@
+use std::{str::FromStr, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::{
+    audit::{
+        audit_application::{AuditLog, snapshot},
+        audit_domain::{Actor, AuditAction},
+    },
+    books::{
+        BookError,
+        books_domain::{Author, AuthorRepository, BookAuthor, ReadAuthorCriteria},
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct RenameAuthorUseCase {
+    pub repo: Arc<dyn AuthorRepository>,
+    pub audit: Arc<AuditLog>,
+}
+
+impl RenameAuthorUseCase {
+    /// Cambia el nombre de un autor en todos sus libros. Si ya existe otro autor con el
+    /// nuevo nombre, se deben fusionar en lugar de renombrar.
+    pub async fn rename_author(
+        &self,
+        id: i64,
+        dto: RenameAuthorDto,
+        actor: &Actor,
+    ) -> Result<Author, BookError> {
+        let name = BookAuthor::from_str(&dto.name)?;
+
+        let previous = self
+            .repo
+            .find_author(ReadAuthorCriteria::ById(id))
+            .await?
+            .ok_or(BookError::AuthorNotFound)?;
+
+        if let Some(other) = self
+            .repo
+            .find_author(ReadAuthorCriteria::ByName(name.to_string()))
+            .await?
+            && other.id != id
+        {
+            return Err(BookError::AuthorAlreadyExists(name.to_string()));
+        }
+
+        info!(
+            "{}",
+            tr!("log.author_renaming", id = id, name = name.as_str())
+        );
+        self.repo.rename_author(id, &name).await?;
+
+        let renamed = Author {
+            name,
+            ..previous.clone()
+        };
+        self.audit
+            .record(
+                actor,
+                AuditAction::AuthorRenamed,
+                id,
+                snapshot(&previous),
+                snapshot(&renamed),
+            )
+            .await;
+
+        Ok(renamed)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RenameAuthorDto {
+    #[schema(min_length = 1)]
+    pub name: String,
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use tracing::info;
+
+use crate::{
+    audit::{
+        audit_application::{AuditLog, snapshot},
+        audit_domain::{Actor, AuditAction},
+    },
+    books::{
+        BookError,
+        books_domain::{Book, BookRepository, DeletedFilter, ReadBookCriteria},
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct RestoreBookUseCase {
+    pub repo: Arc<dyn BookRepository>,
+    pub audit: Arc<AuditLog>,
+}
+
+impl RestoreBookUseCase {
+    /// Restaura un libro eliminado; restaurar un libro vigente no tiene efecto
+    pub async fn restore_book(&self, id: Uuid, actor: &Actor) -> Result<Book, BookError> {
+        let book = self
+            .repo
+            .find(ReadBookCriteria::ById(id), DeletedFilter::Include)
+            .await?
+            .ok_or(BookError::NotFound)?;
+
+        if book.deleted_at.is_none() {
+            return Ok(book);
+        }
+
+        info!("{}", tr!("log.book_restoring", id = id));
+        self.repo.restore(&id).await?;
+
+        let restored = Book {
+            deleted_at: None,
+            ..book.clone()
+        };
+        self.audit
+            .record(
+                actor,
+                AuditAction::BookRestored,
+                id,
+                snapshot(&book),
+                snapshot(&restored),
+            )
+            .await;
+
+        Ok(restored)
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use tracing::info;
+
+use crate::{
+    audit::audit_domain::Actor,
+    books::{
+        BookError,
+        books_application::update_book_use_case::UpdateBookUseCase,
+        books_domain::{Book, BookRepository},
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct RevertBookUseCase {
+    pub repo: Arc<dyn BookRepository>,
+    pub update_book: Arc<UpdateBookUseCase>,
+}
+
+impl RevertBookUseCase {
+    /// Vuelve a guardar una revisión anterior del libro como una actualización más, con
+    /// las mismas validaciones y comprobación de ISBN duplicado
+    pub async fn revert_book(
+        &self,
+        id: Uuid,
+        revision: u32,
+        actor: &Actor,
+    ) -> Result<Book, BookError> {
+        let saved = self
+            .repo
+            .find_revision(&id, revision)
+            .await?
+            .ok_or(BookError::RevisionNotFound(revision))?;
+
+        info!(
+            "{}",
+            tr!("log.book_reverting", id = id, revision = revision)
+        );
+
+        let book = Book {
+            id,
+            deleted_at: None,
+            ..saved.book
+        };
+
+        self.update_book.update_book(book, actor).await
+    }
+}
@@ -1,11 +1,19 @@
 use std::sync::Arc;
 
-use log::info;
 use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::IntoParams;
+use validator::Validate;
 
-use crate::books::{
-    BookError,
-    books_domain::{BookRepository, PaginatedBooks, ReadMultipleBooksCriteria},
+use crate::{
+    books::{
+        BookError,
+        books_domain::{
+            BookFilters, BookRepository, BookSearchResults, ContributorRole,
+            ReadMultipleBooksCriteria,
+        },
+    },
+    i18n::tr,
 };
 
 #[derive(Clone)]
@@ -14,35 +22,84 @@ pub struct SearchBooksUseCase {
 }
 
 impl SearchBooksUseCase {
-    /// Busca libros por título o autor
-    pub async fn search_books(&self, dto: SearchBooksDto) -> Result<PaginatedBooks, BookError> {
-        let criteria = match (&dto.title, &dto.author) {
-            (Some(title), None) | (Some(title), Some(_)) if !title.trim().is_empty() => {
-                info!("Buscando libros por título: {}", title);
+    /// Busca libros por título o colaborador, opcionalmente filtrando por su papel, y los
+    /// refina con los filtros de facetas
+    pub async fn search_books(&self, dto: SearchBooksDto) -> Result<BookSearchResults, BookError> {
+        dto.validate()?;
+
+        let criteria = match (&dto.title, &dto.author, dto.role) {
+            (Some(title), _, _) if !title.trim().is_empty() => {
+                info!("{}", tr!("log.books_search_title", title = title));
                 ReadMultipleBooksCriteria::ByTitle(title.clone())
             }
-            (None, Some(author)) if !author.trim().is_empty() => {
-                info!("Buscando libros por autor: {}", author);
-                ReadMultipleBooksCriteria::ByAuthor(author.clone())
+            (_, Some(author), role) if !author.trim().is_empty() => {
+                info!("{}", tr!("log.books_search_author", author = author));
+                ReadMultipleBooksCriteria::ByAuthor(author.clone(), role)
+            }
+            (_, _, Some(role)) => {
+                info!("{}", tr!("log.books_search_role", role = role));
+                ReadMultipleBooksCriteria::ByAuthor(String::new(), Some(role))
             }
             _ => {
-                info!(
-                    "No se proporcionaron criterios de búsqueda válidos, devolviendo todos los libros"
-                );
+                info!("{}", tr!("log.books_search_all"));
                 ReadMultipleBooksCriteria::All
             }
         };
 
+        let non_empty = |value: Option<String>| value.filter(|value| !value.trim().is_empty());
+        let filters = BookFilters {
+            subject: non_empty(dto.subject),
+            tag: non_empty(dto.tag),
+            publisher: non_empty(dto.publisher),
+            language: non_empty(dto.language),
+            decade: dto.decade,
+            location: dto.location,
+        };
+
         self.repo
-            .find_multiple(criteria, dto.page, dto.page_size)
+            .search(criteria, filters, dto.page, dto.page_size)
             .await
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SearchBooksDto {
+    /// Texto a buscar en el título
     pub title: Option<String>,
+
+    /// Texto a buscar en los autores y demás colaboradores
     pub author: Option<String>,
+
+    /// Papel del colaborador buscado; sin `author`, devuelve los libros con alguien en ese papel
+    pub role: Option<ContributorRole>,
+
+    /// Tema exacto, sin distinguir mayúsculas ni acentos
+    pub subject: Option<String>,
+
+    /// Etiqueta exacta, sin distinguir mayúsculas ni acentos
+    pub tag: Option<String>,
+
+    /// Editorial exacta, sin distinguir mayúsculas ni acentos
+    pub publisher: Option<String>,
+
+    /// Código ISO 639 del idioma
+    #[param(example = "es")]
+    pub language: Option<String>,
+
+    /// Cualquier año de la década, por ejemplo `1960`
+    #[validate(range(min = 1900, max = 2100))]
+    #[param(minimum = 1900, maximum = 2100)]
+    pub decade: Option<u16>,
+
+    /// Identificador de la sucursal en la que el libro tiene ejemplares
+    pub location: Option<i64>,
+
+    /// Número de página, comenzando en 1
+    #[param(minimum = 1)]
     pub page: u32,
+
+    /// Cantidad de libros por página
+    #[param(minimum = 1, maximum = 255)]
     pub page_size: u8,
 }
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    audit::{
+        audit_application::{AuditLog, snapshot},
+        audit_domain::{Actor, AuditAction},
+    },
+    books::{
+        BookError,
+        books_domain::{
+            BookAvailability, BookRepository, DeletedFilter, LocationRepository, ReadBookCriteria,
+        },
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct SetStockUseCase {
+    pub repo: Arc<dyn BookRepository>,
+    pub locations: Arc<dyn LocationRepository>,
+    pub audit: Arc<AuditLog>,
+}
+
+impl SetStockUseCase {
+    /// Fija los ejemplares de un libro vigente en una sucursal. Entre todas las sucursales
+    /// no pueden superar la cantidad guardada del libro, y una clave API limitada a una
+    /// sucursal (`scope`) solo puede modificar la suya.
+    pub async fn set_stock(
+        &self,
+        id: Uuid,
+        location: i64,
+        dto: SetStockDto,
+        actor: &Actor,
+        scope: Option<i64>,
+    ) -> Result<BookAvailability, BookError> {
+        if scope.is_some_and(|scope| scope != location) {
+            return Err(BookError::LocationForbidden);
+        }
+
+        let book = self
+            .repo
+            .find(ReadBookCriteria::ById(id), DeletedFilter::Exclude)
+            .await?
+            .ok_or(BookError::NotFound)?;
+
+        self.locations
+            .find_location(location)
+            .await?
+            .ok_or(BookError::LocationNotFound)?;
+
+        let previous =
+            BookAvailability::new(id, book.stored_quantity, self.locations.stock(&id).await?);
+        let elsewhere = previous
+            .locations
+            .iter()
+            .filter(|stock| stock.location_id != location)
+            .map(|stock| u32::from(stock.quantity))
+            .sum::<u32>();
+        if elsewhere + u32::from(dto.quantity) > u32::from(book.stored_quantity) {
+            return Err(BookError::StockExceedsQuantity);
+        }
+
+        info!(
+            "{}",
+            tr!(
+                "log.stock_setting",
+                id = id,
+                location = location,
+                quantity = dto.quantity
+            )
+        );
+
+        self.locations
+            .set_stock(&id, location, dto.quantity)
+            .await?;
+
+        let availability =
+            BookAvailability::new(id, book.stored_quantity, self.locations.stock(&id).await?);
+        self.audit
+            .record(
+                actor,
+                AuditAction::StockAdjusted,
+                id,
+                snapshot(&previous),
+                snapshot(&availability),
+            )
+            .await;
+
+        Ok(availability)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetStockDto {
+    /// Ejemplares en la sucursal; con cero se quita el libro de la sucursal
+    #[schema(minimum = 0, maximum = 1000)]
+    pub quantity: u16,
+}
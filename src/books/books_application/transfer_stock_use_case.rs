@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use tracing::info;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    audit::{
+        audit_application::{AuditLog, snapshot},
+        audit_domain::{Actor, AuditAction},
+    },
+    books::{
+        BookError,
+        books_domain::{
+            BookRepository, DeletedFilter, LocationRepository, NewStockMovement, ReadBookCriteria,
+            StockMovement,
+        },
+    },
+    i18n::tr,
+};
+
+#[derive(Clone)]
+pub struct TransferStockUseCase {
+    pub repo: Arc<dyn BookRepository>,
+    pub locations: Arc<dyn LocationRepository>,
+    pub audit: Arc<AuditLog>,
+}
+
+impl TransferStockUseCase {
+    /// Traslada ejemplares de un libro vigente entre dos sucursales y registra el
+    /// movimiento. Una clave API limitada a una sucursal (`scope`) solo puede enviar
+    /// ejemplares desde la suya.
+    pub async fn transfer_stock(
+        &self,
+        id: Uuid,
+        dto: TransferStockDto,
+        actor: &Actor,
+        scope: Option<i64>,
+    ) -> Result<StockMovement, BookError> {
+        dto.validate()?;
+
+        if dto.from == dto.to {
+            return Err(BookError::SameLocation);
+        }
+
+        if scope.is_some_and(|scope| scope != dto.from) {
+            return Err(BookError::LocationForbidden);
+        }
+
+        self.repo
+            .find(ReadBookCriteria::ById(id), DeletedFilter::Exclude)
+            .await?
+            .ok_or(BookError::NotFound)?;
+
+        for location in [dto.from, dto.to] {
+            self.locations
+                .find_location(location)
+                .await?
+                .ok_or(BookError::LocationNotFound)?;
+        }
+
+        info!(
+            "{}",
+            tr!(
+                "log.stock_transferring",
+                id = id,
+                quantity = dto.quantity,
+                from = dto.from,
+                to = dto.to
+            )
+        );
+
+        let movement = self
+            .locations
+            .transfer(NewStockMovement {
+                book_id: id,
+                from_location_id: dto.from,
+                to_location_id: dto.to,
+                quantity: dto.quantity,
+                actor: actor.clone(),
+                created_at: OffsetDateTime::now_utc(),
+            })
+            .await?;
+
+        self.audit
+            .record(
+                actor,
+                AuditAction::StockTransferred,
+                id,
+                None,
+                snapshot(&movement),
+            )
+            .await;
+
+        Ok(movement)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct TransferStockDto {
+    /// Sucursal de origen
+    pub from: i64,
+
+    /// Sucursal de destino
+    pub to: i64,
+
+    #[validate(range(min = 1, max = 1000))]
+    #[schema(minimum = 1, maximum = 1000)]
+    pub quantity: u16,
+}
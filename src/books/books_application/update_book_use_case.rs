@@ -1,50 +1,99 @@
 use std::sync::Arc;
 
-use log::info;
+use time::OffsetDateTime;
+use tracing::info;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::books::{
-    BookError,
-    books_domain::{Book, BookRepository, Isbn, ReadBookCriteria},
+use crate::{
+    audit::{
+        audit_application::{AuditLog, snapshot},
+        audit_domain::{Actor, AuditAction},
+    },
+    books::{
+        BookError,
+        books_domain::{
+            Book, BookRepository, DeletedFilter, Isbn, LocationRepository, ReadBookCriteria,
+        },
+    },
+    i18n::tr,
 };
 
 #[derive(Clone)]
 pub struct UpdateBookUseCase {
     pub repo: Arc<dyn BookRepository>,
+    pub locations: Arc<dyn LocationRepository>,
+    pub audit: Arc<AuditLog>,
 }
 
 impl UpdateBookUseCase {
-    /// Evita que dos libros tengan el mismo ISBN
+    /// Evita que dos libros tengan el mismo ISBN, aunque uno de ellos esté eliminado
     #[inline]
     async fn check_collision(&self, id: Uuid, isbn: &Isbn) -> Result<(), BookError> {
         let saved_book = self
             .repo
-            .find(ReadBookCriteria::ByIsbn(isbn.canonical()))
+            .find(
+                ReadBookCriteria::ByIsbn(isbn.canonical()),
+                DeletedFilter::Include,
+            )
             .await?;
 
-        if let Some(book) = saved_book {
-            if book.id != id {
-                return Err(BookError::AlreadyExists(isbn.to_string()));
-            }
+        if let Some(book) = saved_book
+            && book.id != id
+        {
+            return Err(BookError::AlreadyExists(isbn.to_string()));
         }
 
         Ok(())
     }
 
-    /// Actualiza un libro existente en la base de datos
-    pub async fn update_book(&self, book: Book) -> Result<Book, BookError> {
+    /// Actualiza un libro existente en la base de datos y guarda la nueva versión en su
+    /// historial; los libros eliminados no se modifican. La cantidad guardada no puede
+    /// quedar por debajo de los ejemplares asignados a sucursales.
+    pub async fn update_book(&self, book: Book, actor: &Actor) -> Result<Book, BookError> {
         book.validate()?;
 
+        let previous = self
+            .repo
+            .find(ReadBookCriteria::ById(book.id), DeletedFilter::Exclude)
+            .await?
+            .ok_or(BookError::NotFound)?;
+
         self.check_collision(book.id, &book.isbn).await?;
 
+        // Los ejemplares asignados a sucursales deben seguir existiendo
+        let assigned = self
+            .locations
+            .stock(&book.id)
+            .await?
+            .iter()
+            .map(|stock| stock.quantity)
+            .sum::<u16>();
+        if book.stored_quantity < assigned {
+            return Err(BookError::StockExceedsQuantity);
+        }
+
         info!(
-            "Actualizando libro ID: {}, ISBN: {}",
-            book.id,
-            book.isbn.canonical()
+            "{}",
+            tr!(
+                "log.book_updating",
+                id = book.id,
+                isbn = book.isbn.canonical()
+            )
         );
 
-        self.repo.update(book.clone()).await?;
+        self.repo
+            .update_with_revision(book.clone(), actor, OffsetDateTime::now_utc())
+            .await?;
+        self.audit
+            .record(
+                actor,
+                AuditAction::BookUpdated,
+                book.id,
+                snapshot(&previous),
+                snapshot(&book),
+            )
+            .await;
 
         Ok(book)
     }
@@ -1,5 +1,21 @@
+mod authors;
 mod books;
+mod classification;
+mod contributors;
+mod facets;
+mod locations;
+mod publishers;
+mod revisions;
+mod subjects;
 mod utils;
 
+pub use authors::*;
 pub use books::*;
+pub use classification::*;
+pub use contributors::*;
+pub use facets::*;
+pub use locations::*;
+pub use publishers::*;
+pub use revisions::*;
+pub use subjects::*;
 pub use utils::*;
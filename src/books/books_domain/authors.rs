@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::books::BookError;
+
+use super::{Book, BookAuthor};
+
+/// Autor del catálogo con la cantidad de libros vigentes que tiene asociados
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Author {
+    pub id: i64,
+
+    #[schema(value_type = String, min_length = 1)]
+    pub name: BookAuthor,
+
+    /// Libros no eliminados del autor
+    pub book_count: u64,
+}
+
+/// Autor junto con sus libros no eliminados, ordenados por título
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AuthorWithBooks {
+    #[serde(flatten)]
+    pub author: Author,
+
+    pub books: Vec<Book>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReadAuthorCriteria {
+    ById(i64),
+    ByName(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaginatedAuthors {
+    pub authors: Vec<Author>,
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u8,
+}
+
+/// Autores del catálogo y su relación con los libros
+#[async_trait::async_trait]
+pub trait AuthorRepository: Send + Sync {
+    /// Autores cuyo nombre contiene `name`, ordenados por nombre
+    async fn find_authors(
+        &self,
+        name: Option<String>,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedAuthors, BookError>;
+    async fn find_author(&self, criteria: ReadAuthorCriteria) -> Result<Option<Author>, BookError>;
+    /// Libros no eliminados del autor, ordenados por título
+    async fn author_books(&self, id: i64) -> Result<Vec<Book>, BookError>;
+    /// Cambia el nombre del autor en todos sus libros
+    async fn rename_author(&self, id: i64, name: &BookAuthor) -> Result<(), BookError>;
+    /// Asigna los libros de `source` a `target` y elimina `source`
+    async fn merge_authors(&self, source: i64, target: i64) -> Result<(), BookError>;
+}
@@ -1,48 +1,225 @@
+use std::time::SystemTime;
+
+use parse_display::{Display, FromStr};
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidateUrl, ValidationError};
 
-use crate::books::BookError;
+use crate::{audit::audit_domain::Actor, books::BookError};
 
-use super::{Isbn, ValidatedStr};
+use super::{
+    BookContributor, BookRevision, BookSearchResults, CallNumber, Classification, ContributorRole,
+    Isbn, LanguageCode, ShelfDirection, ValidatedStr,
+};
 
 pub type BookTitle = ValidatedStr;
 pub type BookAuthor = ValidatedStr;
 pub type BookPublisher = ValidatedStr;
+pub type BookEdition = ValidatedStr;
+pub type BookSeries = ValidatedStr;
+pub type BookSubject = ValidatedStr;
+pub type BookTag = ValidatedStr;
+
+/// Formato físico o digital de un libro
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Display, FromStr, Serialize, Deserialize, ToSchema,
+)]
+#[display(style = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BookFormat {
+    Hardcover,
+    Paperback,
+    Ebook,
+    Audio,
+}
 
 /// Representa un libro en la librería
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Validate, ToSchema)]
 pub struct Book {
     pub id: Uuid,
 
+    #[schema(value_type = String, min_length = 1)]
     pub title: BookTitle,
 
+    /// Autores y demás colaboradores en el orden en que se catalogaron
     #[validate(length(min = 1))]
-    pub authors: Vec<BookAuthor>,
+    #[schema(min_items = 1)]
+    pub authors: Vec<BookContributor>,
 
+    #[schema(value_type = String, min_length = 1)]
     pub publisher: BookPublisher,
 
+    /// Temas del vocabulario controlado, ver `/subjects`
+    #[serde(default)]
+    #[schema(value_type = Vec<String>)]
+    pub subjects: Vec<BookSubject>,
+
+    /// Etiquetas libres; las nuevas se crean al guardar el libro
+    #[serde(default)]
+    #[schema(value_type = Vec<String>)]
+    pub tags: Vec<BookTag>,
+
     #[validate(range(min = 1900, max = 2100))]
+    #[schema(minimum = 1900, maximum = 2100)]
     pub year: u16,
 
+    #[schema(value_type = String, example = "978-0-306-40615-7")]
     pub isbn: Isbn,
 
     #[validate(range(min = 1, max = 1000))]
+    #[schema(minimum = 1, maximum = 1000)]
     pub stored_quantity: u16,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, min_length = 1)]
+    pub subtitle: Option<BookTitle>,
+
+    /// Mención de edición, por ejemplo "2.ª edición revisada"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, min_length = 1)]
+    pub edition: Option<BookEdition>,
+
+    /// Código ISO 639 del idioma del texto
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "es", pattern = "^[a-z]{2,3}$")]
+    pub language: Option<LanguageCode>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 10000))]
+    #[schema(minimum = 1, maximum = 10000)]
+    pub page_count: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<BookFormat>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, min_length = 1)]
+    pub series: Option<BookSeries>,
+
+    /// Número del libro dentro de `series`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 1000))]
+    #[schema(minimum = 1, maximum = 1000)]
+    pub series_number: Option<u16>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 4000))]
+    #[schema(min_length = 1, max_length = 4000)]
+    pub description: Option<String>,
+
+    /// URL http o https de la imagen de portada
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom(function = "http_url"))]
+    #[schema(example = "https://example.com/portada.jpg")]
+    pub cover_url: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification: Option<Classification>,
+
+    /// Signatura topográfica; determina el orden en `/books/shelf`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "863.64 G216c")]
+    pub call_number: Option<CallNumber>,
+
+    /// Fecha en que se eliminó el libro; solo aparece en los libros eliminados
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub deleted_at: Option<OffsetDateTime>,
+}
+
+/// Solo se aceptan URLs absolutas con esquema http o https
+fn http_url(url: &Option<String>) -> Result<(), ValidationError> {
+    let Some(url) = url else {
+        return Ok(());
+    };
+
+    let is_http = url.starts_with("https://") || url.starts_with("http://");
+    if !is_http || !url.validate_url() {
+        return Err(ValidationError::new("url"));
+    }
+
+    Ok(())
 }
 
 #[async_trait::async_trait]
 pub trait BookRepository: Send + Sync {
-    async fn find(&self, criteria: ReadBookCriteria) -> Result<Option<Book>, BookError>;
+    async fn find(
+        &self,
+        criteria: ReadBookCriteria,
+        deleted: DeletedFilter,
+    ) -> Result<Option<Book>, BookError>;
     async fn find_multiple(
         &self,
         criteria: ReadMultipleBooksCriteria,
+        deleted: DeletedFilter,
         page: u32,
         page_size: u8,
     ) -> Result<PaginatedBooks, BookError>;
     async fn create(&self, book: Book) -> Result<(), BookError>;
+    /// Crea el libro y guarda su primera revisión en la misma transacción
+    async fn create_with_revision(
+        &self,
+        book: Book,
+        actor: &Actor,
+        created_at: OffsetDateTime,
+    ) -> Result<u32, BookError>;
     async fn update(&self, book: Book) -> Result<(), BookError>;
-    async fn delete(&self, id: &Uuid) -> Result<(), BookError>;
+    /// Actualiza el libro y guarda la nueva versión en su historial en la misma transacción
+    async fn update_with_revision(
+        &self,
+        book: Book,
+        actor: &Actor,
+        created_at: OffsetDateTime,
+    ) -> Result<u32, BookError>;
+    /// Marca el libro como eliminado; sigue guardado hasta que se purgue
+    async fn delete(&self, id: &Uuid, deleted_at: OffsetDateTime) -> Result<(), BookError>;
+    /// Quita la marca de eliminado de un libro
+    async fn restore(&self, id: &Uuid) -> Result<(), BookError>;
+    /// Borra definitivamente los libros eliminados antes de `deleted_before` y los devuelve
+    async fn purge(&self, deleted_before: OffsetDateTime) -> Result<Vec<Book>, BookError>;
+    /// Revisiones del libro, de la más antigua a la más reciente
+    async fn revisions(&self, id: &Uuid) -> Result<Vec<BookRevision>, BookError>;
+    async fn find_revision(
+        &self,
+        id: &Uuid,
+        revision: u32,
+    ) -> Result<Option<BookRevision>, BookError>;
+    /// Busca libros vigentes y cuenta las facetas de todos los resultados
+    async fn search(
+        &self,
+        criteria: ReadMultipleBooksCriteria,
+        filters: BookFilters,
+        page: u32,
+        page_size: u8,
+    ) -> Result<BookSearchResults, BookError>;
+    async fn catalog_version(&self) -> Result<CatalogVersion, BookError>;
+}
+
+/// Qué libros eliminados considerar en una búsqueda
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DeletedFilter {
+    /// Solo los libros vigentes
+    #[default]
+    Exclude,
+
+    /// Los libros vigentes y los eliminados
+    Include,
+
+    /// Solo los libros eliminados
+    Only,
+}
+
+/// Marca de la última modificación del catálogo, se incrementa con cada cambio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CatalogVersion {
+    pub revision: u64,
+    pub modified_at: SystemTime,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -55,10 +232,31 @@ pub enum ReadBookCriteria {
 pub enum ReadMultipleBooksCriteria {
     All,
     ByTitle(String),
-    ByAuthor(String),
+    /// Nombre del colaborador y, opcionalmente, su papel en el libro
+    ByAuthor(String, Option<ContributorRole>),
+    /// Libros con signatura, ordenados como en la estantería a partir de la indicada
+    ByShelf(CallNumber, ShelfDirection),
+    /// Libros con ejemplares en la sucursal
+    ByLocation(i64),
+}
+
+/// Filtros que se combinan con el criterio de búsqueda; los nombres se comparan sin
+/// distinguir mayúsculas ni acentos
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BookFilters {
+    pub subject: Option<String>,
+    pub tag: Option<String>,
+    pub publisher: Option<String>,
+    pub language: Option<String>,
+
+    /// Primer año de la década
+    pub decade: Option<u16>,
+
+    /// Sucursal en la que el libro tiene ejemplares
+    pub location: Option<i64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PaginatedBooks {
     pub books: Vec<Book>,
     pub total: u64,
@@ -77,12 +275,30 @@ mod tests {
         let book = Book {
             id: Uuid::new_v4(),
             title: BookTitle::from_str("Title").expect("Failed to create BookTitle"),
-            authors: vec![BookAuthor::from_str("Author").expect("Failed to create BookAuthor")],
+            authors: vec![
+                BookAuthor::from_str("Author")
+                    .expect("Failed to create BookAuthor")
+                    .into(),
+            ],
             publisher: BookPublisher::from_str("Publisher")
                 .expect("Failed to create BookPublisher"),
+            subjects: Vec::new(),
+            tags: Vec::new(),
             year: 2022,
             isbn: Isbn::from_str("ISBN-10 0-596-52068-9").expect("Failed to create Isbn"),
             stored_quantity: 10,
+            subtitle: None,
+            edition: None,
+            language: None,
+            page_count: None,
+            format: None,
+            series: None,
+            series_number: None,
+            description: None,
+            cover_url: None,
+            classification: None,
+            call_number: None,
+            deleted_at: None,
         };
         assert!(book.validate().is_ok());
     }
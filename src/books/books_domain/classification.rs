@@ -0,0 +1,203 @@
+use std::sync::LazyLock;
+
+use parse_display::{Display, FromStr, helpers::regex::Regex};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::books::BookError;
+
+use super::{Book, ValidatedStr};
+
+/// Signatura topográfica: ubicación del libro en la estantería, por ejemplo `863.64 G216c`
+pub type CallNumber = ValidatedStr;
+
+static DEWEY_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d{1,3})(\.\d+)?$").unwrap());
+
+static LC_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Z]{1,3}\d{1,4}(\.\d+)?$").unwrap());
+
+/// Sistema de clasificación bibliográfica
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Display, FromStr, Serialize, Deserialize, ToSchema,
+)]
+#[display(style = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ClassificationScheme {
+    /// Clasificación Decimal Dewey, por ejemplo `863.64`
+    Dewey,
+    /// Clasificación de la Biblioteca del Congreso, por ejemplo `QA76.73`
+    Lc,
+}
+
+/// Número de clasificación del libro en el sistema indicado
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct Classification {
+    pub scheme: ClassificationScheme,
+
+    #[schema(value_type = String, example = "863.64")]
+    pub number: String,
+}
+
+impl Classification {
+    /// Valida el número según el sistema. Los números Dewey se completan con ceros a la
+    /// izquierda hasta tener tres dígitos enteros y los de LC se guardan en mayúsculas.
+    ///
+    /// # Example
+    /// ```
+    /// use library_api::books::books_domain::{Classification, ClassificationScheme};
+    ///
+    /// let dewey = Classification::new(ClassificationScheme::Dewey, "5.133").unwrap();
+    /// assert_eq!(dewey.number, "005.133");
+    /// assert!(Classification::new(ClassificationScheme::Lc, "863.64").is_err());
+    /// ```
+    pub fn new(scheme: ClassificationScheme, number: &str) -> Result<Self, BookError> {
+        let number = number.trim().to_uppercase();
+
+        let number = match scheme {
+            ClassificationScheme::Dewey => {
+                let captures = DEWEY_REGEX
+                    .captures(&number)
+                    .ok_or(BookError::InvalidClassification)?;
+                let decimals = captures.get(2).map_or("", |decimals| decimals.as_str());
+                format!("{:0>3}{decimals}", &captures[1])
+            }
+            ClassificationScheme::Lc if LC_REGEX.is_match(&number) => number,
+            ClassificationScheme::Lc => Err(BookError::InvalidClassification)?,
+        };
+
+        Ok(Self { scheme, number })
+    }
+}
+
+/// Clave para ordenar las signaturas como en la estantería. Se comparan en mayúsculas y
+/// sin espacios repetidos; la primera secuencia de dígitos y las que forman una palabra
+/// aparte (años, volúmenes) se comparan como enteros, y el resto (decimales y números de
+/// Cutter) como fracciones, por lo que `5.1` va antes que `10` y `.R87` antes que `.R9`.
+///
+/// # Example
+/// ```
+/// use library_api::books::books_domain::shelf_key;
+///
+/// assert!(shelf_key("5.1 A1") < shelf_key("10"));
+/// assert!(shelf_key("QA76.73 .R87 2019") < shelf_key("QA76.73 .R9"));
+/// assert!(shelf_key("QA9") < shelf_key("qa76"));
+/// ```
+pub fn shelf_key(call_number: &str) -> String {
+    const INTEGER_WIDTH: usize = 6;
+
+    let mut key = String::with_capacity(call_number.len() + INTEGER_WIDTH);
+    let mut seen_integer = false;
+
+    for word in call_number.split_whitespace() {
+        if !key.is_empty() {
+            key.push(' ');
+        }
+
+        let standalone = word.chars().all(|c| c.is_ascii_digit());
+        let mut chars = word.char_indices().peekable();
+
+        while let Some((start, c)) = chars.next() {
+            if !c.is_ascii_digit() {
+                key.extend(c.to_uppercase());
+                continue;
+            }
+
+            let mut end = start + c.len_utf8();
+            while let Some((index, digit)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                end = index + digit.len_utf8();
+            }
+
+            let digits = &word[start..end];
+            if standalone || !seen_integer {
+                seen_integer = true;
+                key.push_str(&format!("{digits:0>INTEGER_WIDTH$}"));
+            } else {
+                key.push_str(digits);
+            }
+        }
+    }
+
+    key
+}
+
+/// Dirección en la que se recorre la estantería desde una signatura
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShelfDirection {
+    /// Libros con una signatura anterior, del más cercano al más lejano
+    Before,
+    /// Libros con la misma signatura o una posterior, del más cercano al más lejano
+    After,
+}
+
+/// Libros alrededor de una signatura, en el orden de la estantería
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ShelfBrowse {
+    #[schema(value_type = String, example = "863.64 G216c")]
+    pub call_number: CallNumber,
+
+    /// Libros anteriores a la signatura
+    pub before: Vec<Book>,
+
+    /// Libros con la misma signatura o posteriores
+    pub after: Vec<Book>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classification_is_validated_per_scheme() {
+        let lc = Classification::new(ClassificationScheme::Lc, " qa76.73 ").unwrap();
+        assert_eq!(lc.number, "QA76.73");
+        assert_eq!(
+            Classification::new(ClassificationScheme::Dewey, "82")
+                .unwrap()
+                .number,
+            "082"
+        );
+
+        for (scheme, number) in [
+            (ClassificationScheme::Dewey, "1000"),
+            (ClassificationScheme::Dewey, "863,64"),
+            (ClassificationScheme::Lc, "QA"),
+        ] {
+            assert!(matches!(
+                Classification::new(scheme, number),
+                Err(BookError::InvalidClassification)
+            ));
+        }
+    }
+
+    #[test]
+    fn call_numbers_sort_like_the_shelf() {
+        let mut call_numbers = vec![
+            "863.7",
+            "QA76.9 .A43",
+            "82 B12",
+            "863 G2",
+            "863.64 G216c",
+            "QA76.73 .R87 2019",
+            "QA76.73 .R87 2005",
+            "QA9 .K5",
+            "863.64 G22",
+        ];
+
+        call_numbers.sort_by_key(|call_number| shelf_key(call_number));
+
+        assert_eq!(
+            call_numbers,
+            [
+                "82 B12",
+                "863 G2",
+                "863.64 G216c",
+                "863.64 G22",
+                "863.7",
+                "QA9 .K5",
+                "QA76.73 .R87 2005",
+                "QA76.73 .R87 2019",
+                "QA76.9 .A43",
+            ]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use parse_display::{Display, FromStr};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::BookAuthor;
+
+/// Papel de una persona en un libro
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Display,
+    FromStr,
+    Serialize,
+    Deserialize,
+    ToSchema,
+)]
+#[display(style = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ContributorRole {
+    #[default]
+    Author,
+    Editor,
+    Translator,
+    Illustrator,
+}
+
+/// Persona que participa en un libro con un papel determinado
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(from = "ContributorRepr")]
+pub struct BookContributor {
+    #[schema(value_type = String, min_length = 1)]
+    pub name: BookAuthor,
+
+    pub role: ContributorRole,
+}
+
+impl BookContributor {
+    pub fn new(name: BookAuthor, role: ContributorRole) -> Self {
+        Self { name, role }
+    }
+}
+
+impl From<BookAuthor> for BookContributor {
+    fn from(name: BookAuthor) -> Self {
+        Self::new(name, ContributorRole::Author)
+    }
+}
+
+/// Las revisiones guardadas antes de los papeles tienen solo el nombre de cada autor
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ContributorRepr {
+    Name(BookAuthor),
+    Contributor {
+        name: BookAuthor,
+        #[serde(default)]
+        role: ContributorRole,
+    },
+}
+
+impl From<ContributorRepr> for BookContributor {
+    fn from(repr: ContributorRepr) -> Self {
+        match repr {
+            ContributorRepr::Name(name) => name.into(),
+            ContributorRepr::Contributor { name, role } => Self::new(name, role),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn contributor_accepts_plain_names() {
+        let contributors = serde_json::from_value::<Vec<BookContributor>>(json!([
+            "Jorge Luis Borges",
+            { "name": "Norman Thomas di Giovanni", "role": "translator" },
+        ]))
+        .expect("Error al leer colaboradores");
+
+        assert_eq!(contributors[0].role, ContributorRole::Author);
+        assert_eq!(contributors[1].role, ContributorRole::Translator);
+        assert_eq!(
+            serde_json::to_value(&contributors[0]).unwrap(),
+            json!({ "name": "Jorge Luis Borges", "role": "author" })
+        );
+    }
+
+    #[test]
+    fn role_round_trips_as_text() {
+        let role = ContributorRole::from_str("illustrator").expect("Papel inválido");
+
+        assert_eq!(role, ContributorRole::Illustrator);
+        assert_eq!(role.to_string(), "illustrator");
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::PaginatedBooks;
+
+/// Cantidad de libros encontrados con un mismo valor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: u64,
+}
+
+/// Facetas de una búsqueda, calculadas sobre todos los resultados y no solo la página.
+/// Cada lista va de la más frecuente a la menos frecuente, salvo las décadas, que van en
+/// orden cronológico.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct BookFacets {
+    pub subjects: Vec<FacetCount>,
+    pub publishers: Vec<FacetCount>,
+
+    /// Primer año de cada década, por ejemplo `1960`
+    pub decades: Vec<FacetCount>,
+
+    /// Códigos ISO 639; los libros sin idioma no se cuentan
+    pub languages: Vec<FacetCount>,
+}
+
+/// Página de libros encontrados junto con las facetas para refinar la búsqueda
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BookSearchResults {
+    #[serde(flatten)]
+    pub results: PaginatedBooks,
+
+    pub facets: BookFacets,
+}
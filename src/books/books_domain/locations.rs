@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{audit::audit_domain::Actor, books::BookError};
+
+use super::ValidatedStr;
+
+pub type LocationName = ValidatedStr;
+
+/// Sucursal o ubicación donde se guardan ejemplares
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Location {
+    pub id: i64,
+
+    #[schema(value_type = String, min_length = 1)]
+    pub name: LocationName,
+
+    /// Ejemplares de libros no eliminados en la sucursal
+    pub copies: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaginatedLocations {
+    pub locations: Vec<Location>,
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u8,
+}
+
+/// Ejemplares de un libro en una sucursal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LocationStock {
+    pub location_id: i64,
+
+    #[schema(value_type = String)]
+    pub location: LocationName,
+
+    pub quantity: u16,
+}
+
+/// Disponibilidad de un libro por sucursal. Los ejemplares que aún no se asignaron a
+/// ninguna sucursal se cuentan en `unassigned`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct BookAvailability {
+    pub book_id: Uuid,
+    pub stored_quantity: u16,
+    pub unassigned: u16,
+    pub locations: Vec<LocationStock>,
+}
+
+impl BookAvailability {
+    pub fn new(book_id: Uuid, stored_quantity: u16, locations: Vec<LocationStock>) -> Self {
+        let assigned = locations.iter().map(|stock| stock.quantity).sum::<u16>();
+
+        Self {
+            book_id,
+            stored_quantity,
+            unassigned: stored_quantity.saturating_sub(assigned),
+            locations,
+        }
+    }
+
+    /// Ejemplares en la sucursal; cero si no tiene
+    pub fn quantity_at(&self, location_id: i64) -> u16 {
+        self.locations
+            .iter()
+            .find(|stock| stock.location_id == location_id)
+            .map_or(0, |stock| stock.quantity)
+    }
+}
+
+/// Traslado de ejemplares de un libro entre dos sucursales
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StockMovement {
+    pub id: i64,
+    pub book_id: Uuid,
+    pub from_location_id: i64,
+    pub to_location_id: i64,
+    pub quantity: u16,
+    pub actor: Actor,
+
+    #[serde(with = "time::serde::rfc3339")]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: OffsetDateTime,
+}
+
+/// Traslado por registrar; el repositorio le asigna el identificador
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewStockMovement {
+    pub book_id: Uuid,
+    pub from_location_id: i64,
+    pub to_location_id: i64,
+    pub quantity: u16,
+    pub actor: Actor,
+    pub created_at: OffsetDateTime,
+}
+
+/// Sucursales y los ejemplares de cada libro en ellas
+#[async_trait::async_trait]
+pub trait LocationRepository: Send + Sync {
+    /// Sucursales ordenadas por nombre
+    async fn find_locations(
+        &self,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedLocations, BookError>;
+    async fn find_location(&self, id: i64) -> Result<Option<Location>, BookError>;
+    /// Agrega una sucursal; falla si ya existe una con un nombre equivalente
+    async fn create_location(&self, name: &LocationName) -> Result<Location, BookError>;
+    /// Ejemplares del libro en cada sucursal que tiene alguno, ordenados por sucursal
+    async fn stock(&self, id: &Uuid) -> Result<Vec<LocationStock>, BookError>;
+    /// Fija los ejemplares del libro en la sucursal; con cero la quita de su disponibilidad
+    async fn set_stock(&self, id: &Uuid, location: i64, quantity: u16) -> Result<(), BookError>;
+    /// Mueve ejemplares entre sucursales y registra el traslado en una misma transacción;
+    /// falla si la sucursal de origen no tiene suficientes
+    async fn transfer(&self, movement: NewStockMovement) -> Result<StockMovement, BookError>;
+    /// Traslados del libro, del más reciente al más antiguo
+    async fn movements(&self, id: &Uuid) -> Result<Vec<StockMovement>, BookError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn availability_counts_unassigned_copies() {
+        let stock = |location_id, quantity| LocationStock {
+            location_id,
+            location: LocationName::from_str("Sucursal").unwrap(),
+            quantity,
+        };
+
+        let availability = BookAvailability::new(Uuid::new_v4(), 5, vec![stock(1, 2), stock(2, 1)]);
+
+        assert_eq!(availability.unassigned, 2);
+        assert_eq!(availability.quantity_at(2), 1);
+        assert_eq!(availability.quantity_at(3), 0);
+    }
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::books::BookError;
+
+use super::BookPublisher;
+
+/// Editorial del catálogo con la cantidad de libros vigentes que tiene asociados
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Publisher {
+    pub id: i64,
+
+    #[schema(value_type = String, min_length = 1)]
+    pub name: BookPublisher,
+
+    /// Libros no eliminados de la editorial
+    pub book_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaginatedPublishers {
+    pub publishers: Vec<Publisher>,
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u8,
+}
+
+/// Editoriales del catálogo
+#[async_trait::async_trait]
+pub trait PublisherRepository: Send + Sync {
+    /// Editoriales cuyo nombre contiene `name`, sin distinguir mayúsculas ni acentos
+    async fn find_publishers(
+        &self,
+        name: Option<String>,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedPublishers, BookError>;
+    async fn find_publisher(&self, id: i64) -> Result<Option<Publisher>, BookError>;
+    /// Asigna los libros de `source` a `target` y elimina `source`
+    async fn merge_publishers(&self, source: i64, target: i64) -> Result<(), BookError>;
+}
@@ -0,0 +1,147 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::audit::audit_domain::Actor;
+
+use super::Book;
+
+/// Versión completa de un libro guardada después de crearlo o actualizarlo
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BookRevision {
+    /// Número de revisión, comenzando en 1
+    pub revision: u32,
+
+    #[serde(with = "time::serde::rfc3339")]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: OffsetDateTime,
+
+    pub actor: Actor,
+    pub book: Book,
+}
+
+/// Campo que cambió entre dos revisiones consecutivas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldChange {
+    pub field: String,
+
+    #[schema(value_type = Option<Object>)]
+    pub before: Value,
+
+    #[schema(value_type = Option<Object>)]
+    pub after: Value,
+}
+
+impl FieldChange {
+    /// Campos que difieren entre `before` y `after`, en orden alfabético
+    pub fn between(before: &Book, after: &Book) -> Vec<FieldChange> {
+        let (Ok(Value::Object(before)), Ok(Value::Object(after))) =
+            (serde_json::to_value(before), serde_json::to_value(after))
+        else {
+            return Vec::new();
+        };
+
+        let fields = before.keys().chain(after.keys()).collect::<BTreeSet<_>>();
+
+        fields
+            .into_iter()
+            .filter(|field| *field != "id")
+            .filter_map(|field| {
+                let old = before.get(field).cloned().unwrap_or(Value::Null);
+                let new = after.get(field).cloned().unwrap_or(Value::Null);
+                (old != new).then(|| FieldChange {
+                    field: field.clone(),
+                    before: old,
+                    after: new,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Revisión de un libro junto con los cambios respecto a la revisión anterior
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BookHistoryEntry {
+    #[serde(flatten)]
+    pub revision: BookRevision,
+
+    /// Vacío en la primera revisión
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BookHistory {
+    pub book_id: Uuid,
+
+    /// Revisiones de la más antigua a la más reciente
+    pub revisions: Vec<BookHistoryEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::books::books_domain::{BookAuthor, BookPublisher, BookTitle, Isbn};
+
+    fn book() -> Book {
+        Book {
+            id: Uuid::new_v4(),
+            title: BookTitle::from_str("Ficciones").unwrap(),
+            authors: vec![BookAuthor::from_str("Jorge Luis Borges").unwrap().into()],
+            publisher: BookPublisher::from_str("Sur").unwrap(),
+            subjects: Vec::new(),
+            tags: Vec::new(),
+            year: 1944,
+            isbn: Isbn::from_str("978-0-306-40615-7").unwrap(),
+            stored_quantity: 2,
+            subtitle: None,
+            edition: None,
+            language: None,
+            page_count: None,
+            format: None,
+            series: None,
+            series_number: None,
+            description: None,
+            cover_url: None,
+            classification: None,
+            call_number: None,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn changes_list_only_modified_fields() {
+        let before = book();
+        let after = Book {
+            title: BookTitle::from_str("El Aleph").unwrap(),
+            stored_quantity: 5,
+            ..before.clone()
+        };
+
+        let changes = FieldChange::between(&before, &after);
+
+        assert_eq!(
+            changes,
+            [
+                FieldChange {
+                    field: "stored_quantity".to_string(),
+                    before: json!(2),
+                    after: json!(5),
+                },
+                FieldChange {
+                    field: "title".to_string(),
+                    before: json!("Ficciones"),
+                    after: json!("El Aleph"),
+                },
+            ]
+        );
+        assert!(FieldChange::between(&before, &before).is_empty());
+    }
+}
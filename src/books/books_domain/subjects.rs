@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::books::BookError;
+
+use super::{BookSubject, BookTag};
+
+/// Tema del vocabulario controlado con la cantidad de libros vigentes que lo tienen
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Subject {
+    pub id: i64,
+
+    #[schema(value_type = String, min_length = 1)]
+    pub name: BookSubject,
+
+    /// Libros no eliminados con el tema
+    pub book_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaginatedSubjects {
+    pub subjects: Vec<Subject>,
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u8,
+}
+
+/// Etiqueta libre con la cantidad de libros vigentes que la tienen
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Tag {
+    pub id: i64,
+
+    #[schema(value_type = String, min_length = 1)]
+    pub name: BookTag,
+
+    /// Libros no eliminados con la etiqueta
+    pub book_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaginatedTags {
+    pub tags: Vec<Tag>,
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u8,
+}
+
+/// Vocabulario de temas y etiquetas de los libros
+#[async_trait::async_trait]
+pub trait TermRepository: Send + Sync {
+    /// Temas cuyo nombre contiene `name`, sin distinguir mayúsculas ni acentos
+    async fn find_subjects(
+        &self,
+        name: Option<String>,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedSubjects, BookError>;
+    /// Agrega un tema al vocabulario; falla si ya existe uno equivalente
+    async fn create_subject(&self, name: &BookSubject) -> Result<Subject, BookError>;
+    /// Etiquetas cuyo nombre contiene `name`, sin distinguir mayúsculas ni acentos
+    async fn find_tags(
+        &self,
+        name: Option<String>,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedTags, BookError>;
+}
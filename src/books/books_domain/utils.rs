@@ -5,6 +5,7 @@ use std::{
 
 use parse_display::{Display, helpers::regex::Regex};
 use serde::{Deserialize, Serialize};
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
 
 use crate::books::BookError;
 
@@ -23,6 +24,24 @@ impl ValidatedStr {
     }
 }
 
+/// Forma del texto para comparar nombres sin distinguir mayúsculas, acentos ni espacios repetidos
+///
+/// # Example
+/// ```
+/// use library_api::books::books_domain::fold_text;
+///
+/// assert_eq!(fold_text(" Ediciones  Cátedra "), "ediciones catedra");
+/// ```
+pub fn fold_text(text: &str) -> String {
+    text.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 impl FromStr for ValidatedStr {
     type Err = BookError;
 
@@ -58,6 +77,51 @@ impl From<ValidatedStr> for String {
     }
 }
 
+static VALID_LANGUAGE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-z]{2,3}$").unwrap());
+
+/// Código de idioma ISO 639, de dos (639-1) o tres letras (639-2/3), guardado en minúsculas
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+#[serde(try_from = "String", into = "String")]
+pub struct LanguageCode {
+    value: Arc<str>,
+}
+
+impl LanguageCode {
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl FromStr for LanguageCode {
+    type Err = BookError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lowercase = s.trim().to_ascii_lowercase();
+        if !VALID_LANGUAGE_REGEX.is_match(&lowercase) {
+            Err(BookError::InvalidLanguage)?;
+        }
+
+        Ok(Self {
+            value: Arc::from(lowercase),
+        })
+    }
+}
+
+impl TryFrom<String> for LanguageCode {
+    type Error = BookError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl From<LanguageCode> for String {
+    fn from(value: LanguageCode) -> Self {
+        value.value.to_string()
+    }
+}
+
 static VALID_ISBN_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
         r"^(?:ISBN[- ]?(?:10|13)?:? )?(?:\d{3}[- ]?)?\d{1,5}[- ]?\d{1,7}[- ]?\d{1,7}[- ]?[0-9X]$",
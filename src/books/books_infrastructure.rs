@@ -1,8 +1,19 @@
+mod authors_sqlite_repository;
 mod books_sqlite_repository;
 mod db_dtos;
-mod singletons;
+mod locations_sqlite_repository;
+mod middlewares;
+mod publishers_sqlite_repository;
+mod state;
+mod terms_sqlite_repository;
 
 pub mod controllers;
 
+pub use authors_sqlite_repository::*;
 pub use books_sqlite_repository::*;
 pub use db_dtos::*;
+pub use locations_sqlite_repository::*;
+pub use middlewares::*;
+pub use publishers_sqlite_repository::*;
+pub use state::*;
+pub use terms_sqlite_repository::*;
@@ -0,0 +1,348 @@
+use std::cmp::max;
+
+use sqlx::{Pool, Sqlite, SqlitePool, query, query_as};
+
+use crate::books::{
+    BookError,
+    books_domain::{
+        Author, AuthorRepository, Book, BookAuthor, PaginatedAuthors, ReadAuthorCriteria,
+    },
+    books_infrastructure::db_dtos::{DbAuthorWithCount, DbBook},
+};
+
+use super::books_sqlite_repository::{BookSqliteRepository, SELECT_BOOKS, like_pattern};
+
+/// Autores guardados en las tablas `authors` y `book_authors`
+#[derive(Clone)]
+pub struct AuthorSqliteRepository {
+    pub pool: Pool<Sqlite>,
+}
+
+impl AuthorSqliteRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthorRepository for AuthorSqliteRepository {
+    async fn find_authors(
+        &self,
+        name: Option<String>,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedAuthors, BookError> {
+        let page = max(page, 1); // prevenimos un substract-overflow
+        let offset = (page - 1) as i64 * page_size as i64;
+        let limit = page_size as i64;
+        let pattern = like_pattern(&name.unwrap_or_default());
+
+        let authors = query_as::<_, DbAuthorWithCount>(
+            r#"
+            SELECT a.id, a.name, COUNT(DISTINCT b.id) AS book_count
+            FROM authors a
+            LEFT JOIN book_authors ba ON a.id = ba.author_id
+            LEFT JOIN books b ON ba.book_id = b.id AND b.deleted_at IS NULL
+            WHERE a.name LIKE ? ESCAPE '\'
+            GROUP BY a.id
+            ORDER BY a.name
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(&pattern)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| BookError::DatabaseError(format!("Error al buscar autores: {}", e).into()))?;
+
+        let (total,) =
+            query_as::<_, (i64,)>("SELECT COUNT(*) FROM authors WHERE name LIKE ? ESCAPE '\\'")
+                .bind(&pattern)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| {
+                    BookError::DatabaseError(format!("Error al contar autores: {}", e).into())
+                })?;
+
+        Ok(PaginatedAuthors {
+            authors: authors
+                .iter()
+                .map(DbAuthorWithCount::to_domain)
+                .collect::<Result<_, _>>()?,
+            total: total as u64,
+            page,
+            page_size,
+        })
+    }
+
+    async fn find_author(&self, criteria: ReadAuthorCriteria) -> Result<Option<Author>, BookError> {
+        let (param, column) = match criteria {
+            ReadAuthorCriteria::ById(id) => (id.to_string(), "id"),
+            ReadAuthorCriteria::ByName(name) => (name, "name"),
+        };
+
+        let query = format!(
+            r#"
+            SELECT a.id, a.name, COUNT(DISTINCT b.id) AS book_count
+            FROM authors a
+            LEFT JOIN book_authors ba ON a.id = ba.author_id
+            LEFT JOIN books b ON ba.book_id = b.id AND b.deleted_at IS NULL
+            WHERE a.{column} = ?
+            GROUP BY a.id
+            "#
+        );
+
+        let author = query_as::<_, DbAuthorWithCount>(&query)
+            .bind(&param)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al buscar autor: {}", e).into())
+            })?;
+
+        author
+            .as_ref()
+            .map(DbAuthorWithCount::to_domain)
+            .transpose()
+    }
+
+    async fn author_books(&self, id: i64) -> Result<Vec<Book>, BookError> {
+        let books = query_as::<_, DbBook>(&format!(
+            r#"
+            {SELECT_BOOKS}
+            WHERE b.id IN (SELECT book_id FROM book_authors WHERE author_id = ?)
+              AND b.deleted_at IS NULL
+            ORDER BY b.title
+            "#
+        ))
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al buscar libros del autor: {}", e).into())
+        })?;
+
+        BookSqliteRepository::new(self.pool.clone())
+            .to_domain_books(books)
+            .await
+    }
+
+    async fn rename_author(&self, id: i64, name: &BookAuthor) -> Result<(), BookError> {
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al renombrar autor: {}", e).into())
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+
+        let result = query("UPDATE authors SET name = ? WHERE id = ?")
+            .bind(name.as_str())
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| match e.as_database_error() {
+                Some(db_error) if db_error.is_unique_violation() => {
+                    BookError::AuthorAlreadyExists(name.to_string())
+                }
+                _ => map_err(e),
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(BookError::AuthorNotFound);
+        }
+
+        BookSqliteRepository::touch_catalog(&mut tx).await?;
+
+        tx.commit().await.map_err(map_err)
+    }
+
+    async fn merge_authors(&self, source: i64, target: i64) -> Result<(), BookError> {
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al fusionar autores: {}", e).into())
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+
+        // Los libros que ya tenían a ambos autores en el mismo papel conservan una sola relación
+        query(
+            r#"
+            INSERT OR IGNORE INTO book_authors (book_id, author_id, role, position)
+            SELECT book_id, ?, role, position FROM book_authors WHERE author_id = ?
+            "#,
+        )
+        .bind(target)
+        .bind(source)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_err)?;
+
+        query("DELETE FROM book_authors WHERE author_id = ?")
+            .bind(source)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+        let result = query("DELETE FROM authors WHERE id = ?")
+            .bind(source)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+        if result.rows_affected() == 0 {
+            return Err(BookError::AuthorNotFound);
+        }
+
+        BookSqliteRepository::touch_catalog(&mut tx).await?;
+
+        tx.commit().await.map_err(map_err)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use time::OffsetDateTime;
+
+    use crate::books::{
+        books_domain::{BookRepository, DeletedFilter, ReadBookCriteria},
+        books_infrastructure::books_sqlite_repository::tests::{create_test_book, setup_test_db},
+    };
+
+    pub(crate) async fn find_author_by_name(
+        author_repo: &AuthorSqliteRepository,
+        name: &str,
+    ) -> Option<Author> {
+        author_repo
+            .find_author(ReadAuthorCriteria::ByName(name.to_string()))
+            .await
+            .expect("Failed to find author")
+    }
+
+    #[tokio::test]
+    async fn test_authors_are_merged_and_orphans_removed() {
+        // Arrange
+        let repo = setup_test_db().await;
+        let author_repo = AuthorSqliteRepository::new(repo.pool.clone());
+        let book = create_test_book();
+        let book_id = book.id;
+        repo.create(book.clone())
+            .await
+            .expect("Failed to create book");
+        let one = find_author_by_name(&author_repo, "Author One")
+            .await
+            .expect("Author should exist");
+        let two = find_author_by_name(&author_repo, "Author Two")
+            .await
+            .expect("Author should exist");
+
+        // Act - Merge
+        author_repo
+            .merge_authors(one.id, two.id)
+            .await
+            .expect("Failed to merge authors");
+
+        // Assert
+        assert!(
+            find_author_by_name(&author_repo, "Author One")
+                .await
+                .is_none()
+        );
+        let merged = find_author_by_name(&author_repo, "Author Two")
+            .await
+            .expect("Author should exist");
+        assert_eq!(merged.book_count, 1);
+        let found = repo
+            .find(ReadBookCriteria::ById(book_id), DeletedFilter::Exclude)
+            .await
+            .expect("Failed to find book")
+            .expect("Book should exist");
+        assert_eq!(found.authors, [two.name.into()]);
+
+        // Act - Replace the only author
+        let updated = Book {
+            authors: vec![
+                BookAuthor::try_from("Author Three".to_string())
+                    .unwrap()
+                    .into(),
+            ],
+            ..book
+        };
+        repo.update(updated).await.expect("Failed to update book");
+
+        // Assert - Authors without books are removed
+        assert!(
+            find_author_by_name(&author_repo, "Author Two")
+                .await
+                .is_none()
+        );
+        let listed = author_repo
+            .find_authors(None, 1, 10)
+            .await
+            .expect("Failed to list authors");
+        assert_eq!(listed.total, 1);
+
+        // Deleted books keep their authors until purged
+        let now = OffsetDateTime::now_utc();
+        repo.delete(&book_id, now - time::Duration::days(1))
+            .await
+            .expect("Failed to delete book");
+        let three = find_author_by_name(&author_repo, "Author Three")
+            .await
+            .expect("Author should exist");
+        assert_eq!(three.book_count, 0);
+
+        repo.purge(now).await.expect("Failed to purge books");
+        assert!(
+            find_author_by_name(&author_repo, "Author Three")
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_authors_matches_wildcards_literally() {
+        // Arrange
+        let repo = setup_test_db().await;
+        let author_repo = AuthorSqliteRepository::new(repo.pool.clone());
+        let book = Book {
+            authors: vec![
+                BookAuthor::try_from("Author One".to_string())
+                    .unwrap()
+                    .into(),
+                BookAuthor::try_from("100% Author_X".to_string())
+                    .unwrap()
+                    .into(),
+            ],
+            ..create_test_book()
+        };
+        repo.create(book).await.expect("Failed to create book");
+        let names = |authors: PaginatedAuthors| {
+            authors
+                .authors
+                .into_iter()
+                .map(|author| author.name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // Act
+        let underscore = author_repo
+            .find_authors(Some("_".to_string()), 1, 10)
+            .await
+            .expect("Failed to list authors");
+        let percent = author_repo
+            .find_authors(Some("%".to_string()), 1, 10)
+            .await
+            .expect("Failed to list authors");
+        let backslash = author_repo
+            .find_authors(Some("\\".to_string()), 1, 10)
+            .await
+            .expect("Failed to list authors");
+
+        // Assert - `%` and `_` only match themselves
+        assert_eq!(underscore.total, 1);
+        assert_eq!(names(underscore), ["100% Author_X"]);
+        assert_eq!(names(percent), ["100% Author_X"]);
+        assert_eq!(backslash.total, 0);
+    }
+}
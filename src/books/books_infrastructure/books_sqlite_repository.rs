@@ -1,34 +1,148 @@
-use std::cmp::max;
+use std::{
+    cmp::max,
+    collections::BTreeMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-use sqlx::{Pool, Sqlite, query, query_as};
+use sqlx::{Pool, Sqlite, SqliteConnection, SqlitePool, query, query_as};
+use time::OffsetDateTime;
+use tracing::info;
 use uuid::Uuid;
 
-use crate::books::{
-    BookError,
-    books_domain::{
-        Book, BookAuthor, BookRepository, PaginatedBooks, ReadBookCriteria,
-        ReadMultipleBooksCriteria,
+use crate::{
+    audit::audit_domain::Actor,
+    books::{
+        BookError,
+        books_domain::{
+            Book, BookAuthor, BookContributor, BookEdition, BookFacets, BookFilters, BookPublisher,
+            BookRepository, BookRevision, BookSearchResults, BookSeries, BookSubject, BookTag,
+            BookTitle, CallNumber, CatalogVersion, DeletedFilter, FacetCount, LanguageCode,
+            PaginatedBooks, ReadBookCriteria, ReadMultipleBooksCriteria, ShelfDirection,
+            ValidatedStr, fold_text, shelf_key,
+        },
+        books_infrastructure::db_dtos::{DbBook, DbBookRevision, DbContributor, DbFacetCount},
     },
-    books_infrastructure::db_dtos::{DbAuthorName, DbBook},
+    i18n::tr,
+};
+
+/// Relación libro-autor; una persona puede tener varios papeles en el mismo libro y
+/// `position` conserva el orden en que se catalogaron
+const CREATE_BOOK_AUTHORS: &str = r#"
+    CREATE TABLE IF NOT EXISTS book_authors (
+        book_id TEXT NOT NULL,
+        author_id INTEGER NOT NULL,
+        role TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        PRIMARY KEY (book_id, author_id, role),
+        FOREIGN KEY (book_id) REFERENCES books(id) ON DELETE CASCADE,
+        FOREIGN KEY (author_id) REFERENCES authors(id) ON DELETE CASCADE
+    )
+"#;
+
+/// Datos bibliográficos opcionales de `books`, que las bases de datos anteriores no tienen.
+/// `shelf_key` es la clave de orden de `call_number`, calculada con [`shelf_key`].
+const DETAIL_COLUMNS: [(&str, &str); 13] = [
+    ("subtitle", "TEXT"),
+    ("edition", "TEXT"),
+    ("language", "TEXT"),
+    ("page_count", "INTEGER"),
+    ("format", "TEXT"),
+    ("series", "TEXT"),
+    ("series_number", "INTEGER"),
+    ("description", "TEXT"),
+    ("cover_url", "TEXT"),
+    ("classification_scheme", "TEXT"),
+    ("classification", "TEXT"),
+    ("call_number", "TEXT"),
+    ("shelf_key", "TEXT"),
+];
+
+/// Vocabulario asociado a los libros: la tabla de términos y la relación con `books`
+pub(super) struct Terms {
+    pub(super) table: &'static str,
+    pub(super) relation: &'static str,
+    pub(super) column: &'static str,
+}
+
+/// Temas del vocabulario controlado; solo se crean desde `/subjects`
+pub(super) const SUBJECTS: Terms = Terms {
+    table: "subjects",
+    relation: "book_subjects",
+    column: "subject_id",
+};
+
+/// Etiquetas libres; se crean al guardar un libro y se eliminan al quedar sin libros
+pub(super) const TAGS: Terms = Terms {
+    table: "tags",
+    relation: "book_tags",
+    column: "tag_id",
 };
 
+/// Valores distintos que se devuelven por cada faceta de una búsqueda
+const FACET_LIMIT: u32 = 20;
+
+/// Columnas de [`DbBook`]; el nombre de la editorial se toma de `publishers`
+pub(super) const SELECT_BOOKS: &str = r#"
+    SELECT b.id, b.title, p.name AS publisher, b.year, b.isbn, b.stored, b.deleted_at,
+           b.subtitle, b.edition, b.language, b.page_count, b.format, b.series,
+           b.series_number, b.description, b.cover_url, b.classification_scheme,
+           b.classification, b.call_number
+    FROM books b
+    JOIN publishers p ON b.publisher_id = p.id
+"#;
+
 #[derive(Clone)]
 pub struct BookSqliteRepository {
     pub pool: Pool<Sqlite>,
 }
 
 impl BookSqliteRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
     pub async fn init_tables(&self) -> Result<(), BookError> {
+        // Crear tabla de editoriales; `normalized_name` evita duplicados que solo difieren
+        // en mayúsculas, acentos o espacios
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS publishers (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                normalized_name TEXT NOT NULL UNIQUE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear tabla publishers: {}", e).into())
+        })?;
+
         // Crear tabla de libros
         query(
             r#"
             CREATE TABLE IF NOT EXISTS books (
                 id TEXT PRIMARY KEY,
                 title TEXT NOT NULL,
-                publisher TEXT NOT NULL,
+                publisher_id INTEGER NOT NULL REFERENCES publishers(id),
                 year INTEGER NOT NULL,
                 isbn TEXT NOT NULL,
                 stored INTEGER NOT NULL,
+                deleted_at INTEGER,
+                subtitle TEXT,
+                edition TEXT,
+                language TEXT,
+                page_count INTEGER,
+                format TEXT,
+                series TEXT,
+                series_number INTEGER,
+                description TEXT,
+                cover_url TEXT,
+                classification_scheme TEXT,
+                classification TEXT,
+                call_number TEXT,
+                shelf_key TEXT,
                 UNIQUE(isbn)
             )
             "#,
@@ -39,6 +153,79 @@ impl BookSqliteRepository {
             BookError::DatabaseError(format!("Error al crear tabla books: {}", e).into())
         })?;
 
+        // Las bases de datos creadas antes de la eliminación lógica no tienen la columna
+        let (has_deleted_at,) = query_as::<_, (bool,)>(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('books') WHERE name = 'deleted_at'",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al consultar columnas de books: {}", e).into())
+        })?;
+
+        if !has_deleted_at {
+            query("ALTER TABLE books ADD COLUMN deleted_at INTEGER")
+                .execute(&self.pool)
+                .await
+                .map_err(|e| {
+                    BookError::DatabaseError(
+                        format!("Error al agregar columna deleted_at: {}", e).into(),
+                    )
+                })?;
+        }
+
+        // Los libros existentes quedan sin datos bibliográficos opcionales
+        for (column, column_type) in DETAIL_COLUMNS {
+            let (has_column,) = query_as::<_, (bool,)>(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('books') WHERE name = ?",
+            )
+            .bind(column)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(
+                    format!("Error al consultar columnas de books: {}", e).into(),
+                )
+            })?;
+
+            if !has_column {
+                query(&format!(
+                    "ALTER TABLE books ADD COLUMN {column} {column_type}"
+                ))
+                .execute(&self.pool)
+                .await
+                .map_err(|e| {
+                    BookError::DatabaseError(
+                        format!("Error al agregar columna {column}: {}", e).into(),
+                    )
+                })?;
+            }
+        }
+
+        // El recorrido de la estantería ordena y filtra por `shelf_key`
+        query("CREATE INDEX IF NOT EXISTS books_shelf_key ON books(shelf_key)")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(
+                    format!("Error al crear índice books_shelf_key: {}", e).into(),
+                )
+            })?;
+
+        // Las bases de datos anteriores guardan el nombre de la editorial en cada libro
+        let (has_publisher,) = query_as::<_, (bool,)>(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('books') WHERE name = 'publisher'",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al consultar columnas de books: {}", e).into())
+        })?;
+
+        if has_publisher {
+            self.migrate_publishers().await?;
+        }
+
         // Crear tabla de autores
         query(
             r#"
@@ -55,32 +242,349 @@ impl BookSqliteRepository {
         })?;
 
         // Crear tabla de relación libro-autor
+        query(CREATE_BOOK_AUTHORS)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al crear tabla book_authors: {}", e).into())
+            })?;
+
+        // Las bases de datos anteriores no guardan el papel ni el orden de los autores
+        let (has_position,) = query_as::<_, (bool,)>(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('book_authors') WHERE name = 'position'",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(
+                format!("Error al consultar columnas de book_authors: {}", e).into(),
+            )
+        })?;
+
+        if !has_position {
+            self.migrate_book_authors().await?;
+        }
+
+        // Crear tablas de temas y etiquetas, con la misma normalización que las editoriales
+        for terms in [&SUBJECTS, &TAGS] {
+            let Terms {
+                table,
+                relation,
+                column,
+            } = terms;
+
+            query(&format!(
+                r#"
+                CREATE TABLE IF NOT EXISTS {table} (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name TEXT NOT NULL,
+                    normalized_name TEXT NOT NULL UNIQUE
+                )
+                "#
+            ))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al crear tabla {table}: {}", e).into())
+            })?;
+
+            query(&format!(
+                r#"
+                CREATE TABLE IF NOT EXISTS {relation} (
+                    book_id TEXT NOT NULL,
+                    {column} INTEGER NOT NULL,
+                    PRIMARY KEY (book_id, {column}),
+                    FOREIGN KEY (book_id) REFERENCES books(id) ON DELETE CASCADE,
+                    FOREIGN KEY ({column}) REFERENCES {table}(id) ON DELETE CASCADE
+                )
+                "#
+            ))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al crear tabla {relation}: {}", e).into())
+            })?;
+        }
+
+        // Versiones anteriores no eliminaban los autores que quedaban sin libros
+        let mut conn = self.pool.acquire().await.map_err(|e| {
+            BookError::DatabaseError(format!("Error al obtener conexión: {}", e).into())
+        })?;
+        Self::delete_orphans(&mut conn).await?;
+        drop(conn);
+
+        // Crear tabla de revisiones, con la versión completa del libro en JSON
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS book_revisions (
+                book_id TEXT NOT NULL,
+                revision INTEGER NOT NULL,
+                snapshot TEXT NOT NULL,
+                actor TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (book_id, revision),
+                FOREIGN KEY (book_id) REFERENCES books(id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear tabla book_revisions: {}", e).into())
+        })?;
+
+        // Crear tabla de sucursales, con la misma normalización que las editoriales
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS locations (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                normalized_name TEXT NOT NULL UNIQUE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear tabla locations: {}", e).into())
+        })?;
+
+        // Crear tabla de ejemplares por sucursal; las sucursales sin ejemplares no tienen fila
         query(
             r#"
-            CREATE TABLE IF NOT EXISTS book_authors (
+            CREATE TABLE IF NOT EXISTS book_stock (
                 book_id TEXT NOT NULL,
-                author_id INTEGER NOT NULL,
-                PRIMARY KEY (book_id, author_id),
+                location_id INTEGER NOT NULL,
+                quantity INTEGER NOT NULL CHECK (quantity > 0),
+                PRIMARY KEY (book_id, location_id),
                 FOREIGN KEY (book_id) REFERENCES books(id) ON DELETE CASCADE,
-                FOREIGN KEY (author_id) REFERENCES authors(id) ON DELETE CASCADE
+                FOREIGN KEY (location_id) REFERENCES locations(id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear tabla book_stock: {}", e).into())
+        })?;
+
+        // Crear tabla de traslados entre sucursales
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS stock_movements (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                book_id TEXT NOT NULL,
+                from_location_id INTEGER NOT NULL REFERENCES locations(id),
+                to_location_id INTEGER NOT NULL REFERENCES locations(id),
+                quantity INTEGER NOT NULL,
+                actor TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                FOREIGN KEY (book_id) REFERENCES books(id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear tabla stock_movements: {}", e).into())
+        })?;
+
+        // Crear tabla con la versión del catálogo (una sola fila)
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS catalog_state (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                revision INTEGER NOT NULL,
+                modified_at INTEGER NOT NULL
             )
             "#,
         )
         .execute(&self.pool)
         .await
         .map_err(|e| {
-            BookError::DatabaseError(format!("Error al crear tabla book_authors: {}", e).into())
+            BookError::DatabaseError(format!("Error al crear tabla catalog_state: {}", e).into())
         })?;
 
+        query("INSERT OR IGNORE INTO catalog_state (id, revision, modified_at) VALUES (1, 0, ?)")
+            .bind(unix_millis_now())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(
+                    format!("Error al inicializar versión del catálogo: {}", e).into(),
+                )
+            })?;
+
+        Ok(())
+    }
+
+    /// Registra un cambio en el catálogo, invalidando las respuestas cacheadas. Debe ejecutarse
+    /// en la misma transacción que el cambio, para que la versión no quede desactualizada.
+    pub(super) async fn touch_catalog(conn: &mut SqliteConnection) -> Result<(), BookError> {
+        query("UPDATE catalog_state SET revision = revision + 1, modified_at = ? WHERE id = 1")
+            .bind(unix_millis_now())
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(
+                    format!("Error al actualizar versión del catálogo: {}", e).into(),
+                )
+            })?;
+
+        Ok(())
+    }
+
+    /// Pasa los nombres de `books.publisher` a la tabla `publishers`, unificando los que solo
+    /// difieren en mayúsculas, acentos o espacios. Se ejecuta una sola vez, ya que al terminar
+    /// elimina la columna.
+    async fn migrate_publishers(&self) -> Result<(), BookError> {
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al migrar editoriales: {}", e).into())
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+
+        query("ALTER TABLE books ADD COLUMN publisher_id INTEGER REFERENCES publishers(id)")
+            .execute(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+        // La grafía más usada de cada grupo queda como nombre de la editorial
+        let values = query_as::<_, (String,)>(
+            "SELECT publisher FROM books GROUP BY publisher ORDER BY COUNT(*) DESC, publisher",
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(map_err)?;
+
+        let mut groups = BTreeMap::<String, Vec<String>>::new();
+        for (value,) in &values {
+            groups
+                .entry(fold_text(value))
+                .or_default()
+                .push(value.clone());
+        }
+
+        for (normalized_name, spellings) in &groups {
+            let (publisher_id,) = query_as::<_, (i64,)>(
+                "INSERT INTO publishers (name, normalized_name) VALUES (?, ?) RETURNING id",
+            )
+            .bind(&spellings[0])
+            .bind(normalized_name)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+            for spelling in spellings {
+                query("UPDATE books SET publisher_id = ? WHERE publisher = ?")
+                    .bind(publisher_id)
+                    .bind(spelling)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(map_err)?;
+            }
+        }
+
+        query("ALTER TABLE books DROP COLUMN publisher")
+            .execute(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+        tx.commit().await.map_err(map_err)?;
+
+        info!(
+            "{}",
+            tr!(
+                "log.publishers_migrated",
+                publishers = groups.len(),
+                values = values.len()
+            )
+        );
+
         Ok(())
     }
 
-    async fn get_or_create_author(&self, author: &BookAuthor) -> Result<i64, BookError> {
+    /// Reconstruye `book_authors` con papel y posición; los autores existentes quedan como
+    /// `author`, ordenados por nombre como se mostraban hasta ahora
+    async fn migrate_book_authors(&self) -> Result<(), BookError> {
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(
+                format!("Error al migrar relaciones libro-autor: {}", e).into(),
+            )
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+
+        query("ALTER TABLE book_authors RENAME TO legacy_book_authors")
+            .execute(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+        query(CREATE_BOOK_AUTHORS)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+        query(
+            r#"
+            INSERT INTO book_authors (book_id, author_id, role, position)
+            SELECT ba.book_id, ba.author_id, 'author',
+                   ROW_NUMBER() OVER (PARTITION BY ba.book_id ORDER BY a.name) - 1
+            FROM legacy_book_authors ba
+            JOIN authors a ON ba.author_id = a.id
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_err)?;
+
+        query("DROP TABLE legacy_book_authors")
+            .execute(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+        tx.commit().await.map_err(map_err)
+    }
+
+    /// Busca la editorial sin distinguir mayúsculas ni acentos, o la crea con el nombre dado
+    async fn get_or_create_publisher(
+        conn: &mut SqliteConnection,
+        publisher: &BookPublisher,
+    ) -> Result<i64, BookError> {
+        let normalized_name = fold_text(publisher.as_str());
+        let result = query_as::<_, (i64,)>("SELECT id FROM publishers WHERE normalized_name = ?")
+            .bind(&normalized_name)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al buscar editorial: {}", e).into())
+            })?;
+
+        if let Some(row) = result {
+            return Ok(row.0);
+        }
+
+        let result = query_as::<_, (i64,)>(
+            "INSERT INTO publishers (name, normalized_name) VALUES (?, ?) RETURNING id",
+        )
+        .bind(publisher.as_str())
+        .bind(&normalized_name)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| BookError::DatabaseError(format!("Error al crear editorial: {}", e).into()))?;
+
+        Ok(result.0)
+    }
+
+    async fn get_or_create_author(
+        conn: &mut SqliteConnection,
+        author: &BookAuthor,
+    ) -> Result<i64, BookError> {
         // Intentamos obtener el autor
         let author_name = author.as_str();
         let result = query_as::<_, (i64,)>("SELECT id FROM authors WHERE name = ?")
             .bind(author_name)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *conn)
             .await
             .map_err(|e| {
                 BookError::DatabaseError(format!("Error al buscar autor: {}", e).into())
@@ -93,7 +597,7 @@ impl BookSqliteRepository {
         // Si no existe, lo creamos
         let result = query_as::<_, (i64,)>("INSERT INTO authors (name) VALUES (?) RETURNING id")
             .bind(author_name)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *conn)
             .await
             .map_err(|e| BookError::DatabaseError(format!("Error al crear autor: {}", e).into()))?;
 
@@ -101,14 +605,14 @@ impl BookSqliteRepository {
     }
 
     async fn save_book_authors(
-        &self,
+        conn: &mut SqliteConnection,
         book_id: Uuid,
-        authors: &[BookAuthor],
+        authors: &[BookContributor],
     ) -> Result<(), BookError> {
         // Primero eliminamos las relaciones existentes
         query("DELETE FROM book_authors WHERE book_id = ?")
             .bind(book_id.to_string())
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await
             .map_err(|e| {
                 BookError::DatabaseError(
@@ -117,32 +621,69 @@ impl BookSqliteRepository {
             })?;
 
         // Ahora creamos las nuevas relaciones
-        for author in authors {
-            let author_id = self.get_or_create_author(author).await?;
+        for (position, contributor) in authors.iter().enumerate() {
+            let author_id = Self::get_or_create_author(&mut *conn, &contributor.name).await?;
 
-            query("INSERT INTO book_authors (book_id, author_id) VALUES (?, ?)")
-                .bind(book_id.to_string())
-                .bind(author_id)
-                .execute(&self.pool)
-                .await
-                .map_err(|e| {
-                    BookError::DatabaseError(
-                        format!("Error al guardar relación libro-autor: {}", e).into(),
-                    )
-                })?;
+            query(
+                "INSERT INTO book_authors (book_id, author_id, role, position) VALUES (?, ?, ?, ?)",
+            )
+            .bind(book_id.to_string())
+            .bind(author_id)
+            .bind(contributor.role.to_string())
+            .bind(position as i64)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(
+                    format!("Error al guardar relación libro-autor: {}", e).into(),
+                )
+            })?;
         }
 
         Ok(())
     }
 
-    async fn load_book_authors(&self, book_id: &str) -> Result<Vec<BookAuthor>, BookError> {
-        let authors = query_as::<_, DbAuthorName>(
+    /// Elimina los autores, editoriales y etiquetas que ya no tienen libros, ni siquiera
+    /// eliminados. Los temas se conservan porque forman parte del vocabulario.
+    async fn delete_orphans(conn: &mut SqliteConnection) -> Result<(), BookError> {
+        query("DELETE FROM authors WHERE id NOT IN (SELECT author_id FROM book_authors)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(
+                    format!("Error al eliminar autores sin libros: {}", e).into(),
+                )
+            })?;
+
+        query("DELETE FROM publishers WHERE id NOT IN (SELECT publisher_id FROM books)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(
+                    format!("Error al eliminar editoriales sin libros: {}", e).into(),
+                )
+            })?;
+
+        query("DELETE FROM tags WHERE id NOT IN (SELECT tag_id FROM book_tags)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(
+                    format!("Error al eliminar etiquetas sin libros: {}", e).into(),
+                )
+            })?;
+
+        Ok(())
+    }
+
+    async fn load_book_authors(&self, book_id: &str) -> Result<Vec<BookContributor>, BookError> {
+        let authors = query_as::<_, DbContributor>(
             r#"
-            SELECT a.name
+            SELECT a.name, ba.role
             FROM authors a
             JOIN book_authors ba ON a.id = ba.author_id
             WHERE ba.book_id = ?
-            ORDER BY a.name
+            ORDER BY ba.position
             "#,
         )
         .bind(book_id)
@@ -160,90 +701,331 @@ impl BookSqliteRepository {
         Ok(result)
     }
 
-    async fn get_total_books(
+    /// Nombres de los temas o etiquetas del libro, ordenados alfabéticamente
+    async fn load_book_terms(
         &self,
-        criteria: &ReadMultipleBooksCriteria,
-    ) -> Result<u64, BookError> {
-        let result = match criteria {
-            ReadMultipleBooksCriteria::All => query_as::<_, (i64,)>("SELECT COUNT(*) FROM books")
-                .fetch_one(&self.pool)
-                .await
-                .map_err(|e| {
-                    BookError::DatabaseError(format!("Error al contar libros: {}", e).into())
-                })?,
-            ReadMultipleBooksCriteria::ByTitle(title) => {
-                query_as::<_, (i64,)>("SELECT COUNT(*) FROM books WHERE title LIKE ?")
-                    .bind(format!("%{}%", title))
-                    .fetch_one(&self.pool)
-                    .await
-                    .map_err(|e| {
-                        BookError::DatabaseError(
-                            format!("Error al contar libros por título: {}", e).into(),
-                        )
-                    })?
-            }
-            ReadMultipleBooksCriteria::ByAuthor(author) => query_as::<_, (i64,)>(
-                r#"
-                    SELECT COUNT(DISTINCT b.id)
-                    FROM books b
-                    JOIN book_authors ba ON b.id = ba.book_id
-                    JOIN authors a ON ba.author_id = a.id
-                    WHERE a.name LIKE ?
-                    "#,
+        book_id: &str,
+        terms: &Terms,
+    ) -> Result<Vec<ValidatedStr>, BookError> {
+        let Terms {
+            table,
+            relation,
+            column,
+        } = terms;
+
+        let names = query_as::<_, (String,)>(&format!(
+            r#"
+            SELECT t.name
+            FROM {table} t
+            JOIN {relation} r ON t.id = r.{column}
+            WHERE r.book_id = ?
+            ORDER BY t.normalized_name
+            "#
+        ))
+        .bind(book_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al cargar {table} del libro: {}", e).into())
+        })?;
+
+        names
+            .into_iter()
+            .map(|(name,)| {
+                ValidatedStr::try_from(name).map_err(|e| {
+                    BookError::DatabaseError(format!("Error al crear {table}: {:?}", e).into())
+                })
+            })
+            .collect()
+    }
+
+    /// Verifica que exista un libro no eliminado con el id indicado
+    async fn ensure_exists(&self, id: &Uuid) -> Result<(), BookError> {
+        let existing = self
+            .find(ReadBookCriteria::ById(*id), DeletedFilter::Exclude)
+            .await?;
+
+        if existing.is_none() {
+            return Err(BookError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Inserta el libro con su editorial, autores, temas y etiquetas
+    async fn insert_book(
+        conn: &mut SqliteConnection,
+        book: &Book,
+        subject_ids: &[i64],
+    ) -> Result<(), BookError> {
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al crear libro: {}", e).into())
+        };
+
+        let publisher_id = Self::get_or_create_publisher(&mut *conn, &book.publisher).await?;
+
+        // Crear el libro en la tabla de libros
+        query(
+            r#"
+            INSERT INTO books (
+                id, title, publisher_id, year, isbn, stored, subtitle, edition, language,
+                page_count, format, series, series_number, description, cover_url,
+                classification_scheme, classification, call_number, shelf_key
             )
-            .bind(format!("%{}%", author))
-            .fetch_one(&self.pool)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(book.id.to_string())
+        .bind(book.title.as_str())
+        .bind(publisher_id)
+        .bind(book.year)
+        .bind(book.isbn.canonical())
+        .bind(book.stored_quantity as i64)
+        .bind(book.subtitle.as_ref().map(BookTitle::as_str))
+        .bind(book.edition.as_ref().map(BookEdition::as_str))
+        .bind(book.language.as_ref().map(LanguageCode::as_str))
+        .bind(book.page_count)
+        .bind(book.format.map(|format| format.to_string()))
+        .bind(book.series.as_ref().map(BookSeries::as_str))
+        .bind(book.series_number)
+        .bind(book.description.as_deref())
+        .bind(book.cover_url.as_deref())
+        .bind(
+            book.classification
+                .as_ref()
+                .map(|classification| classification.scheme.to_string()),
+        )
+        .bind(
+            book.classification
+                .as_ref()
+                .map(|classification| classification.number.as_str()),
+        )
+        .bind(book.call_number.as_ref().map(CallNumber::as_str))
+        .bind(
+            book.call_number
+                .as_ref()
+                .map(|call_number| shelf_key(call_number.as_str())),
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(map_err)?;
+
+        // Guardar los autores, temas y etiquetas del libro
+        Self::save_book_relations(&mut *conn, book, subject_ids).await
+    }
+
+    /// Actualiza el libro y sus relaciones, y elimina los autores y editoriales huérfanos
+    async fn update_book(
+        conn: &mut SqliteConnection,
+        book: &Book,
+        subject_ids: &[i64],
+    ) -> Result<(), BookError> {
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al actualizar libro: {}", e).into())
+        };
+
+        let publisher_id = Self::get_or_create_publisher(&mut *conn, &book.publisher).await?;
+
+        // Actualizar el libro
+        query(
+            r#"
+            UPDATE books
+            SET title = ?, year = ?, publisher_id = ?, stored = ?, isbn = ?, subtitle = ?,
+                edition = ?, language = ?, page_count = ?, format = ?, series = ?,
+                series_number = ?, description = ?, cover_url = ?, classification_scheme = ?,
+                classification = ?, call_number = ?, shelf_key = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(book.title.as_str())
+        .bind(book.year)
+        .bind(publisher_id)
+        .bind(book.stored_quantity as i64)
+        .bind(book.isbn.as_str())
+        .bind(book.subtitle.as_ref().map(BookTitle::as_str))
+        .bind(book.edition.as_ref().map(BookEdition::as_str))
+        .bind(book.language.as_ref().map(LanguageCode::as_str))
+        .bind(book.page_count)
+        .bind(book.format.map(|format| format.to_string()))
+        .bind(book.series.as_ref().map(BookSeries::as_str))
+        .bind(book.series_number)
+        .bind(book.description.as_deref())
+        .bind(book.cover_url.as_deref())
+        .bind(
+            book.classification
+                .as_ref()
+                .map(|classification| classification.scheme.to_string()),
+        )
+        .bind(
+            book.classification
+                .as_ref()
+                .map(|classification| classification.number.as_str()),
+        )
+        .bind(book.call_number.as_ref().map(CallNumber::as_str))
+        .bind(
+            book.call_number
+                .as_ref()
+                .map(|call_number| shelf_key(call_number.as_str())),
+        )
+        .bind(book.id.to_string())
+        .execute(&mut *conn)
+        .await
+        .map_err(map_err)?;
+
+        // Actualizar los autores, temas y etiquetas del libro
+        Self::save_book_relations(&mut *conn, book, subject_ids).await?;
+        Self::delete_orphans(&mut *conn).await
+    }
+
+    /// Guarda una versión completa del libro y devuelve su número de revisión
+    async fn insert_revision(
+        conn: &mut SqliteConnection,
+        book: &Book,
+        actor: &Actor,
+        created_at: OffsetDateTime,
+    ) -> Result<u32, BookError> {
+        let snapshot = serde_json::to_string(book).map_err(|e| {
+            BookError::DatabaseError(format!("Error al serializar revisión: {}", e).into())
+        })?;
+
+        // El número de revisión se calcula en la misma sentencia para evitar duplicados
+        let (revision,) = query_as::<_, (i64,)>(
+            r#"
+            INSERT INTO book_revisions (book_id, revision, snapshot, actor, created_at)
+            SELECT ?, COALESCE(MAX(revision), 0) + 1, ?, ?, ?
+            FROM book_revisions
+            WHERE book_id = ?
+            RETURNING revision
+            "#,
+        )
+        .bind(book.id.to_string())
+        .bind(snapshot)
+        .bind(actor.as_str())
+        .bind(unix_millis(created_at))
+        .bind(book.id.to_string())
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al guardar revisión: {}", e).into())
+        })?;
+
+        Ok(revision as u32)
+    }
+
+    /// Ids de los temas del libro; todos deben existir previamente en el vocabulario
+    async fn resolve_subjects(&self, subjects: &[BookSubject]) -> Result<Vec<i64>, BookError> {
+        let mut ids = Vec::with_capacity(subjects.len());
+        for subject in subjects {
+            let result = query_as::<_, (i64,)>("SELECT id FROM subjects WHERE normalized_name = ?")
+                .bind(fold_text(subject.as_str()))
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| {
+                    BookError::DatabaseError(format!("Error al buscar tema: {}", e).into())
+                })?;
+
+            match result {
+                Some((id,)) => ids.push(id),
+                None => return Err(BookError::UnknownSubject(subject.to_string())),
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Busca la etiqueta sin distinguir mayúsculas ni acentos, o la crea con el nombre dado
+    async fn get_or_create_tag(
+        conn: &mut SqliteConnection,
+        tag: &BookTag,
+    ) -> Result<i64, BookError> {
+        let normalized_name = fold_text(tag.as_str());
+        let result = query_as::<_, (i64,)>("SELECT id FROM tags WHERE normalized_name = ?")
+            .bind(&normalized_name)
+            .fetch_optional(&mut *conn)
             .await
             .map_err(|e| {
-                BookError::DatabaseError(format!("Error al contar libros por autor: {}", e).into())
-            })?,
-        };
+                BookError::DatabaseError(format!("Error al buscar etiqueta: {}", e).into())
+            })?;
+
+        if let Some(row) = result {
+            return Ok(row.0);
+        }
 
-        Ok(result.0 as u64)
+        let result = query_as::<_, (i64,)>(
+            "INSERT INTO tags (name, normalized_name) VALUES (?, ?) RETURNING id",
+        )
+        .bind(tag.as_str())
+        .bind(&normalized_name)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| BookError::DatabaseError(format!("Error al crear etiqueta: {}", e).into()))?;
+
+        Ok(result.0)
     }
-}
 
-#[async_trait::async_trait]
-impl BookRepository for BookSqliteRepository {
-    async fn find(&self, criteria: ReadBookCriteria) -> Result<Option<Book>, BookError> {
-        let (param, query) = match criteria {
-            ReadBookCriteria::ById(uuid) => (
-                uuid.to_string(),
-                r#"
-                SELECT *
-                FROM books
-                WHERE id = ?
-                "#,
-            ),
-            ReadBookCriteria::ByIsbn(isbn) => (
-                isbn.to_string(),
-                r#"
-                SELECT *
-                FROM books
-                WHERE isbn = ?
-                "#,
-            ),
-        };
+    /// Reemplaza los temas o etiquetas del libro; los repetidos se guardan una sola vez
+    async fn save_book_terms(
+        conn: &mut SqliteConnection,
+        book_id: Uuid,
+        terms: &Terms,
+        ids: &[i64],
+    ) -> Result<(), BookError> {
+        let Terms {
+            relation, column, ..
+        } = terms;
 
-        let book = query_as::<_, DbBook>(query)
-            .bind(&param)
-            .fetch_optional(&self.pool)
+        query(&format!("DELETE FROM {relation} WHERE book_id = ?"))
+            .bind(book_id.to_string())
+            .execute(&mut *conn)
             .await
             .map_err(|e| {
-                BookError::DatabaseError(format!("Error al buscar libro: {}", e).into())
+                BookError::DatabaseError(
+                    format!("Error al eliminar relaciones de {relation}: {}", e).into(),
+                )
             })?;
 
-        if let Some(db_book) = book {
-            let authors = self.load_book_authors(&db_book.id).await?;
-            db_book.to_domain_with_authors(authors).await.map(Some)
-        } else {
-            Ok(None)
+        for id in ids {
+            query(&format!(
+                "INSERT OR IGNORE INTO {relation} (book_id, {column}) VALUES (?, ?)"
+            ))
+            .bind(book_id.to_string())
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(
+                    format!("Error al guardar relación de {relation}: {}", e).into(),
+                )
+            })?;
         }
+
+        Ok(())
     }
 
-    async fn find_multiple(
+    /// Guarda los autores, temas y etiquetas del libro. Los temas ya deben estar resueltos
+    /// con [`Self::resolve_subjects`], para no dejar el libro a medio guardar.
+    async fn save_book_relations(
+        conn: &mut SqliteConnection,
+        book: &Book,
+        subject_ids: &[i64],
+    ) -> Result<(), BookError> {
+        Self::save_book_authors(&mut *conn, book.id, &book.authors).await?;
+        Self::save_book_terms(&mut *conn, book.id, &SUBJECTS, subject_ids).await?;
+
+        let mut tag_ids = Vec::with_capacity(book.tags.len());
+        for tag in &book.tags {
+            tag_ids.push(Self::get_or_create_tag(&mut *conn, tag).await?);
+        }
+
+        Self::save_book_terms(&mut *conn, book.id, &TAGS, &tag_ids).await
+    }
+
+    /// Página de libros que cumplen `condition`, generada por [`books_condition`], en el
+    /// orden de [`books_order`]
+    async fn find_page(
         &self,
-        criteria: ReadMultipleBooksCriteria,
+        (condition, params): &(String, Vec<Option<String>>),
+        order: &str,
         page: u32,
         page_size: u8,
     ) -> Result<PaginatedBooks, BookError> {
@@ -251,165 +1033,591 @@ impl BookRepository for BookSqliteRepository {
         let offset = (page - 1) as i64 * page_size as i64;
         let limit = page_size as i64;
 
-        let books = match &criteria {
-            ReadMultipleBooksCriteria::All => query_as::<_, DbBook>(
-                r#"
-                    SELECT *
-                    FROM books
-                    ORDER BY title
-                    LIMIT ? OFFSET ?
-                    "#,
-            )
+        let books_sql = format!(
+            r#"
+            {SELECT_BOOKS}
+            WHERE {condition}
+            ORDER BY {order}
+            LIMIT ? OFFSET ?
+            "#
+        );
+        let mut books_query = query_as::<_, DbBook>(&books_sql);
+        for param in params {
+            books_query = books_query.bind(param.clone());
+        }
+
+        let books = books_query
             .bind(limit)
             .bind(offset)
             .fetch_all(&self.pool)
             .await
             .map_err(|e| {
                 BookError::DatabaseError(format!("Error al buscar libros: {}", e).into())
-            })?,
-            ReadMultipleBooksCriteria::ByTitle(title) => query_as::<_, DbBook>(
-                r#"
-                    SELECT *
-                    FROM books
-                    WHERE title LIKE ?
-                    ORDER BY title
-                    LIMIT ? OFFSET ?
-                    "#,
+            })?;
+
+        let count_sql = format!("SELECT COUNT(*) FROM books b WHERE {condition}");
+        let mut count_query = query_as::<_, (i64,)>(&count_sql);
+        for param in params {
+            count_query = count_query.bind(param.clone());
+        }
+
+        let (total,) = count_query.fetch_one(&self.pool).await.map_err(|e| {
+            BookError::DatabaseError(format!("Error al contar libros: {}", e).into())
+        })?;
+
+        Ok(PaginatedBooks {
+            books: self.to_domain_books(books).await?,
+            total: total as u64,
+            page,
+            page_size,
+        })
+    }
+
+    /// Cuenta los libros por valor con una consulta que devuelve `value` y `count`
+    async fn count_facet(
+        &self,
+        sql: &str,
+        params: &[Option<String>],
+    ) -> Result<Vec<FacetCount>, BookError> {
+        let mut facet_query = query_as::<_, DbFacetCount>(sql);
+        for param in params {
+            facet_query = facet_query.bind(param.clone());
+        }
+
+        let counts = facet_query.fetch_all(&self.pool).await.map_err(|e| {
+            BookError::DatabaseError(format!("Error al contar facetas: {}", e).into())
+        })?;
+
+        Ok(counts.into_iter().map(DbFacetCount::into_domain).collect())
+    }
+
+    /// Facetas de todos los libros que cumplen `condition`
+    async fn facets(
+        &self,
+        (condition, params): &(String, Vec<Option<String>>),
+    ) -> Result<BookFacets, BookError> {
+        let subjects = self
+            .count_facet(
+                &format!(
+                    r#"
+                    SELECT s.name AS value, COUNT(*) AS count
+                    FROM book_subjects bs
+                    JOIN subjects s ON bs.subject_id = s.id
+                    JOIN books b ON bs.book_id = b.id
+                    WHERE {condition}
+                    GROUP BY s.id
+                    ORDER BY count DESC, s.normalized_name
+                    LIMIT {FACET_LIMIT}
+                    "#
+                ),
+                params,
             )
-            .bind(format!("%{}%", title))
-            .bind(limit)
-            .bind(offset)
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| {
-                BookError::DatabaseError(format!("Error al buscar libros por título: {}", e).into())
-            })?,
-            ReadMultipleBooksCriteria::ByAuthor(author) => query_as::<_, DbBook>(
-                r#"
-                    SELECT DISTINCT b.*
+            .await?;
+
+        let publishers = self
+            .count_facet(
+                &format!(
+                    r#"
+                    SELECT p.name AS value, COUNT(*) AS count
                     FROM books b
-                    JOIN book_authors ba ON b.id = ba.book_id
-                    JOIN authors a ON ba.author_id = a.id
-                    WHERE a.name LIKE ?
-                    ORDER BY b.title
-                    LIMIT ? OFFSET ?
-                    "#,
+                    JOIN publishers p ON b.publisher_id = p.id
+                    WHERE {condition}
+                    GROUP BY p.id
+                    ORDER BY count DESC, p.normalized_name
+                    LIMIT {FACET_LIMIT}
+                    "#
+                ),
+                params,
             )
-            .bind(format!("%{}%", author))
-            .bind(limit)
-            .bind(offset)
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| {
-                BookError::DatabaseError(format!("Error al buscar libros por autor: {}", e).into())
-            })?,
-        };
+            .await?;
+
+        let decades = self
+            .count_facet(
+                &format!(
+                    r#"
+                    SELECT CAST(b.year / 10 * 10 AS TEXT) AS value, COUNT(*) AS count
+                    FROM books b
+                    WHERE {condition}
+                    GROUP BY b.year / 10
+                    ORDER BY b.year / 10
+                    "#
+                ),
+                params,
+            )
+            .await?;
 
+        let languages = self
+            .count_facet(
+                &format!(
+                    r#"
+                    SELECT b.language AS value, COUNT(*) AS count
+                    FROM books b
+                    WHERE b.language IS NOT NULL AND {condition}
+                    GROUP BY b.language
+                    ORDER BY count DESC, b.language
+                    LIMIT {FACET_LIMIT}
+                    "#
+                ),
+                params,
+            )
+            .await?;
+
+        Ok(BookFacets {
+            subjects,
+            publishers,
+            decades,
+            languages,
+        })
+    }
+
+    /// Completa el libro con sus autores, temas y etiquetas
+    async fn load_book(&self, db_book: DbBook) -> Result<Book, BookError> {
+        let authors = self.load_book_authors(&db_book.id).await?;
+        let subjects = self.load_book_terms(&db_book.id, &SUBJECTS).await?;
+        let tags = self.load_book_terms(&db_book.id, &TAGS).await?;
+
+        db_book.to_domain(authors, subjects, tags)
+    }
+
+    pub(super) async fn to_domain_books(&self, books: Vec<DbBook>) -> Result<Vec<Book>, BookError> {
         let mut domain_books = Vec::with_capacity(books.len());
         for db_book in books {
-            let authors = self.load_book_authors(&db_book.id).await?;
-            let book = db_book.to_domain_with_authors(authors).await?;
-            domain_books.push(book);
+            domain_books.push(self.load_book(db_book).await?);
         }
 
-        let total = self.get_total_books(&criteria).await?;
+        Ok(domain_books)
+    }
+}
+
+/// Orden de los libros para el criterio: por título, salvo en la estantería, que se recorre
+/// desde la signatura indicada hacia cada lado
+fn books_order(criteria: &ReadMultipleBooksCriteria) -> &'static str {
+    match criteria {
+        ReadMultipleBooksCriteria::ByShelf(_, ShelfDirection::Before) => {
+            "b.shelf_key DESC, b.title DESC, b.id DESC"
+        }
+        ReadMultipleBooksCriteria::ByShelf(_, ShelfDirection::After) => {
+            "b.shelf_key, b.title, b.id"
+        }
+        _ => "b.title",
+    }
+}
 
-        Ok(PaginatedBooks {
-            books: domain_books,
-            total,
-            page,
-            page_size,
-        })
+/// Condición SQL sobre `books b` para el criterio y los filtros, junto con sus parámetros
+/// en orden. La década y la sucursal se incluyen como literales porque son enteros.
+fn books_condition(
+    criteria: &ReadMultipleBooksCriteria,
+    filters: &BookFilters,
+    deleted: DeletedFilter,
+) -> (String, Vec<Option<String>>) {
+    let mut conditions = vec![deleted_condition("b", deleted)];
+    let mut params = Vec::new();
+
+    match criteria {
+        ReadMultipleBooksCriteria::All => {}
+        ReadMultipleBooksCriteria::ByTitle(title) => {
+            conditions.push("b.title LIKE ? ESCAPE '\\'".to_string());
+            params.push(Some(like_pattern(title)));
+        }
+        ReadMultipleBooksCriteria::ByAuthor(author, role) => {
+            conditions.push(
+                r#"b.id IN (
+                    SELECT ba.book_id
+                    FROM book_authors ba
+                    JOIN authors a ON ba.author_id = a.id
+                    WHERE a.name LIKE ? ESCAPE '\' AND (? IS NULL OR ba.role = ?)
+                )"#
+                .to_string(),
+            );
+            let role = role.map(|role| role.to_string());
+            params.extend([Some(like_pattern(author)), role.clone(), role]);
+        }
+        ReadMultipleBooksCriteria::ByShelf(call_number, direction) => {
+            conditions.push(match direction {
+                ShelfDirection::Before => "b.shelf_key < ?".to_string(),
+                ShelfDirection::After => "b.shelf_key >= ?".to_string(),
+            });
+            params.push(Some(shelf_key(call_number.as_str())));
+        }
+        ReadMultipleBooksCriteria::ByLocation(location) => {
+            conditions.push(location_condition(*location));
+        }
+    }
+
+    for (value, terms) in [(&filters.subject, &SUBJECTS), (&filters.tag, &TAGS)] {
+        if let Some(value) = value {
+            let Terms {
+                table,
+                relation,
+                column,
+            } = terms;
+            conditions.push(format!(
+                "b.id IN (SELECT r.book_id FROM {relation} r JOIN {table} t ON r.{column} = t.id WHERE t.normalized_name = ?)"
+            ));
+            params.push(Some(fold_text(value)));
+        }
+    }
+
+    if let Some(publisher) = &filters.publisher {
+        conditions
+            .push("b.publisher_id IN (SELECT id FROM publishers WHERE normalized_name = ?)".into());
+        params.push(Some(fold_text(publisher)));
+    }
+
+    if let Some(language) = &filters.language {
+        conditions.push("b.language = ?".to_string());
+        params.push(Some(language.trim().to_lowercase()));
+    }
+
+    if let Some(decade) = filters.decade {
+        let decade = decade - decade % 10;
+        conditions.push(format!("b.year BETWEEN {decade} AND {}", decade + 9));
+    }
+
+    if let Some(location) = filters.location {
+        conditions.push(location_condition(location));
+    }
+
+    (conditions.join(" AND "), params)
+}
+
+/// Patrón de `LIKE ... ESCAPE '\'` que busca `value` como texto literal dentro del campo
+pub(super) fn like_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+/// Condición SQL sobre `books b` para los libros con ejemplares en la sucursal
+fn location_condition(location: i64) -> String {
+    format!("b.id IN (SELECT book_id FROM book_stock WHERE location_id = {location})")
+}
+
+/// Condición SQL sobre la columna `deleted_at` de `table` para el filtro indicado
+fn deleted_condition(table: &str, deleted: DeletedFilter) -> String {
+    match deleted {
+        DeletedFilter::Exclude => format!("{table}.deleted_at IS NULL"),
+        DeletedFilter::Include => "1 = 1".to_string(),
+        DeletedFilter::Only => format!("{table}.deleted_at IS NOT NULL"),
+    }
+}
+
+#[async_trait::async_trait]
+impl BookRepository for BookSqliteRepository {
+    async fn find(
+        &self,
+        criteria: ReadBookCriteria,
+        deleted: DeletedFilter,
+    ) -> Result<Option<Book>, BookError> {
+        let (param, column) = match criteria {
+            ReadBookCriteria::ById(uuid) => (uuid.to_string(), "id"),
+            ReadBookCriteria::ByIsbn(isbn) => (isbn.to_string(), "isbn"),
+        };
+
+        let query = format!(
+            "{SELECT_BOOKS} WHERE b.{column} = ? AND {}",
+            deleted_condition("b", deleted)
+        );
+
+        let book = query_as::<_, DbBook>(&query)
+            .bind(&param)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al buscar libro: {}", e).into())
+            })?;
+
+        if let Some(db_book) = book {
+            self.load_book(db_book).await.map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn find_multiple(
+        &self,
+        criteria: ReadMultipleBooksCriteria,
+        deleted: DeletedFilter,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedBooks, BookError> {
+        let condition = books_condition(&criteria, &BookFilters::default(), deleted);
+
+        self.find_page(&condition, books_order(&criteria), page, page_size)
+            .await
     }
 
     async fn create(&self, book: Book) -> Result<(), BookError> {
-        // Crear el libro en la tabla de libros
-        query(
-            r#"
-            INSERT INTO books (id, title, publisher, year, isbn, stored)
-            VALUES (?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(book.id.to_string())
-        .bind(book.title.as_str())
-        .bind(book.publisher.as_str())
-        .bind(book.year)
-        .bind(book.isbn.canonical())
-        .bind(book.stored_quantity as i64)
-        .execute(&self.pool)
-        .await
-        .map_err(|e| BookError::DatabaseError(format!("Error al crear libro: {}", e).into()))?;
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al crear libro: {}", e).into())
+        };
 
-        // Guardar los autores del libro
-        self.save_book_authors(book.id, &book.authors).await?;
+        let subject_ids = self.resolve_subjects(&book.subjects).await?;
 
-        Ok(())
+        // El libro, sus relaciones y la versión del catálogo se guardan juntos
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+        Self::insert_book(&mut tx, &book, &subject_ids).await?;
+        Self::touch_catalog(&mut tx).await?;
+
+        tx.commit().await.map_err(map_err)
+    }
+
+    async fn create_with_revision(
+        &self,
+        book: Book,
+        actor: &Actor,
+        created_at: OffsetDateTime,
+    ) -> Result<u32, BookError> {
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al crear libro: {}", e).into())
+        };
+
+        let subject_ids = self.resolve_subjects(&book.subjects).await?;
+
+        // Un libro nunca queda guardado sin su primera revisión
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+        Self::insert_book(&mut tx, &book, &subject_ids).await?;
+        let revision = Self::insert_revision(&mut tx, &book, actor, created_at).await?;
+        Self::touch_catalog(&mut tx).await?;
+
+        tx.commit().await.map_err(map_err)?;
+
+        Ok(revision)
     }
 
     async fn update(&self, book: Book) -> Result<(), BookError> {
+        self.ensure_exists(&book.id).await?;
+
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al actualizar libro: {}", e).into())
+        };
+
+        let subject_ids = self.resolve_subjects(&book.subjects).await?;
+
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+        Self::update_book(&mut tx, &book, &subject_ids).await?;
+        Self::touch_catalog(&mut tx).await?;
+
+        tx.commit().await.map_err(map_err)
+    }
+
+    async fn update_with_revision(
+        &self,
+        book: Book,
+        actor: &Actor,
+        created_at: OffsetDateTime,
+    ) -> Result<u32, BookError> {
+        self.ensure_exists(&book.id).await?;
+
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al actualizar libro: {}", e).into())
+        };
+
+        let subject_ids = self.resolve_subjects(&book.subjects).await?;
+
+        // El historial siempre coincide con el estado guardado del libro
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+        Self::update_book(&mut tx, &book, &subject_ids).await?;
+        let revision = Self::insert_revision(&mut tx, &book, actor, created_at).await?;
+        Self::touch_catalog(&mut tx).await?;
+
+        tx.commit().await.map_err(map_err)?;
+
+        Ok(revision)
+    }
+
+    async fn delete(&self, id: &Uuid, deleted_at: OffsetDateTime) -> Result<(), BookError> {
         // Verificar si existe el libro
-        let existing = self.find(ReadBookCriteria::ById(book.id)).await?;
+        let existing = self
+            .find(ReadBookCriteria::ById(*id), DeletedFilter::Exclude)
+            .await?;
 
         if existing.is_none() {
             return Err(BookError::NotFound);
         }
 
-        // Actualizar el libro
-        query(
-            r#"
-            UPDATE books
-            SET title = ?, year = ?, publisher = ?, stored = ?, isbn = ?
-            WHERE id = ?
-            "#,
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al eliminar libro: {}", e).into())
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+
+        // El libro y sus autores se conservan hasta que se purgue
+        query("UPDATE books SET deleted_at = ? WHERE id = ?")
+            .bind(unix_millis(deleted_at))
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+        Self::touch_catalog(&mut tx).await?;
+
+        tx.commit().await.map_err(map_err)
+    }
+
+    async fn restore(&self, id: &Uuid) -> Result<(), BookError> {
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al restaurar libro: {}", e).into())
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+
+        let result =
+            query("UPDATE books SET deleted_at = NULL WHERE id = ? AND deleted_at IS NOT NULL")
+                .bind(id.to_string())
+                .execute(&mut *tx)
+                .await
+                .map_err(map_err)?;
+
+        if result.rows_affected() == 0 {
+            return Err(BookError::NotFound);
+        }
+
+        Self::touch_catalog(&mut tx).await?;
+
+        tx.commit().await.map_err(map_err)
+    }
+
+    async fn purge(&self, deleted_before: OffsetDateTime) -> Result<Vec<Book>, BookError> {
+        let deleted_before = unix_millis(deleted_before);
+
+        let books = query_as::<_, DbBook>(&format!(
+            "{SELECT_BOOKS} WHERE b.deleted_at IS NOT NULL AND b.deleted_at < ? ORDER BY b.deleted_at"
+        ))
+        .bind(deleted_before)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al buscar libros por purgar: {}", e).into())
+        })?;
+
+        if books.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let purged = self.to_domain_books(books).await?;
+
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al purgar libros: {}", e).into())
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+
+        // SQLite elimina automáticamente las filas relacionadas en book_authors
+        // debido a la restricción ON DELETE CASCADE
+        query("DELETE FROM books WHERE deleted_at IS NOT NULL AND deleted_at < ?")
+            .bind(deleted_before)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+        Self::delete_orphans(&mut tx).await?;
+        Self::touch_catalog(&mut tx).await?;
+
+        tx.commit().await.map_err(map_err)?;
+
+        Ok(purged)
+    }
+
+    async fn revisions(&self, id: &Uuid) -> Result<Vec<BookRevision>, BookError> {
+        let revisions = query_as::<_, DbBookRevision>(
+            "SELECT * FROM book_revisions WHERE book_id = ? ORDER BY revision",
         )
-        .bind(book.title.as_str())
-        .bind(book.year)
-        .bind(book.publisher.as_str())
-        .bind(book.stored_quantity as i64)
-        .bind(book.isbn.as_str())
-        .bind(book.id.to_string())
-        .execute(&self.pool)
+        .bind(id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al buscar revisiones: {}", e).into())
+        })?;
+
+        revisions
+            .into_iter()
+            .map(DbBookRevision::into_domain)
+            .collect()
+    }
+
+    async fn find_revision(
+        &self,
+        id: &Uuid,
+        revision: u32,
+    ) -> Result<Option<BookRevision>, BookError> {
+        let revision = query_as::<_, DbBookRevision>(
+            "SELECT * FROM book_revisions WHERE book_id = ? AND revision = ?",
+        )
+        .bind(id.to_string())
+        .bind(revision as i64)
+        .fetch_optional(&self.pool)
         .await
-        .map_err(|e| {
-            BookError::DatabaseError(format!("Error al actualizar libro: {}", e).into())
-        })?;
+        .map_err(|e| BookError::DatabaseError(format!("Error al buscar revisión: {}", e).into()))?;
 
-        // Actualizar los autores del libro
-        self.save_book_authors(book.id, &book.authors).await?;
+        revision.map(DbBookRevision::into_domain).transpose()
+    }
 
-        Ok(())
+    async fn search(
+        &self,
+        criteria: ReadMultipleBooksCriteria,
+        filters: BookFilters,
+        page: u32,
+        page_size: u8,
+    ) -> Result<BookSearchResults, BookError> {
+        let condition = books_condition(&criteria, &filters, DeletedFilter::Exclude);
+
+        Ok(BookSearchResults {
+            results: self
+                .find_page(&condition, books_order(&criteria), page, page_size)
+                .await?,
+            facets: self.facets(&condition).await?,
+        })
     }
 
-    async fn delete(&self, id: &Uuid) -> Result<(), BookError> {
-        // Verificar si existe el libro
-        let existing = self.find(ReadBookCriteria::ById(*id)).await?;
+    async fn catalog_version(&self) -> Result<CatalogVersion, BookError> {
+        let (revision, modified_at) = query_as::<_, (i64, i64)>(
+            "SELECT revision, modified_at FROM catalog_state WHERE id = 1",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al obtener versión del catálogo: {}", e).into())
+        })?;
 
-        if existing.is_none() {
-            return Err(BookError::NotFound);
-        }
+        Ok(CatalogVersion {
+            revision: revision as u64,
+            modified_at: UNIX_EPOCH + Duration::from_millis(modified_at as u64),
+        })
+    }
+}
 
-        // SQLite elimina automáticamente las filas relacionadas en book_authors
-        // debido a la restricción ON DELETE CASCADE
-        query("DELETE FROM books WHERE id = ?")
-            .bind(id.to_string())
-            .execute(&self.pool)
-            .await
-            .map_err(|e| {
-                BookError::DatabaseError(format!("Error al eliminar libro: {}", e).into())
-            })?;
+#[inline]
+pub(super) fn unix_millis(timestamp: OffsetDateTime) -> i64 {
+    (timestamp.unix_timestamp_nanos() / 1_000_000) as i64
+}
 
-        Ok(())
-    }
+#[inline]
+fn unix_millis_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
-    use crate::books::books_domain::{BookAuthor, BookPublisher, BookTitle, Isbn};
-    use sqlx::SqlitePool;
-
-    async fn setup_test_db() -> BookSqliteRepository {
+    use crate::books::{
+        books_domain::{
+            BookAuthor, BookFormat, BookPublisher, BookTitle, Classification, ClassificationScheme,
+            ContributorRole, Isbn, PublisherRepository, TermRepository,
+        },
+        books_infrastructure::{
+            AuthorSqliteRepository, PublisherSqliteRepository, TermSqliteRepository,
+            authors_sqlite_repository::tests::find_author_by_name,
+        },
+    };
+
+    pub(crate) async fn setup_test_db() -> BookSqliteRepository {
         let pool = SqlitePool::connect("sqlite::memory:")
             .await
             .expect("Failed to create in-memory SQLite database");
@@ -422,7 +1630,7 @@ mod tests {
         repo
     }
 
-    fn create_test_book() -> Book {
+    pub(crate) fn create_test_book() -> Book {
         let id = Uuid::new_v4();
         let title = BookTitle::try_from("Test Book".to_string()).unwrap();
         let author1 = BookAuthor::try_from("Author One".to_string()).unwrap();
@@ -433,11 +1641,25 @@ mod tests {
         Book {
             id,
             title,
-            authors: vec![author1, author2],
+            authors: vec![author1.into(), author2.into()],
             year: 2023,
             publisher,
+            subjects: Vec::new(),
+            tags: Vec::new(),
             stored_quantity: 5,
             isbn,
+            subtitle: None,
+            edition: None,
+            language: None,
+            page_count: None,
+            format: None,
+            series: None,
+            series_number: None,
+            description: None,
+            cover_url: None,
+            classification: None,
+            call_number: None,
+            deleted_at: None,
         }
     }
 
@@ -451,7 +1673,7 @@ mod tests {
         // Act
         repo.create(book).await.expect("Failed to create book");
         let result = repo
-            .find(ReadBookCriteria::ById(book_id))
+            .find(ReadBookCriteria::ById(book_id), DeletedFilter::Exclude)
             .await
             .expect("Failed to find book");
 
@@ -475,7 +1697,7 @@ mod tests {
         repo.create(book).await.expect("Failed to create book");
 
         let found_book = repo
-            .find(ReadBookCriteria::ById(book_id))
+            .find(ReadBookCriteria::ById(book_id), DeletedFilter::Exclude)
             .await
             .expect("Failed to find book")
             .unwrap();
@@ -486,11 +1708,12 @@ mod tests {
         let updated_book = Book {
             id: found_book.id,
             title: new_title,
-            authors: vec![new_author],
+            authors: vec![new_author.into()],
             year: 2024,
             publisher: found_book.publisher.clone(),
             stored_quantity: 10,
             isbn: found_book.isbn.clone(),
+            ..found_book
         };
 
         repo.update(updated_book)
@@ -499,7 +1722,7 @@ mod tests {
 
         // Assert
         let result = repo
-            .find(ReadBookCriteria::ById(book_id))
+            .find(ReadBookCriteria::ById(book_id), DeletedFilter::Exclude)
             .await
             .expect("Failed to find updated book");
         assert!(result.is_some(), "Updated book not found");
@@ -508,11 +1731,62 @@ mod tests {
         assert_eq!(found_book.id, book_id);
         assert_eq!(found_book.title.as_str(), "Updated Title");
         assert_eq!(found_book.authors.len(), 1);
-        assert_eq!(found_book.authors[0].as_str(), "New Author");
+        assert_eq!(found_book.authors[0].name.as_str(), "New Author");
         assert_eq!(found_book.year, 2024);
         assert_eq!(found_book.stored_quantity, 10);
     }
 
+    #[tokio::test]
+    async fn test_bibliographic_details_are_saved() {
+        // Arrange
+        let repo = setup_test_db().await;
+        let isbn = Isbn::try_from("9783161484100".to_string()).unwrap();
+        let book = Book {
+            isbn: isbn.clone(),
+            subtitle: BookTitle::try_from("A Subtitle".to_string()).ok(),
+            edition: BookEdition::try_from("2nd edition".to_string()).ok(),
+            language: LanguageCode::try_from("en".to_string()).ok(),
+            page_count: Some(320),
+            format: Some(BookFormat::Paperback),
+            series: BookSeries::try_from("Test Series".to_string()).ok(),
+            series_number: Some(3),
+            description: Some("Ünicode & symbols © allowed".to_string()),
+            cover_url: Some("https://example.com/cover.jpg".to_string()),
+            ..create_test_book()
+        };
+
+        // Act
+        repo.create(book.clone())
+            .await
+            .expect("Failed to create book");
+        let found = repo
+            .find(ReadBookCriteria::ById(book.id), DeletedFilter::Exclude)
+            .await
+            .expect("Failed to find book")
+            .expect("Book should exist");
+
+        // Assert
+        assert_eq!(found, book);
+
+        // Act - Details can be cleared on update
+        let cleared = Book {
+            id: book.id,
+            isbn,
+            ..create_test_book()
+        };
+        repo.update(cleared.clone())
+            .await
+            .expect("Failed to update book");
+        let found = repo
+            .find(ReadBookCriteria::ById(book.id), DeletedFilter::Exclude)
+            .await
+            .expect("Failed to find book")
+            .expect("Book should exist");
+
+        // Assert
+        assert_eq!(found, cleared);
+    }
+
     #[tokio::test]
     async fn test_delete_book() {
         // Arrange
@@ -522,16 +1796,566 @@ mod tests {
 
         // Act - Create and then delete
         repo.create(book).await.expect("Failed to create book");
-        repo.delete(&book_id).await.expect("Failed to delete book");
+        repo.delete(&book_id, OffsetDateTime::now_utc())
+            .await
+            .expect("Failed to delete book");
 
         // Assert
         let result = repo
-            .find(ReadBookCriteria::ById(book_id))
+            .find(ReadBookCriteria::ById(book_id), DeletedFilter::Exclude)
             .await
             .expect("Failed to query book");
         assert!(result.is_none(), "Book should have been deleted");
     }
 
+    #[tokio::test]
+    async fn test_init_tables_migrates_existing_books() {
+        // Arrange - Table created before soft deletes existed
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .expect("Failed to create in-memory SQLite database");
+        query(
+            "CREATE TABLE books (id TEXT PRIMARY KEY, title TEXT NOT NULL, publisher TEXT NOT NULL, \
+             year INTEGER NOT NULL, isbn TEXT NOT NULL, stored INTEGER NOT NULL, UNIQUE(isbn))",
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create legacy table");
+        for (isbn, publisher) in [
+            ("9780306406157", "Planeta"),
+            ("9788437604954", "Planeta"),
+            ("9781402894626", "PLANÉTA "),
+            ("9780143039433", "Alfaguara"),
+        ] {
+            query(
+                "INSERT INTO books (id, title, publisher, year, isbn, stored) \
+                 VALUES (?, 'Legacy', ?, 1990, ?, 1)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(publisher)
+            .bind(isbn)
+            .execute(&pool)
+            .await
+            .expect("Failed to insert legacy book");
+        }
+        let repo = BookSqliteRepository { pool };
+        let publisher_repo = PublisherSqliteRepository::new(repo.pool.clone());
+
+        // Act - Initializing twice must not fail
+        repo.init_tables().await.expect("Failed to migrate tables");
+        repo.init_tables().await.expect("Failed to migrate tables");
+
+        // Assert - Publishers are deduplicated keeping the most used spelling
+        let publishers = publisher_repo
+            .find_publishers(None, 1, 10)
+            .await
+            .expect("Failed to list publishers");
+        let names = publishers
+            .publishers
+            .iter()
+            .map(|publisher| (publisher.name.as_str(), publisher.book_count))
+            .collect::<Vec<_>>();
+        assert_eq!(names, [("Alfaguara", 1), ("Planeta", 3)]);
+
+        let legacy = repo
+            .find(
+                ReadBookCriteria::ByIsbn("9781402894626".to_string()),
+                DeletedFilter::Exclude,
+            )
+            .await
+            .expect("Failed to find book")
+            .expect("Legacy book should exist");
+        assert_eq!(legacy.publisher.as_str(), "Planeta");
+
+        repo.create(create_test_book())
+            .await
+            .expect("Failed to create book");
+        let listed = repo
+            .find_multiple(
+                ReadMultipleBooksCriteria::All,
+                DeletedFilter::Exclude,
+                1,
+                10,
+            )
+            .await
+            .expect("Failed to find books");
+        assert_eq!(listed.total, 5);
+    }
+
+    #[tokio::test]
+    async fn test_init_tables_migrates_book_authors() {
+        // Arrange - Relations saved before roles and positions existed
+        let repo = setup_test_db().await;
+        let book = create_test_book();
+        repo.create(book.clone())
+            .await
+            .expect("Failed to create book");
+        query("DROP TABLE book_authors")
+            .execute(&repo.pool)
+            .await
+            .expect("Failed to drop table");
+        query(
+            "CREATE TABLE book_authors (book_id TEXT NOT NULL, author_id INTEGER NOT NULL, \
+             PRIMARY KEY (book_id, author_id))",
+        )
+        .execute(&repo.pool)
+        .await
+        .expect("Failed to create legacy table");
+        query("INSERT INTO book_authors (book_id, author_id) SELECT ?, id FROM authors")
+            .bind(book.id.to_string())
+            .execute(&repo.pool)
+            .await
+            .expect("Failed to insert legacy relations");
+
+        // Act
+        repo.init_tables().await.expect("Failed to migrate tables");
+        repo.init_tables().await.expect("Failed to migrate tables");
+
+        // Assert - Existing relations become authors ordered by name
+        let found = repo
+            .find(ReadBookCriteria::ById(book.id), DeletedFilter::Exclude)
+            .await
+            .expect("Failed to find book")
+            .expect("Book should exist");
+        assert_eq!(found.authors, book.authors);
+    }
+
+    #[tokio::test]
+    async fn test_contributors_keep_order_and_roles() {
+        // Arrange - The same person can appear with several roles
+        let repo = setup_test_db().await;
+        let author_repo = AuthorSqliteRepository::new(repo.pool.clone());
+        let contributor = |name: &str, role| {
+            BookContributor::new(BookAuthor::try_from(name.to_string()).unwrap(), role)
+        };
+        let book = Book {
+            authors: vec![
+                contributor("Zoe Writer", ContributorRole::Author),
+                contributor("Ann Translator", ContributorRole::Translator),
+                contributor("Zoe Writer", ContributorRole::Illustrator),
+                contributor("Bob Editor", ContributorRole::Editor),
+            ],
+            ..create_test_book()
+        };
+
+        // Act
+        repo.create(book.clone())
+            .await
+            .expect("Failed to create book");
+
+        // Assert - Contributors come back in the cataloged order
+        let found = repo
+            .find(ReadBookCriteria::ById(book.id), DeletedFilter::Exclude)
+            .await
+            .expect("Failed to find book")
+            .expect("Book should exist");
+        assert_eq!(found.authors, book.authors);
+
+        let writer = find_author_by_name(&author_repo, "Zoe Writer")
+            .await
+            .expect("Author should exist");
+        assert_eq!(writer.book_count, 1);
+
+        // Assert - Search can be narrowed by role
+        for (name, role, expected) in [
+            ("Ann", None, 1),
+            ("Ann", Some(ContributorRole::Translator), 1),
+            ("Ann", Some(ContributorRole::Author), 0),
+            ("", Some(ContributorRole::Illustrator), 1),
+        ] {
+            let result = repo
+                .find_multiple(
+                    ReadMultipleBooksCriteria::ByAuthor(name.to_string(), role),
+                    DeletedFilter::Exclude,
+                    1,
+                    10,
+                )
+                .await
+                .expect("Failed to find books by contributor");
+            assert_eq!(result.total, expected, "{name} {role:?}");
+            assert_eq!(result.books.len() as u64, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_filters_and_counts_facets() {
+        // Arrange
+        let repo = setup_test_db().await;
+        let term_repo = TermSqliteRepository::new(repo.pool.clone());
+        for name in ["Novela", "Historia"] {
+            term_repo
+                .create_subject(&BookSubject::try_from(name.to_string()).unwrap())
+                .await
+                .expect("Failed to create subject");
+        }
+        let books = [
+            (
+                "978-3-16-148410-0",
+                1967,
+                "es",
+                vec!["Novela"],
+                "Sudamericana",
+            ),
+            (
+                "978-0-306-40615-7",
+                1962,
+                "es",
+                vec!["Novela"],
+                "Sudamericana",
+            ),
+            (
+                "978-84-376-0495-4",
+                1985,
+                "en",
+                vec!["Historia", "Novela"],
+                "Ed. Test",
+            ),
+        ];
+        for (isbn, year, language, subjects, publisher) in books {
+            let book = Book {
+                id: Uuid::new_v4(),
+                isbn: Isbn::try_from(isbn.to_string()).unwrap(),
+                year,
+                language: Some(LanguageCode::try_from(language.to_string()).unwrap()),
+                subjects: subjects
+                    .into_iter()
+                    .map(|subject| BookSubject::try_from(subject.to_string()).unwrap())
+                    .collect(),
+                publisher: BookPublisher::try_from(publisher.to_string()).unwrap(),
+                ..create_test_book()
+            };
+            repo.create(book).await.expect("Failed to create book");
+        }
+
+        // Act
+        let all = repo
+            .search(ReadMultipleBooksCriteria::All, BookFilters::default(), 1, 1)
+            .await
+            .expect("Failed to search books");
+
+        // Assert - Facets count every result, not only the page
+        assert_eq!(all.results.books.len(), 1);
+        assert_eq!(all.results.total, 3);
+        let facet = |value: &str, count| FacetCount {
+            value: value.to_string(),
+            count,
+        };
+        assert_eq!(
+            all.facets.subjects,
+            vec![facet("Novela", 3), facet("Historia", 1)]
+        );
+        assert_eq!(
+            all.facets.publishers,
+            vec![facet("Sudamericana", 2), facet("Ed. Test", 1)]
+        );
+        assert_eq!(all.facets.decades, vec![facet("1960", 2), facet("1980", 1)]);
+        assert_eq!(all.facets.languages, vec![facet("es", 2), facet("en", 1)]);
+
+        // Act - Filters are combined
+        let filtered = repo
+            .search(
+                ReadMultipleBooksCriteria::ByTitle("Test".to_string()),
+                BookFilters {
+                    subject: Some("novela".to_string()),
+                    publisher: Some("sudamericana".to_string()),
+                    decade: Some(1965),
+                    ..Default::default()
+                },
+                1,
+                10,
+            )
+            .await
+            .expect("Failed to search books");
+
+        // Assert
+        assert_eq!(filtered.results.total, 2);
+        assert_eq!(filtered.facets.decades, vec![facet("1960", 2)]);
+        assert_eq!(filtered.facets.subjects, vec![facet("Novela", 2)]);
+
+        let none = repo
+            .search(
+                ReadMultipleBooksCriteria::All,
+                BookFilters {
+                    language: Some("EN".to_string()),
+                    tag: Some("inexistente".to_string()),
+                    ..Default::default()
+                },
+                1,
+                10,
+            )
+            .await
+            .expect("Failed to search books");
+        assert_eq!(none.results.total, 0);
+        assert_eq!(none.facets, BookFacets::default());
+    }
+
+    #[tokio::test]
+    async fn test_find_multiple_by_shelf() {
+        // Arrange
+        let repo = setup_test_db().await;
+        let shelved = [
+            ("978-3-16-148410-0", Some("863.64 G216c")),
+            ("978-0-306-40615-7", Some("82 B12")),
+            ("978-84-376-0495-4", Some("863.7 C3")),
+            ("978-1-4028-9462-6", None),
+        ];
+        for (isbn, call_number) in shelved {
+            let book = Book {
+                id: Uuid::new_v4(),
+                isbn: Isbn::try_from(isbn.to_string()).unwrap(),
+                classification: Some(
+                    Classification::new(ClassificationScheme::Dewey, "863.64").unwrap(),
+                ),
+                call_number: call_number
+                    .map(|call_number| CallNumber::try_from(call_number.to_string()).unwrap()),
+                ..create_test_book()
+            };
+            repo.create(book).await.expect("Failed to create book");
+        }
+        let call_number = CallNumber::try_from("863.64 G216c".to_string()).unwrap();
+        let call_numbers = |books: PaginatedBooks| {
+            books
+                .books
+                .into_iter()
+                .map(|book| book.call_number.unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // Act
+        let before = repo
+            .find_multiple(
+                ReadMultipleBooksCriteria::ByShelf(call_number.clone(), ShelfDirection::Before),
+                DeletedFilter::Exclude,
+                1,
+                10,
+            )
+            .await
+            .expect("Failed to browse shelf");
+        let after = repo
+            .find_multiple(
+                ReadMultipleBooksCriteria::ByShelf(call_number, ShelfDirection::After),
+                DeletedFilter::Exclude,
+                1,
+                10,
+            )
+            .await
+            .expect("Failed to browse shelf");
+
+        // Assert - Dewey numbers sort numerically and books without call number are skipped
+        assert_eq!(before.total, 1);
+        assert_eq!(
+            before.books[0].classification.as_ref().unwrap().number,
+            "863.64"
+        );
+        assert_eq!(call_numbers(before), ["82 B12"]);
+        assert_eq!(call_numbers(after), ["863.64 G216c", "863.7 C3"]);
+    }
+
+    #[tokio::test]
+    async fn test_soft_delete_restore_and_purge() {
+        // Arrange
+        let repo = setup_test_db().await;
+        let book = create_test_book();
+        let book_id = book.id;
+        let now = OffsetDateTime::now_utc();
+        repo.create(book).await.expect("Failed to create book");
+
+        // Act - Delete
+        repo.delete(&book_id, now - time::Duration::days(40))
+            .await
+            .expect("Failed to delete book");
+
+        // Assert - Hidden by default, visible when requested
+        let hidden = repo
+            .find(ReadBookCriteria::ById(book_id), DeletedFilter::Exclude)
+            .await
+            .expect("Failed to query book");
+        assert!(hidden.is_none());
+
+        let deleted = repo
+            .find(ReadBookCriteria::ById(book_id), DeletedFilter::Include)
+            .await
+            .expect("Failed to query book")
+            .expect("Deleted book should still be stored");
+        assert!(deleted.deleted_at.is_some());
+        assert_eq!(deleted.authors.len(), 2);
+
+        let listed = repo
+            .find_multiple(
+                ReadMultipleBooksCriteria::All,
+                DeletedFilter::Exclude,
+                1,
+                10,
+            )
+            .await
+            .expect("Failed to find books");
+        assert_eq!(listed.total, 0);
+        let listed = repo
+            .find_multiple(ReadMultipleBooksCriteria::All, DeletedFilter::Only, 1, 10)
+            .await
+            .expect("Failed to find books");
+        assert_eq!(listed.total, 1);
+
+        // Act - Restore
+        repo.restore(&book_id)
+            .await
+            .expect("Failed to restore book");
+        let restored = repo
+            .find(ReadBookCriteria::ById(book_id), DeletedFilter::Exclude)
+            .await
+            .expect("Failed to query book")
+            .expect("Restored book should be visible");
+        assert!(restored.deleted_at.is_none());
+        assert!(matches!(
+            repo.restore(&book_id).await,
+            Err(BookError::NotFound)
+        ));
+
+        // Act - Purge only books deleted before the retention period
+        repo.delete(&book_id, now - time::Duration::days(10))
+            .await
+            .expect("Failed to delete book");
+        let purged = repo
+            .purge(now - time::Duration::days(30))
+            .await
+            .expect("Failed to purge books");
+        assert!(purged.is_empty());
+
+        let purged = repo
+            .purge(now - time::Duration::days(5))
+            .await
+            .expect("Failed to purge books");
+        assert_eq!(purged.len(), 1);
+        assert_eq!(purged[0].id, book_id);
+
+        let gone = repo
+            .find(ReadBookCriteria::ById(book_id), DeletedFilter::Include)
+            .await
+            .expect("Failed to query book");
+        assert!(gone.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_revisions_are_numbered_per_book() {
+        // Arrange
+        let repo = setup_test_db().await;
+        let book = create_test_book();
+        let book_id = book.id;
+        let actor = Actor::cli();
+        let now = OffsetDateTime::now_utc();
+
+        // Act
+        let first = repo
+            .create_with_revision(book.clone(), &actor, now)
+            .await
+            .expect("Failed to create book");
+        let updated = Book {
+            stored_quantity: 9,
+            ..book.clone()
+        };
+        let second = repo
+            .update_with_revision(updated, &actor, now)
+            .await
+            .expect("Failed to update book");
+
+        // Assert
+        assert_eq!((first, second), (1, 2));
+        let revisions = repo
+            .revisions(&book_id)
+            .await
+            .expect("Failed to list revisions");
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].book, book);
+        assert_eq!(revisions[1].book.stored_quantity, 9);
+        assert_eq!(revisions[1].actor, actor);
+
+        let found = repo
+            .find_revision(&book_id, 2)
+            .await
+            .expect("Failed to find revision");
+        assert_eq!(found.map(|revision| revision.revision), Some(2));
+        let missing = repo
+            .find_revision(&book_id, 3)
+            .await
+            .expect("Failed to find revision");
+        assert!(missing.is_none());
+
+        // Las revisiones se borran junto con el libro purgado
+        repo.delete(&book_id, now - time::Duration::days(1))
+            .await
+            .expect("Failed to delete book");
+        repo.purge(now).await.expect("Failed to purge books");
+        let revisions = repo
+            .revisions(&book_id)
+            .await
+            .expect("Failed to list revisions");
+        assert!(revisions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_catalog_version_changes_on_mutation() {
+        // Arrange
+        let repo = setup_test_db().await;
+        let book = create_test_book();
+        let book_id = book.id;
+        let initial = repo
+            .catalog_version()
+            .await
+            .expect("Failed to get catalog version");
+
+        // Act
+        repo.create(book).await.expect("Failed to create book");
+        let after_create = repo
+            .catalog_version()
+            .await
+            .expect("Failed to get catalog version");
+
+        repo.delete(&book_id, OffsetDateTime::now_utc())
+            .await
+            .expect("Failed to delete book");
+        let after_delete = repo
+            .catalog_version()
+            .await
+            .expect("Failed to get catalog version");
+
+        // Assert
+        assert_eq!(initial.revision, 0);
+        assert_eq!(after_create.revision, 1);
+        assert_eq!(after_delete.revision, 2);
+        assert!(after_delete.modified_at >= initial.modified_at);
+    }
+
+    #[tokio::test]
+    async fn test_failed_create_leaves_catalog_untouched() {
+        // Arrange
+        let repo = setup_test_db().await;
+        let publisher_repo = PublisherSqliteRepository::new(repo.pool.clone());
+        repo.create(create_test_book())
+            .await
+            .expect("Failed to create book");
+        let duplicate = Book {
+            id: Uuid::new_v4(),
+            publisher: BookPublisher::try_from("Otra Editorial".to_string()).unwrap(),
+            ..create_test_book()
+        };
+
+        // Act
+        let result = repo.create(duplicate).await;
+
+        // Assert - the new publisher and the version bump are rolled back with the book
+        assert!(result.is_err());
+        let version = repo
+            .catalog_version()
+            .await
+            .expect("Failed to get catalog version");
+        assert_eq!(version.revision, 1);
+        let publishers = publisher_repo
+            .find_publishers(None, 1, 10)
+            .await
+            .expect("Failed to find publishers");
+        assert_eq!(publishers.total, 1);
+    }
+
     #[tokio::test]
     async fn test_find_multiple_all() {
         // Arrange
@@ -549,11 +2373,12 @@ mod tests {
             let book = Book {
                 id,
                 title,
-                authors: vec![author],
+                authors: vec![author.into()],
                 year: 2020 + i,
                 publisher,
                 stored_quantity: i,
                 isbn,
+                ..create_test_book()
             };
 
             repo.create(book).await.expect("Failed to create book");
@@ -561,7 +2386,12 @@ mod tests {
 
         // Act
         let result = repo
-            .find_multiple(ReadMultipleBooksCriteria::All, 1, 10)
+            .find_multiple(
+                ReadMultipleBooksCriteria::All,
+                DeletedFilter::Exclude,
+                1,
+                10,
+            )
             .await
             .expect("Failed to find books");
 
@@ -596,11 +2426,12 @@ mod tests {
             let book = Book {
                 id,
                 title: book_title,
-                authors: vec![author],
+                authors: vec![author.into()],
                 year: 2023,
                 publisher,
                 stored_quantity: 1,
                 isbn,
+                ..create_test_book()
             };
 
             repo.create(book).await.expect("Failed to create book");
@@ -610,6 +2441,7 @@ mod tests {
         let result = repo
             .find_multiple(
                 ReadMultipleBooksCriteria::ByTitle("Apple".to_string()),
+                DeletedFilter::Exclude,
                 1,
                 10,
             )
@@ -638,9 +2470,9 @@ mod tests {
         for (index, (title, authors)) in author_combinations.iter().enumerate() {
             let id = Uuid::new_v4();
             let book_title = BookTitle::try_from(title.to_string()).unwrap();
-            let book_authors: Vec<BookAuthor> = authors
+            let book_authors: Vec<BookContributor> = authors
                 .iter()
-                .map(|a| BookAuthor::try_from(a.to_string()).unwrap())
+                .map(|a| BookAuthor::try_from(a.to_string()).unwrap().into())
                 .collect();
             let publisher = BookPublisher::try_from("Publisher".to_string()).unwrap();
             let isbn = Isbn::try_from(format!("{}{}", base_isbn, index + 1)).unwrap();
@@ -652,6 +2484,7 @@ mod tests {
                 publisher,
                 stored_quantity: 1,
                 isbn,
+                ..create_test_book()
             };
 
             repo.create(book).await.expect("Failed to create book");
@@ -660,7 +2493,8 @@ mod tests {
         // Act
         let result = repo
             .find_multiple(
-                ReadMultipleBooksCriteria::ByAuthor("John".to_string()),
+                ReadMultipleBooksCriteria::ByAuthor("John".to_string(), None),
+                DeletedFilter::Exclude,
                 1,
                 10,
             )
@@ -687,11 +2521,12 @@ mod tests {
             let book = Book {
                 id,
                 title,
-                authors: vec![author],
+                authors: vec![author.into()],
                 year: 2023,
                 publisher,
                 stored_quantity: 1,
                 isbn,
+                ..create_test_book()
             };
 
             repo.create(book).await.expect("Failed to create book");
@@ -699,13 +2534,13 @@ mod tests {
 
         // Act - Get first page with 3 items
         let page1 = repo
-            .find_multiple(ReadMultipleBooksCriteria::All, 1, 3)
+            .find_multiple(ReadMultipleBooksCriteria::All, DeletedFilter::Exclude, 1, 3)
             .await
             .expect("Failed to get first page");
 
         // Get second page with 3 items
         let page2 = repo
-            .find_multiple(ReadMultipleBooksCriteria::All, 2, 3)
+            .find_multiple(ReadMultipleBooksCriteria::All, DeletedFilter::Exclude, 2, 3)
             .await
             .expect("Failed to get second page");
 
@@ -1,91 +1,1041 @@
 use axum::{
-    Json,
-    extract::{Path, Query},
+    Extension, Json,
+    extract::{Path, Query, State},
 };
 use http::StatusCode;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{
-    AppError,
+    AppError, ProblemDetails,
+    api_keys::api_keys_infrastructure::AuthenticatedKey,
+    audit::audit_domain::Actor,
     books::{
+        BookError,
         books_application::{
-            create_book_use_case::{CreateBookDto, CreateBookUseCase},
-            delete_book_use_case::DeleteBookUseCase,
-            get_book_by_id_use_case::GetBookByIdUseCase,
-            get_books_use_case::{GetBooksDto, GetBooksUseCase},
-            search_books_use_case::{SearchBooksDto, SearchBooksUseCase},
-            update_book_use_case::UpdateBookUseCase,
+            browse_shelf_use_case::BrowseShelfDto, create_book_use_case::CreateBookDto,
+            create_location_use_case::CreateLocationDto, create_subject_use_case::CreateSubjectDto,
+            get_authors_use_case::GetAuthorsDto, get_book_by_id_use_case::GetBookDto,
+            get_books_use_case::GetBooksDto, get_locations_use_case::GetLocationsDto,
+            get_publishers_use_case::GetPublishersDto, get_subjects_use_case::GetSubjectsDto,
+            get_tags_use_case::GetTagsDto, merge_authors_use_case::MergeAuthorsDto,
+            merge_publishers_use_case::MergePublishersDto, rename_author_use_case::RenameAuthorDto,
+            search_books_use_case::SearchBooksDto, set_stock_use_case::SetStockDto,
+            transfer_stock_use_case::TransferStockDto,
+        },
+        books_domain::{
+            Author, AuthorWithBooks, Book, BookAvailability, BookHistory, BookSearchResults,
+            Location, PaginatedAuthors, PaginatedBooks, PaginatedLocations, PaginatedPublishers,
+            PaginatedSubjects, PaginatedTags, Publisher, ShelfBrowse, StockMovement, Subject,
         },
-        books_domain::{Book, PaginatedBooks},
     },
+    metrics::Metrics,
 };
 
+use super::BooksState;
+
+/// Etiqueta de la documentación OpenAPI para las operaciones sobre libros
+pub const BOOKS_TAG: &str = "books";
+
+/// Etiqueta de la documentación OpenAPI para las operaciones sobre autores
+pub const AUTHORS_TAG: &str = "authors";
+
+/// Etiqueta de la documentación OpenAPI para las operaciones sobre editoriales
+pub const PUBLISHERS_TAG: &str = "publishers";
+
+/// Etiqueta de la documentación OpenAPI para el vocabulario de temas
+pub const SUBJECTS_TAG: &str = "subjects";
+
+/// Etiqueta de la documentación OpenAPI para las etiquetas libres de los libros
+pub const TAGS_TAG: &str = "tags";
+
+/// Etiqueta de la documentación OpenAPI para las sucursales y el inventario por sucursal
+pub const LOCATIONS_TAG: &str = "locations";
+
+/// Registra un nuevo libro en el catálogo
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = BOOKS_TAG,
+    request_body = CreateBookDto,
+    security(("api_key" = [])),
+    responses(
+        (status = 201, description = "Libro creado", body = BookId),
+        (
+            status = 400, description = "Datos inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 409, description = "Ya existe un libro con el mismo ISBN",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
 pub async fn create_book_controller(
+    State(books): State<BooksState>,
+    State(metrics): State<Metrics>,
+    Extension(key): Extension<AuthenticatedKey>,
     Json(dto): Json<CreateBookDto>,
 ) -> Result<(StatusCode, Json<BookId>), AppError> {
-    let use_case = CreateBookUseCase::get_instance();
+    let use_case = &books.create_book;
 
-    let book_id = use_case.create_book(dto).await?;
+    let book_id = use_case.create_book(dto, &Actor::api_key(&key.id)).await?;
+    metrics.book_created();
     let response = BookId { id: book_id };
 
     Ok((StatusCode::CREATED, Json(response)))
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, ToSchema)]
 pub struct BookId {
     pub id: Uuid,
 }
 
-pub async fn get_book_controller(Path(id): Path<String>) -> Result<Json<Book>, AppError> {
-    let id = Uuid::try_from(id).map_err(|_| AppError::Custom("id inválido".into()))?;
+/// Obtiene un libro por su identificador
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = BOOKS_TAG,
+    params(("id" = Uuid, Path, description = "Identificador del libro"), GetBookDto),
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Libro encontrado", body = Book),
+        (
+            status = 400, description = "Identificador inválido",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Libro no encontrado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn get_book_controller(
+    State(books): State<BooksState>,
+    Path(id): Path<String>,
+    Query(dto): Query<GetBookDto>,
+) -> Result<Json<Book>, AppError> {
+    let id = Uuid::try_from(id).map_err(|_| BookError::InvalidId)?;
 
-    let use_case = GetBookByIdUseCase::get_instance();
+    let use_case = &books.get_book_by_id;
 
-    let book = use_case.get_book_by_id(id).await?;
+    let book = use_case.get_book_by_id(id, dto.deleted_filter()).await?;
 
     Ok(Json(book))
 }
 
+/// Reemplaza los datos de un libro existente
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    tag = BOOKS_TAG,
+    params(("id" = Uuid, Path, description = "Identificador del libro")),
+    request_body = CreateBookDto,
+    security(("api_key" = [])),
+    responses(
+        (status = 204, description = "Libro actualizado"),
+        (
+            status = 400, description = "Datos inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Libro no encontrado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 409, description = "Ya existe otro libro con el mismo ISBN",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
 pub async fn update_book_controller(
+    State(books): State<BooksState>,
+    Extension(key): Extension<AuthenticatedKey>,
     Path(id): Path<String>,
     Json(dto): Json<CreateBookDto>,
 ) -> Result<StatusCode, AppError> {
-    let id = Uuid::try_from(id).map_err(|_| AppError::Custom("id inválido".into()))?;
+    let id = Uuid::try_from(id).map_err(|_| BookError::InvalidId)?;
     let book = Book::try_from((id, dto))?;
 
-    let use_case = UpdateBookUseCase::get_instance();
+    let use_case = &books.update_book;
 
-    use_case.update_book(book).await?;
+    use_case.update_book(book, &Actor::api_key(&key.id)).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
-pub async fn delete_book_controller(Path(id): Path<String>) -> Result<StatusCode, AppError> {
-    let id = Uuid::try_from(id).map_err(|_| AppError::Custom("id inválido".into()))?;
+/// Elimina un libro del catálogo; puede restaurarse hasta que se purgue
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = BOOKS_TAG,
+    params(("id" = Uuid, Path, description = "Identificador del libro")),
+    security(("api_key" = [])),
+    responses(
+        (status = 204, description = "Libro eliminado"),
+        (
+            status = 400, description = "Identificador inválido",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Libro no encontrado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn delete_book_controller(
+    State(books): State<BooksState>,
+    State(metrics): State<Metrics>,
+    Extension(key): Extension<AuthenticatedKey>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let id = Uuid::try_from(id).map_err(|_| BookError::InvalidId)?;
 
-    let use_case = DeleteBookUseCase::get_instance();
+    let use_case = &books.delete_book;
 
-    use_case.delete_book(id).await?;
+    use_case.delete_book(id, &Actor::api_key(&key.id)).await?;
+    metrics.book_deleted();
 
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Restaura un libro eliminado que aún no se purgó
+#[utoipa::path(
+    post,
+    path = "/{id}/restore",
+    tag = BOOKS_TAG,
+    params(("id" = Uuid, Path, description = "Identificador del libro")),
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Libro restaurado", body = Book),
+        (
+            status = 400, description = "Identificador inválido",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Libro no encontrado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn restore_book_controller(
+    State(books): State<BooksState>,
+    Extension(key): Extension<AuthenticatedKey>,
+    Path(id): Path<String>,
+) -> Result<Json<Book>, AppError> {
+    let id = Uuid::try_from(id).map_err(|_| BookError::InvalidId)?;
+
+    let use_case = &books.restore_book;
+
+    let book = use_case.restore_book(id, &Actor::api_key(&key.id)).await?;
+
+    Ok(Json(book))
+}
+
+/// Lista las revisiones de un libro con los cambios entre revisiones consecutivas
+#[utoipa::path(
+    get,
+    path = "/{id}/history",
+    tag = BOOKS_TAG,
+    params(("id" = Uuid, Path, description = "Identificador del libro")),
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Historial del libro", body = BookHistory),
+        (
+            status = 400, description = "Identificador inválido",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Libro no encontrado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn get_book_history_controller(
+    State(books): State<BooksState>,
+    Path(id): Path<String>,
+) -> Result<Json<BookHistory>, AppError> {
+    let id = Uuid::try_from(id).map_err(|_| BookError::InvalidId)?;
+
+    let use_case = &books.book_history;
+
+    let history = use_case.get_book_history(id).await?;
+
+    Ok(Json(history))
+}
+
+/// Vuelve a los datos de una revisión anterior, guardándolos como una nueva revisión
+#[utoipa::path(
+    post,
+    path = "/{id}/revert/{rev}",
+    tag = BOOKS_TAG,
+    params(
+        ("id" = Uuid, Path, description = "Identificador del libro"),
+        ("rev" = u32, Path, description = "Revisión a restaurar"),
+    ),
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Libro revertido", body = Book),
+        (
+            status = 400, description = "Identificador o revisión inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Libro o revisión no encontrados",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 409, description = "Ya existe otro libro con el mismo ISBN",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn revert_book_controller(
+    State(books): State<BooksState>,
+    Extension(key): Extension<AuthenticatedKey>,
+    Path((id, rev)): Path<(String, String)>,
+) -> Result<Json<Book>, AppError> {
+    let id = Uuid::try_from(id).map_err(|_| BookError::InvalidId)?;
+    let rev = rev.parse::<u32>().map_err(|_| BookError::InvalidRevision)?;
+
+    let use_case = &books.revert_book;
+
+    let book = use_case
+        .revert_book(id, rev, &Actor::api_key(&key.id))
+        .await?;
+
+    Ok(Json(book))
+}
+
+/// Lista los libros eliminados que aún pueden restaurarse
+#[utoipa::path(
+    get,
+    path = "/deleted",
+    tag = BOOKS_TAG,
+    params(GetBooksDto),
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Página de libros eliminados", body = PaginatedBooks),
+        (
+            status = 400, description = "Parámetros inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn get_deleted_books_controller(
+    State(books): State<BooksState>,
+    Query(dto): Query<GetBooksDto>,
+) -> Result<Json<PaginatedBooks>, AppError> {
+    let use_case = &books.get_books;
+
+    let books = use_case.get_deleted_books(dto).await?;
+
+    Ok(Json(books))
+}
+
+/// Lista los libros del catálogo de forma paginada
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = BOOKS_TAG,
+    params(GetBooksDto),
+    responses(
+        (status = 200, description = "Página de libros", body = PaginatedBooks),
+        (status = 304, description = "El catálogo no cambió desde la última consulta"),
+        (
+            status = 400, description = "Parámetros inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
 pub async fn get_all_books_controller(
+    State(books): State<BooksState>,
     Query(dto): Query<GetBooksDto>,
 ) -> Result<Json<PaginatedBooks>, AppError> {
-    let use_case = GetBooksUseCase::get_instance();
+    let use_case = &books.get_books;
 
     let books = use_case.get_all_books(dto).await?;
 
     Ok(Json(books))
 }
 
+/// Busca libros por título o autor y los refina por tema, etiqueta, editorial, idioma o década
+#[utoipa::path(
+    get,
+    path = "/search",
+    tag = BOOKS_TAG,
+    params(SearchBooksDto),
+    responses(
+        (
+            status = 200, description = "Página de libros encontrados con sus facetas",
+            body = BookSearchResults
+        ),
+        (status = 304, description = "El catálogo no cambió desde la última consulta"),
+        (
+            status = 400, description = "Parámetros inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
 pub async fn search_books_controller(
+    State(books): State<BooksState>,
     Query(dto): Query<SearchBooksDto>,
-) -> Result<Json<PaginatedBooks>, AppError> {
-    let use_case = SearchBooksUseCase::get_instance();
+) -> Result<Json<BookSearchResults>, AppError> {
+    let use_case = &books.search_books;
 
     let books = use_case.search_books(dto).await?;
 
     Ok(Json(books))
 }
+
+/// Recorre la estantería alrededor de una signatura, en orden de clasificación
+#[utoipa::path(
+    get,
+    path = "/shelf",
+    tag = BOOKS_TAG,
+    params(BrowseShelfDto),
+    responses(
+        (
+            status = 200, description = "Libros anteriores y posteriores a la signatura",
+            body = ShelfBrowse
+        ),
+        (status = 304, description = "El catálogo no cambió desde la última consulta"),
+        (
+            status = 400, description = "Parámetros inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn browse_shelf_controller(
+    State(books): State<BooksState>,
+    Query(dto): Query<BrowseShelfDto>,
+) -> Result<Json<ShelfBrowse>, AppError> {
+    let use_case = &books.browse_shelf;
+
+    let shelf = use_case.browse_shelf(dto).await?;
+
+    Ok(Json(shelf))
+}
+
+/// Lista los autores del catálogo con la cantidad de libros de cada uno
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = AUTHORS_TAG,
+    params(GetAuthorsDto),
+    responses(
+        (status = 200, description = "Página de autores", body = PaginatedAuthors),
+        (status = 304, description = "El catálogo no cambió desde la última consulta"),
+        (
+            status = 400, description = "Parámetros inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn get_authors_controller(
+    State(books): State<BooksState>,
+    Query(dto): Query<GetAuthorsDto>,
+) -> Result<Json<PaginatedAuthors>, AppError> {
+    let use_case = &books.get_authors;
+
+    let authors = use_case.get_authors(dto).await?;
+
+    Ok(Json(authors))
+}
+
+/// Obtiene un autor junto con sus libros
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = AUTHORS_TAG,
+    params(("id" = i64, Path, description = "Identificador del autor")),
+    responses(
+        (status = 200, description = "Autor encontrado", body = AuthorWithBooks),
+        (status = 304, description = "El catálogo no cambió desde la última consulta"),
+        (
+            status = 400, description = "Identificador inválido",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Autor no encontrado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn get_author_controller(
+    State(books): State<BooksState>,
+    Path(id): Path<String>,
+) -> Result<Json<AuthorWithBooks>, AppError> {
+    let id = id.parse::<i64>().map_err(|_| BookError::InvalidAuthorId)?;
+
+    let use_case = &books.get_authors;
+
+    let author = use_case.get_author(id).await?;
+
+    Ok(Json(author))
+}
+
+/// Cambia el nombre de un autor en todos sus libros
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    tag = AUTHORS_TAG,
+    params(("id" = i64, Path, description = "Identificador del autor")),
+    request_body = RenameAuthorDto,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Autor renombrado", body = Author),
+        (
+            status = 400, description = "Datos inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Autor no encontrado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 409, description = "Ya existe otro autor con el mismo nombre",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn rename_author_controller(
+    State(books): State<BooksState>,
+    Extension(key): Extension<AuthenticatedKey>,
+    Path(id): Path<String>,
+    Json(dto): Json<RenameAuthorDto>,
+) -> Result<Json<Author>, AppError> {
+    let id = id.parse::<i64>().map_err(|_| BookError::InvalidAuthorId)?;
+
+    let use_case = &books.rename_author;
+
+    let author = use_case
+        .rename_author(id, dto, &Actor::api_key(&key.id))
+        .await?;
+
+    Ok(Json(author))
+}
+
+/// Fusiona un autor duplicado con otro; sus libros pasan al autor indicado en `into`
+#[utoipa::path(
+    post,
+    path = "/{id}/merge",
+    tag = AUTHORS_TAG,
+    params(("id" = i64, Path, description = "Identificador del autor duplicado")),
+    request_body = MergeAuthorsDto,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Autor resultante de la fusión", body = Author),
+        (
+            status = 400, description = "Datos inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Autor no encontrado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn merge_authors_controller(
+    State(books): State<BooksState>,
+    Extension(key): Extension<AuthenticatedKey>,
+    Path(id): Path<String>,
+    Json(dto): Json<MergeAuthorsDto>,
+) -> Result<Json<Author>, AppError> {
+    let id = id.parse::<i64>().map_err(|_| BookError::InvalidAuthorId)?;
+
+    let use_case = &books.merge_authors;
+
+    let author = use_case
+        .merge_authors(id, dto, &Actor::api_key(&key.id))
+        .await?;
+
+    Ok(Json(author))
+}
+
+/// Lista o busca las editoriales del catálogo con la cantidad de libros de cada una
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = PUBLISHERS_TAG,
+    params(GetPublishersDto),
+    responses(
+        (status = 200, description = "Página de editoriales", body = PaginatedPublishers),
+        (status = 304, description = "El catálogo no cambió desde la última consulta"),
+        (
+            status = 400, description = "Parámetros inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn get_publishers_controller(
+    State(books): State<BooksState>,
+    Query(dto): Query<GetPublishersDto>,
+) -> Result<Json<PaginatedPublishers>, AppError> {
+    let use_case = &books.get_publishers;
+
+    let publishers = use_case.get_publishers(dto).await?;
+
+    Ok(Json(publishers))
+}
+
+/// Fusiona una editorial duplicada con otra; sus libros pasan a la editorial indicada en `into`
+#[utoipa::path(
+    post,
+    path = "/{id}/merge",
+    tag = PUBLISHERS_TAG,
+    params(("id" = i64, Path, description = "Identificador de la editorial duplicada")),
+    request_body = MergePublishersDto,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Editorial resultante de la fusión", body = Publisher),
+        (
+            status = 400, description = "Datos inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Editorial no encontrada",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn merge_publishers_controller(
+    State(books): State<BooksState>,
+    Extension(key): Extension<AuthenticatedKey>,
+    Path(id): Path<String>,
+    Json(dto): Json<MergePublishersDto>,
+) -> Result<Json<Publisher>, AppError> {
+    let id = id
+        .parse::<i64>()
+        .map_err(|_| BookError::InvalidPublisherId)?;
+
+    let use_case = &books.merge_publishers;
+
+    let publisher = use_case
+        .merge_publishers(id, dto, &Actor::api_key(&key.id))
+        .await?;
+
+    Ok(Json(publisher))
+}
+
+/// Lista o busca los temas del vocabulario con la cantidad de libros de cada uno
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = SUBJECTS_TAG,
+    params(GetSubjectsDto),
+    responses(
+        (status = 200, description = "Página de temas", body = PaginatedSubjects),
+        (status = 304, description = "El catálogo no cambió desde la última consulta"),
+        (
+            status = 400, description = "Parámetros inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn get_subjects_controller(
+    State(books): State<BooksState>,
+    Query(dto): Query<GetSubjectsDto>,
+) -> Result<Json<PaginatedSubjects>, AppError> {
+    let use_case = &books.get_subjects;
+
+    let subjects = use_case.get_subjects(dto).await?;
+
+    Ok(Json(subjects))
+}
+
+/// Agrega un tema al vocabulario; los libros solo pueden usar temas existentes
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = SUBJECTS_TAG,
+    request_body = CreateSubjectDto,
+    security(("api_key" = [])),
+    responses(
+        (status = 201, description = "Tema creado", body = Subject),
+        (
+            status = 400, description = "Datos inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 409, description = "Ya existe un tema equivalente",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn create_subject_controller(
+    State(books): State<BooksState>,
+    Extension(key): Extension<AuthenticatedKey>,
+    Json(dto): Json<CreateSubjectDto>,
+) -> Result<(StatusCode, Json<Subject>), AppError> {
+    let use_case = &books.create_subject;
+
+    let subject = use_case
+        .create_subject(dto, &Actor::api_key(&key.id))
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(subject)))
+}
+
+/// Lista o busca las etiquetas de los libros con la cantidad de libros de cada una
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = TAGS_TAG,
+    params(GetTagsDto),
+    responses(
+        (status = 200, description = "Página de etiquetas", body = PaginatedTags),
+        (status = 304, description = "El catálogo no cambió desde la última consulta"),
+        (
+            status = 400, description = "Parámetros inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn get_tags_controller(
+    State(books): State<BooksState>,
+    Query(dto): Query<GetTagsDto>,
+) -> Result<Json<PaginatedTags>, AppError> {
+    let use_case = &books.get_tags;
+
+    let tags = use_case.get_tags(dto).await?;
+
+    Ok(Json(tags))
+}
+
+/// Lista las sucursales con la cantidad de ejemplares de cada una
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = LOCATIONS_TAG,
+    params(GetLocationsDto),
+    responses(
+        (status = 200, description = "Página de sucursales", body = PaginatedLocations),
+        (status = 304, description = "El catálogo no cambió desde la última consulta"),
+        (
+            status = 400, description = "Parámetros inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn get_locations_controller(
+    State(books): State<BooksState>,
+    Query(dto): Query<GetLocationsDto>,
+) -> Result<Json<PaginatedLocations>, AppError> {
+    let use_case = &books.get_locations;
+
+    let locations = use_case.get_locations(dto).await?;
+
+    Ok(Json(locations))
+}
+
+/// Agrega una sucursal; las ApiKeys limitadas a una sucursal no pueden hacerlo
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = LOCATIONS_TAG,
+    request_body = CreateLocationDto,
+    security(("api_key" = [])),
+    responses(
+        (status = 201, description = "Sucursal creada", body = Location),
+        (
+            status = 400, description = "Datos inválidos",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a una sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 409, description = "Ya existe una sucursal equivalente",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn create_location_controller(
+    State(books): State<BooksState>,
+    Extension(key): Extension<AuthenticatedKey>,
+    Json(dto): Json<CreateLocationDto>,
+) -> Result<(StatusCode, Json<Location>), AppError> {
+    let use_case = &books.create_location;
+
+    let location = use_case
+        .create_location(dto, &Actor::api_key(&key.id), key.scope.location())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(location)))
+}
+
+/// Ejemplares de un libro en cada sucursal y los que aún no se asignaron
+#[utoipa::path(
+    get,
+    path = "/{id}/availability",
+    tag = BOOKS_TAG,
+    params(("id" = Uuid, Path, description = "Identificador del libro")),
+    responses(
+        (status = 200, description = "Disponibilidad del libro", body = BookAvailability),
+        (status = 304, description = "El catálogo no cambió desde la última consulta"),
+        (
+            status = 400, description = "Identificador inválido",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Libro no encontrado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn get_book_availability_controller(
+    State(books): State<BooksState>,
+    Path(id): Path<String>,
+) -> Result<Json<BookAvailability>, AppError> {
+    let id = Uuid::try_from(id).map_err(|_| BookError::InvalidId)?;
+
+    let use_case = &books.book_availability;
+
+    let availability = use_case.get_availability(id).await?;
+
+    Ok(Json(availability))
+}
+
+/// Fija los ejemplares de un libro en una sucursal
+#[utoipa::path(
+    put,
+    path = "/{id}/locations/{location_id}",
+    tag = BOOKS_TAG,
+    params(
+        ("id" = Uuid, Path, description = "Identificador del libro"),
+        ("location_id" = i64, Path, description = "Identificador de la sucursal"),
+    ),
+    request_body = SetStockDto,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Disponibilidad actualizada", body = BookAvailability),
+        (
+            status = 400,
+            description = "Datos inválidos o más ejemplares que la cantidad guardada",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a otra sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Libro o sucursal no encontrados",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn set_stock_controller(
+    State(books): State<BooksState>,
+    Extension(key): Extension<AuthenticatedKey>,
+    Path((id, location_id)): Path<(String, String)>,
+    Json(dto): Json<SetStockDto>,
+) -> Result<Json<BookAvailability>, AppError> {
+    let id = Uuid::try_from(id).map_err(|_| BookError::InvalidId)?;
+    let location_id = location_id
+        .parse::<i64>()
+        .map_err(|_| BookError::InvalidLocationId)?;
+
+    let use_case = &books.set_stock;
+
+    let availability = use_case
+        .set_stock(
+            id,
+            location_id,
+            dto,
+            &Actor::api_key(&key.id),
+            key.scope.location(),
+        )
+        .await?;
+
+    Ok(Json(availability))
+}
+
+/// Traslada ejemplares de un libro entre dos sucursales
+#[utoipa::path(
+    post,
+    path = "/{id}/transfers",
+    tag = BOOKS_TAG,
+    params(("id" = Uuid, Path, description = "Identificador del libro")),
+    request_body = TransferStockDto,
+    security(("api_key" = [])),
+    responses(
+        (status = 201, description = "Traslado registrado", body = StockMovement),
+        (
+            status = 400,
+            description = "Datos inválidos o ejemplares insuficientes en la sucursal de origen",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "La ApiKey está limitada a otra sucursal",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Libro o sucursal no encontrados",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn transfer_stock_controller(
+    State(books): State<BooksState>,
+    Extension(key): Extension<AuthenticatedKey>,
+    Path(id): Path<String>,
+    Json(dto): Json<TransferStockDto>,
+) -> Result<(StatusCode, Json<StockMovement>), AppError> {
+    let id = Uuid::try_from(id).map_err(|_| BookError::InvalidId)?;
+
+    let use_case = &books.transfer_stock;
+
+    let movement = use_case
+        .transfer_stock(id, dto, &Actor::api_key(&key.id), key.scope.location())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(movement)))
+}
+
+/// Lista los traslados de un libro entre sucursales, del más reciente al más antiguo. Con
+/// una ApiKey limitada a una sucursal solo se listan los que salen o llegan a ella.
+#[utoipa::path(
+    get,
+    path = "/{id}/movements",
+    tag = BOOKS_TAG,
+    params(("id" = Uuid, Path, description = "Identificador del libro")),
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Traslados del libro", body = Vec<StockMovement>),
+        (
+            status = 400, description = "Identificador inválido",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "No autenticado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Libro no encontrado",
+            body = ProblemDetails, content_type = "application/problem+json"
+        ),
+    )
+)]
+pub async fn get_stock_movements_controller(
+    State(books): State<BooksState>,
+    Extension(key): Extension<AuthenticatedKey>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<StockMovement>>, AppError> {
+    let id = Uuid::try_from(id).map_err(|_| BookError::InvalidId)?;
+
+    let use_case = &books.stock_movements;
+
+    let movements = use_case.get_movements(id, key.scope.location()).await?;
+
+    Ok(Json(movements))
+}
@@ -1,11 +1,17 @@
 use std::str::FromStr;
 
 use sqlx::FromRow;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::books::{
     BookError,
-    books_domain::{Book, BookAuthor, BookPublisher, BookTitle, Isbn},
+    books_domain::{
+        Author, Book, BookAuthor, BookContributor, BookEdition, BookFormat, BookPublisher,
+        BookRevision, BookSeries, BookSubject, BookTag, BookTitle, CallNumber, Classification,
+        ClassificationScheme, ContributorRole, FacetCount, Isbn, LanguageCode, Location,
+        LocationName, LocationStock, Publisher, StockMovement, Subject, Tag,
+    },
 };
 
 /// DTO para la tabla books
@@ -17,12 +23,27 @@ pub struct DbBook {
     pub year: i64,
     pub isbn: String,
     pub stored: i64,
+    pub deleted_at: Option<i64>,
+    pub subtitle: Option<String>,
+    pub edition: Option<String>,
+    pub language: Option<String>,
+    pub page_count: Option<i64>,
+    pub format: Option<String>,
+    pub series: Option<String>,
+    pub series_number: Option<i64>,
+    pub description: Option<String>,
+    pub cover_url: Option<String>,
+    pub classification_scheme: Option<String>,
+    pub classification: Option<String>,
+    pub call_number: Option<String>,
 }
 
 impl DbBook {
-    pub async fn to_domain_with_authors(
+    pub fn to_domain(
         &self,
-        authors: Vec<BookAuthor>,
+        authors: Vec<BookContributor>,
+        subjects: Vec<BookSubject>,
+        tags: Vec<BookTag>,
     ) -> Result<Book, BookError> {
         let id = Uuid::try_from(self.id.clone()).map_err(|e| {
             BookError::DatabaseError(format!("Error al crear BookId: {:?}", e).into())
@@ -40,14 +61,108 @@ impl DbBook {
             BookError::DatabaseError(format!("Error al crear Isbn: {:?}", e).into())
         })?;
 
+        let subtitle = self
+            .subtitle
+            .as_deref()
+            .map(BookTitle::from_str)
+            .transpose()
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al crear subtitle: {:?}", e).into())
+            })?;
+
+        let edition = self
+            .edition
+            .as_deref()
+            .map(BookEdition::from_str)
+            .transpose()
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al crear BookEdition: {:?}", e).into())
+            })?;
+
+        let language = self
+            .language
+            .as_deref()
+            .map(LanguageCode::from_str)
+            .transpose()
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al crear LanguageCode: {:?}", e).into())
+            })?;
+
+        let format = self
+            .format
+            .as_deref()
+            .map(BookFormat::from_str)
+            .transpose()
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al crear BookFormat: {:?}", e).into())
+            })?;
+
+        let series = self
+            .series
+            .as_deref()
+            .map(BookSeries::from_str)
+            .transpose()
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al crear BookSeries: {:?}", e).into())
+            })?;
+
+        let classification = self
+            .classification_scheme
+            .as_deref()
+            .zip(self.classification.as_deref())
+            .map(|(scheme, number)| {
+                ClassificationScheme::from_str(scheme)
+                    .map_err(|e| {
+                        BookError::DatabaseError(
+                            format!("Error al crear ClassificationScheme: {:?}", e).into(),
+                        )
+                    })
+                    .and_then(|scheme| Classification::new(scheme, number))
+            })
+            .transpose()
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al crear Classification: {:?}", e).into())
+            })?;
+
+        let call_number = self
+            .call_number
+            .as_deref()
+            .map(CallNumber::from_str)
+            .transpose()
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al crear CallNumber: {:?}", e).into())
+            })?;
+
+        let deleted_at = self
+            .deleted_at
+            .map(|millis| OffsetDateTime::from_unix_timestamp_nanos(i128::from(millis) * 1_000_000))
+            .transpose()
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al crear deleted_at: {:?}", e).into())
+            })?;
+
         let book = Book {
             id,
             title,
             authors,
             publisher,
+            subjects,
+            tags,
             year: self.year as u16,
             isbn,
             stored_quantity: self.stored as u16,
+            subtitle,
+            edition,
+            language,
+            page_count: self.page_count.map(|pages| pages as u16),
+            format,
+            series,
+            series_number: self.series_number.map(|number| number as u16),
+            description: self.description.clone(),
+            cover_url: self.cover_url.clone(),
+            classification,
+            call_number,
+            deleted_at,
         };
 
         Ok(book)
@@ -61,16 +176,234 @@ pub struct DbAuthor {
     pub name: String,
 }
 
-/// DTO para obtener solo el nombre del autor en consultas
+/// DTO para los autores junto con la cantidad de libros vigentes
 #[derive(Debug, FromRow)]
-pub struct DbAuthorName {
+pub struct DbAuthorWithCount {
+    pub id: i64,
     pub name: String,
+    pub book_count: i64,
 }
 
-impl DbAuthorName {
-    pub fn to_domain(&self) -> Result<BookAuthor, BookError> {
-        BookAuthor::try_from(self.name.clone()).map_err(|e| {
+impl DbAuthorWithCount {
+    pub fn to_domain(&self) -> Result<Author, BookError> {
+        let name = BookAuthor::try_from(self.name.clone()).map_err(|e| {
             BookError::DatabaseError(format!("Error al crear BookAuthor: {:?}", e).into())
+        })?;
+
+        Ok(Author {
+            id: self.id,
+            name,
+            book_count: self.book_count as u64,
+        })
+    }
+}
+
+/// DTO para las editoriales junto con la cantidad de libros vigentes
+#[derive(Debug, FromRow)]
+pub struct DbPublisherWithCount {
+    pub id: i64,
+    pub name: String,
+    pub book_count: i64,
+}
+
+impl DbPublisherWithCount {
+    pub fn to_domain(&self) -> Result<Publisher, BookError> {
+        let name = BookPublisher::try_from(self.name.clone()).map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear BookPublisher: {:?}", e).into())
+        })?;
+
+        Ok(Publisher {
+            id: self.id,
+            name,
+            book_count: self.book_count as u64,
+        })
+    }
+}
+
+/// DTO para los temas o etiquetas junto con la cantidad de libros vigentes
+#[derive(Debug, FromRow)]
+pub struct DbTermWithCount {
+    pub id: i64,
+    pub name: String,
+    pub book_count: i64,
+}
+
+impl DbTermWithCount {
+    pub fn to_subject(&self) -> Result<Subject, BookError> {
+        let name = BookSubject::try_from(self.name.clone()).map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear BookSubject: {:?}", e).into())
+        })?;
+
+        Ok(Subject {
+            id: self.id,
+            name,
+            book_count: self.book_count as u64,
+        })
+    }
+
+    pub fn to_tag(&self) -> Result<Tag, BookError> {
+        let name = BookTag::try_from(self.name.clone()).map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear BookTag: {:?}", e).into())
+        })?;
+
+        Ok(Tag {
+            id: self.id,
+            name,
+            book_count: self.book_count as u64,
+        })
+    }
+}
+
+/// DTO para las sucursales junto con sus ejemplares de libros vigentes
+#[derive(Debug, FromRow)]
+pub struct DbLocationWithCount {
+    pub id: i64,
+    pub name: String,
+    pub copies: i64,
+}
+
+impl DbLocationWithCount {
+    pub fn to_domain(&self) -> Result<Location, BookError> {
+        let name = LocationName::try_from(self.name.clone()).map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear LocationName: {:?}", e).into())
+        })?;
+
+        Ok(Location {
+            id: self.id,
+            name,
+            copies: self.copies as u64,
+        })
+    }
+}
+
+/// DTO para los ejemplares de un libro en una sucursal
+#[derive(Debug, FromRow)]
+pub struct DbLocationStock {
+    pub location_id: i64,
+    pub location: String,
+    pub quantity: i64,
+}
+
+impl DbLocationStock {
+    pub fn to_domain(&self) -> Result<LocationStock, BookError> {
+        let location = LocationName::try_from(self.location.clone()).map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear LocationName: {:?}", e).into())
+        })?;
+
+        Ok(LocationStock {
+            location_id: self.location_id,
+            location,
+            quantity: self.quantity as u16,
+        })
+    }
+}
+
+/// DTO para la tabla stock_movements
+#[derive(Debug, FromRow)]
+pub struct DbStockMovement {
+    pub id: i64,
+    pub book_id: String,
+    pub from_location_id: i64,
+    pub to_location_id: i64,
+    pub quantity: i64,
+    pub actor: String,
+    pub created_at: i64,
+}
+
+impl DbStockMovement {
+    pub fn into_domain(self) -> Result<StockMovement, BookError> {
+        let book_id = Uuid::try_from(self.book_id.clone()).map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear BookId: {:?}", e).into())
+        })?;
+
+        let created_at =
+            OffsetDateTime::from_unix_timestamp_nanos(i128::from(self.created_at) * 1_000_000)
+                .map_err(|e| {
+                    BookError::DatabaseError(format!("Error al crear created_at: {:?}", e).into())
+                })?;
+
+        Ok(StockMovement {
+            id: self.id,
+            book_id,
+            from_location_id: self.from_location_id,
+            to_location_id: self.to_location_id,
+            quantity: self.quantity as u16,
+            actor: self.actor.into(),
+            created_at,
+        })
+    }
+}
+
+/// DTO para la cantidad de libros con un mismo valor en una faceta
+#[derive(Debug, FromRow)]
+pub struct DbFacetCount {
+    pub value: String,
+    pub count: i64,
+}
+
+impl DbFacetCount {
+    pub fn into_domain(self) -> FacetCount {
+        FacetCount {
+            value: self.value,
+            count: self.count as u64,
+        }
+    }
+}
+
+/// DTO para obtener el nombre y el papel de cada colaborador de un libro
+#[derive(Debug, FromRow)]
+pub struct DbContributor {
+    pub name: String,
+    pub role: String,
+}
+
+impl DbContributor {
+    pub fn to_domain(&self) -> Result<BookContributor, BookError> {
+        let name = BookAuthor::try_from(self.name.clone()).map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear BookAuthor: {:?}", e).into())
+        })?;
+
+        let role = ContributorRole::from_str(&self.role).map_err(|e| {
+            BookError::DatabaseError(format!("Error al crear ContributorRole: {:?}", e).into())
+        })?;
+
+        Ok(BookContributor::new(name, role))
+    }
+}
+
+/// DTO para la tabla book_revisions
+#[derive(Debug, FromRow)]
+pub struct DbBookRevision {
+    pub book_id: String,
+    pub revision: i64,
+    pub snapshot: String,
+    pub actor: String,
+    pub created_at: i64,
+}
+
+impl DbBookRevision {
+    pub fn into_domain(self) -> Result<BookRevision, BookError> {
+        let book = serde_json::from_str(&self.snapshot).map_err(|e| {
+            BookError::DatabaseError(
+                format!(
+                    "Error al leer revisión {} de {}: {:?}",
+                    self.revision, self.book_id, e
+                )
+                .into(),
+            )
+        })?;
+
+        let created_at =
+            OffsetDateTime::from_unix_timestamp_nanos(i128::from(self.created_at) * 1_000_000)
+                .map_err(|e| {
+                    BookError::DatabaseError(format!("Error al crear created_at: {:?}", e).into())
+                })?;
+
+        Ok(BookRevision {
+            revision: self.revision as u32,
+            created_at,
+            actor: self.actor.into(),
+            book,
         })
     }
 }
@@ -0,0 +1,372 @@
+use std::cmp::max;
+
+use sqlx::{Pool, Sqlite, SqlitePool, query, query_as};
+use uuid::Uuid;
+
+use crate::books::{
+    BookError,
+    books_domain::{
+        Location, LocationName, LocationRepository, LocationStock, NewStockMovement,
+        PaginatedLocations, StockMovement, fold_text,
+    },
+    books_infrastructure::db_dtos::{DbLocationStock, DbLocationWithCount, DbStockMovement},
+};
+
+use super::books_sqlite_repository::{BookSqliteRepository, unix_millis};
+
+/// Columnas de [`DbLocationWithCount`]; solo se cuentan los ejemplares de libros vigentes.
+/// Se debe agrupar por `l.id`.
+const SELECT_LOCATIONS: &str = r#"
+    SELECT l.id, l.name, COALESCE(SUM(bs.quantity), 0) AS copies
+    FROM locations l
+    LEFT JOIN book_stock bs ON bs.location_id = l.id
+        AND bs.book_id IN (SELECT id FROM books WHERE deleted_at IS NULL)
+"#;
+
+/// Sucursales, ejemplares y traslados guardados en `locations`, `book_stock` y
+/// `stock_movements`
+#[derive(Clone)]
+pub struct LocationSqliteRepository {
+    pub pool: Pool<Sqlite>,
+}
+
+impl LocationSqliteRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl LocationRepository for LocationSqliteRepository {
+    async fn find_locations(
+        &self,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedLocations, BookError> {
+        let page = max(page, 1); // prevenimos un substract-overflow
+        let offset = (page - 1) as i64 * page_size as i64;
+        let limit = page_size as i64;
+
+        let locations = query_as::<_, DbLocationWithCount>(&format!(
+            "{SELECT_LOCATIONS} GROUP BY l.id ORDER BY l.normalized_name LIMIT ? OFFSET ?"
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al buscar sucursales: {}", e).into())
+        })?;
+
+        let (total,) = query_as::<_, (i64,)>("SELECT COUNT(*) FROM locations")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| {
+                BookError::DatabaseError(format!("Error al contar sucursales: {}", e).into())
+            })?;
+
+        Ok(PaginatedLocations {
+            locations: locations
+                .iter()
+                .map(DbLocationWithCount::to_domain)
+                .collect::<Result<_, _>>()?,
+            total: total as u64,
+            page,
+            page_size,
+        })
+    }
+
+    async fn find_location(&self, id: i64) -> Result<Option<Location>, BookError> {
+        let location = query_as::<_, DbLocationWithCount>(&format!(
+            "{SELECT_LOCATIONS} WHERE l.id = ? GROUP BY l.id"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| BookError::DatabaseError(format!("Error al buscar sucursal: {}", e).into()))?;
+
+        location
+            .as_ref()
+            .map(DbLocationWithCount::to_domain)
+            .transpose()
+    }
+
+    async fn create_location(&self, name: &LocationName) -> Result<Location, BookError> {
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al crear sucursal: {}", e).into())
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+
+        let result = query_as::<_, (i64,)>(
+            "INSERT OR IGNORE INTO locations (name, normalized_name) VALUES (?, ?) RETURNING id",
+        )
+        .bind(name.as_str())
+        .bind(fold_text(name.as_str()))
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_err)?;
+
+        let Some((id,)) = result else {
+            return Err(BookError::LocationAlreadyExists(name.to_string()));
+        };
+
+        BookSqliteRepository::touch_catalog(&mut tx).await?;
+        tx.commit().await.map_err(map_err)?;
+
+        Ok(Location {
+            id,
+            name: name.clone(),
+            copies: 0,
+        })
+    }
+
+    async fn stock(&self, id: &Uuid) -> Result<Vec<LocationStock>, BookError> {
+        let stock = query_as::<_, DbLocationStock>(
+            r#"
+            SELECT l.id AS location_id, l.name AS location, bs.quantity
+            FROM book_stock bs
+            JOIN locations l ON bs.location_id = l.id
+            WHERE bs.book_id = ?
+            ORDER BY l.normalized_name
+            "#,
+        )
+        .bind(id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al buscar ejemplares del libro: {}", e).into())
+        })?;
+
+        stock.iter().map(DbLocationStock::to_domain).collect()
+    }
+
+    async fn set_stock(&self, id: &Uuid, location: i64, quantity: u16) -> Result<(), BookError> {
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al guardar ejemplares: {}", e).into())
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+
+        let result = if quantity == 0 {
+            query("DELETE FROM book_stock WHERE book_id = ? AND location_id = ?")
+                .bind(id.to_string())
+                .bind(location)
+                .execute(&mut *tx)
+                .await
+        } else {
+            query(
+                r#"
+                INSERT INTO book_stock (book_id, location_id, quantity) VALUES (?, ?, ?)
+                ON CONFLICT (book_id, location_id) DO UPDATE SET quantity = excluded.quantity
+                "#,
+            )
+            .bind(id.to_string())
+            .bind(location)
+            .bind(quantity as i64)
+            .execute(&mut *tx)
+            .await
+        };
+
+        result.map_err(map_err)?;
+        BookSqliteRepository::touch_catalog(&mut tx).await?;
+
+        tx.commit().await.map_err(map_err)
+    }
+
+    async fn transfer(&self, movement: NewStockMovement) -> Result<StockMovement, BookError> {
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al trasladar ejemplares: {}", e).into())
+        };
+
+        let book_id = movement.book_id.to_string();
+        let quantity = movement.quantity as i64;
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+
+        // Si se trasladan todos los ejemplares, la sucursal de origen deja de tener el libro
+        let emptied =
+            query("DELETE FROM book_stock WHERE book_id = ? AND location_id = ? AND quantity = ?")
+                .bind(&book_id)
+                .bind(movement.from_location_id)
+                .bind(quantity)
+                .execute(&mut *tx)
+                .await
+                .map_err(map_err)?;
+
+        if emptied.rows_affected() == 0 {
+            let result = query(
+                "UPDATE book_stock SET quantity = quantity - ? WHERE book_id = ? AND location_id = ? AND quantity > ?",
+            )
+            .bind(quantity)
+            .bind(&book_id)
+            .bind(movement.from_location_id)
+            .bind(quantity)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+            if result.rows_affected() == 0 {
+                return Err(BookError::InsufficientStock);
+            }
+        }
+
+        query(
+            r#"
+            INSERT INTO book_stock (book_id, location_id, quantity) VALUES (?, ?, ?)
+            ON CONFLICT (book_id, location_id) DO UPDATE SET quantity = quantity + excluded.quantity
+            "#,
+        )
+        .bind(&book_id)
+        .bind(movement.to_location_id)
+        .bind(quantity)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_err)?;
+
+        let (id,) = query_as::<_, (i64,)>(
+            r#"
+            INSERT INTO stock_movements
+                (book_id, from_location_id, to_location_id, quantity, actor, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            RETURNING id
+            "#,
+        )
+        .bind(&book_id)
+        .bind(movement.from_location_id)
+        .bind(movement.to_location_id)
+        .bind(quantity)
+        .bind(movement.actor.as_str())
+        .bind(unix_millis(movement.created_at))
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_err)?;
+
+        BookSqliteRepository::touch_catalog(&mut tx).await?;
+
+        tx.commit().await.map_err(map_err)?;
+
+        Ok(StockMovement {
+            id,
+            book_id: movement.book_id,
+            from_location_id: movement.from_location_id,
+            to_location_id: movement.to_location_id,
+            quantity: movement.quantity,
+            actor: movement.actor,
+            created_at: movement.created_at,
+        })
+    }
+
+    async fn movements(&self, id: &Uuid) -> Result<Vec<StockMovement>, BookError> {
+        let movements = query_as::<_, DbStockMovement>(
+            "SELECT * FROM stock_movements WHERE book_id = ? ORDER BY id DESC",
+        )
+        .bind(id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al buscar traslados: {}", e).into())
+        })?;
+
+        movements
+            .into_iter()
+            .map(DbStockMovement::into_domain)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use time::OffsetDateTime;
+
+    use crate::{
+        audit::audit_domain::Actor,
+        books::{
+            books_domain::{BookRepository, DeletedFilter, ReadMultipleBooksCriteria},
+            books_infrastructure::books_sqlite_repository::tests::{
+                create_test_book, setup_test_db,
+            },
+        },
+    };
+
+    #[tokio::test]
+    async fn test_locations_stock_and_transfers() {
+        // Arrange
+        let repo = setup_test_db().await;
+        let location_repo = LocationSqliteRepository::new(repo.pool.clone());
+        let book = create_test_book();
+        let book_id = book.id;
+        repo.create(book).await.expect("Failed to create book");
+        let centro = location_repo
+            .create_location(&"Centro".parse::<LocationName>().unwrap())
+            .await
+            .expect("Failed to create location");
+        let norte = location_repo
+            .create_location(&"Norte".parse::<LocationName>().unwrap())
+            .await
+            .expect("Failed to create location");
+        let movement = |quantity| NewStockMovement {
+            book_id,
+            from_location_id: centro.id,
+            to_location_id: norte.id,
+            quantity,
+            actor: Actor::cli(),
+            created_at: OffsetDateTime::now_utc(),
+        };
+
+        // Act
+        let duplicate = location_repo
+            .create_location(&"centro".parse::<LocationName>().unwrap())
+            .await;
+        location_repo
+            .set_stock(&book_id, centro.id, 3)
+            .await
+            .expect("Failed to set stock");
+        let transferred = location_repo
+            .transfer(movement(3))
+            .await
+            .expect("Failed to transfer stock");
+        let insufficient = location_repo.transfer(movement(1)).await;
+
+        // Assert - the emptied location is removed and the failed transfer leaves no trace
+        assert!(matches!(
+            duplicate,
+            Err(BookError::LocationAlreadyExists(_))
+        ));
+        assert_eq!(transferred.quantity, 3);
+        assert!(matches!(insufficient, Err(BookError::InsufficientStock)));
+        let stock = location_repo
+            .stock(&book_id)
+            .await
+            .expect("Failed to get stock");
+        assert_eq!(stock.len(), 1);
+        assert_eq!((stock[0].location_id, stock[0].quantity), (norte.id, 3));
+        let movements = location_repo
+            .movements(&book_id)
+            .await
+            .expect("Failed to get movements");
+        assert_eq!(movements.len(), 1);
+        assert_eq!(movements[0].id, transferred.id);
+        let books = repo
+            .find_multiple(
+                ReadMultipleBooksCriteria::ByLocation(norte.id),
+                DeletedFilter::Exclude,
+                1,
+                10,
+            )
+            .await
+            .expect("Failed to find books");
+        assert_eq!(books.total, 1);
+        let locations = location_repo
+            .find_locations(1, 10)
+            .await
+            .expect("Failed to find locations");
+        let copies = locations
+            .locations
+            .iter()
+            .map(|location| location.copies)
+            .collect::<Vec<_>>();
+        assert_eq!(copies, [0, 3]);
+    }
+}
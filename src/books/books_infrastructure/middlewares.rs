@@ -0,0 +1,199 @@
+use std::{sync::Arc, time::SystemTime};
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http::{
+    HeaderMap, HeaderValue, Method, StatusCode,
+    header::{CACHE_CONTROL, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+};
+use tracing::warn;
+
+use crate::{
+    books::{
+        books_application::get_catalog_version_use_case::GetCatalogVersionUseCase,
+        books_domain::CatalogVersion,
+    },
+    i18n::tr,
+};
+
+/// Valor por defecto de la cabecera `Cache-Control` para las rutas públicas del catálogo
+pub const DEFAULT_CACHE_CONTROL: &str = "public, max-age=60";
+
+#[derive(Clone)]
+pub struct HttpCacheMiddleware {
+    pub use_case: Arc<GetCatalogVersionUseCase>,
+    pub cache_control: HeaderValue,
+}
+
+impl HttpCacheMiddleware {
+    /// Responde con 304 si el cliente ya tiene la versión vigente del catálogo,
+    /// en otro caso ejecuta la petición y agrega las cabeceras de cache
+    pub async fn handle(&self, req: Request, next: Next) -> Response {
+        if !matches!(*req.method(), Method::GET | Method::HEAD) {
+            return next.run(req).await;
+        }
+
+        let version = match self.use_case.get_catalog_version().await {
+            Ok(version) => version,
+            Err(e) => {
+                warn!("{}", tr!("log.catalog_version_unavailable", error = e));
+                return next.run(req).await;
+            }
+        };
+
+        let validators = CacheValidators::from(version);
+
+        if validators.is_fresh(req.headers()) {
+            let mut response = StatusCode::NOT_MODIFIED.into_response();
+            self.apply_headers(response.headers_mut(), &validators);
+            return response;
+        }
+
+        let mut response = next.run(req).await;
+        if response.status().is_success() {
+            self.apply_headers(response.headers_mut(), &validators);
+        }
+
+        response
+    }
+
+    fn apply_headers(&self, headers: &mut HeaderMap, validators: &CacheValidators) {
+        if let Ok(etag) = HeaderValue::from_str(&validators.etag) {
+            headers.insert(ETAG, etag);
+        }
+
+        if let Ok(last_modified) = HeaderValue::from_str(&validators.last_modified_header()) {
+            headers.insert(LAST_MODIFIED, last_modified);
+        }
+
+        headers.insert(CACHE_CONTROL, self.cache_control.clone());
+    }
+}
+
+/// Validadores HTTP derivados de la versión del catálogo
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CacheValidators {
+    etag: String,
+    last_modified: SystemTime,
+}
+
+impl From<CatalogVersion> for CacheValidators {
+    fn from(version: CatalogVersion) -> Self {
+        let millis = version
+            .modified_at
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        Self {
+            etag: format!("W/\"{}-{:x}\"", version.revision, millis),
+            last_modified: version.modified_at,
+        }
+    }
+}
+
+impl CacheValidators {
+    fn last_modified_header(&self) -> String {
+        httpdate::fmt_http_date(self.last_modified)
+    }
+
+    /// Evalúa las precondiciones de la petición; `If-None-Match` tiene prioridad sobre
+    /// `If-Modified-Since` (RFC 9110, sección 13.2.2)
+    fn is_fresh(&self, headers: &HeaderMap) -> bool {
+        if let Some(if_none_match) = headers.get(IF_NONE_MATCH) {
+            return if_none_match
+                .to_str()
+                .map(|value| self.matches_etag(value))
+                .unwrap_or(false);
+        }
+
+        headers
+            .get(IF_MODIFIED_SINCE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| httpdate::parse_http_date(value).ok())
+            .map(|since| {
+                // Las fechas HTTP tienen resolución de segundos
+                let modified = httpdate::parse_http_date(&self.last_modified_header())
+                    .unwrap_or(self.last_modified);
+                modified <= since
+            })
+            .unwrap_or(false)
+    }
+
+    /// Comparación débil de ETags, según se requiere para `If-None-Match`
+    fn matches_etag(&self, if_none_match: &str) -> bool {
+        let own = self.etag.trim_start_matches("W/");
+
+        if_none_match
+            .split(',')
+            .map(str::trim)
+            .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == own)
+    }
+}
+
+pub async fn http_cache_middleware(
+    State(cache_middleware): State<Arc<HttpCacheMiddleware>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    cache_middleware.handle(req, next).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn validators() -> CacheValidators {
+        CacheValidators::from(CatalogVersion {
+            revision: 7,
+            modified_at: SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_500),
+        })
+    }
+
+    fn headers(name: http::HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn etag_matching_works() {
+        let validators = validators();
+        let etag = validators.etag.clone();
+
+        assert!(validators.is_fresh(&headers(IF_NONE_MATCH, &etag)));
+        assert!(validators.is_fresh(&headers(IF_NONE_MATCH, "*")));
+        assert!(validators.is_fresh(&headers(
+            IF_NONE_MATCH,
+            &format!("\"other\", {}", etag.trim_start_matches("W/"))
+        )));
+        assert!(!validators.is_fresh(&headers(IF_NONE_MATCH, "W/\"6-0\"")));
+        assert!(!validators.is_fresh(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn if_modified_since_works() {
+        let validators = validators();
+        let last_modified = validators.last_modified_header();
+
+        assert!(validators.is_fresh(&headers(IF_MODIFIED_SINCE, &last_modified)));
+        assert!(!validators.is_fresh(&headers(IF_MODIFIED_SINCE, "Sun, 06 Nov 1994 08:49:37 GMT")));
+    }
+
+    #[test]
+    fn if_none_match_takes_precedence() {
+        let validators = validators();
+        let mut headers = headers(IF_NONE_MATCH, "W/\"6-0\"");
+        headers.insert(
+            IF_MODIFIED_SINCE,
+            HeaderValue::from_str(&validators.last_modified_header()).unwrap(),
+        );
+
+        assert!(!validators.is_fresh(&headers));
+    }
+}
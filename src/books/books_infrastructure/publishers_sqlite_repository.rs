@@ -0,0 +1,214 @@
+use std::cmp::max;
+
+use sqlx::{Pool, Sqlite, SqlitePool, query, query_as};
+
+use crate::books::{
+    BookError,
+    books_domain::{PaginatedPublishers, Publisher, PublisherRepository, fold_text},
+    books_infrastructure::db_dtos::DbPublisherWithCount,
+};
+
+use super::books_sqlite_repository::{BookSqliteRepository, like_pattern};
+
+/// Editoriales guardadas en la tabla `publishers`
+#[derive(Clone)]
+pub struct PublisherSqliteRepository {
+    pub pool: Pool<Sqlite>,
+}
+
+impl PublisherSqliteRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl PublisherRepository for PublisherSqliteRepository {
+    async fn find_publishers(
+        &self,
+        name: Option<String>,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedPublishers, BookError> {
+        let page = max(page, 1); // prevenimos un substract-overflow
+        let offset = (page - 1) as i64 * page_size as i64;
+        let limit = page_size as i64;
+        let pattern = like_pattern(&fold_text(&name.unwrap_or_default()));
+
+        let publishers = query_as::<_, DbPublisherWithCount>(
+            r#"
+            SELECT p.id, p.name, COUNT(b.id) AS book_count
+            FROM publishers p
+            LEFT JOIN books b ON b.publisher_id = p.id AND b.deleted_at IS NULL
+            WHERE p.normalized_name LIKE ? ESCAPE '\'
+            GROUP BY p.id
+            ORDER BY p.normalized_name
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(&pattern)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al buscar editoriales: {}", e).into())
+        })?;
+
+        let (total,) = query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM publishers WHERE normalized_name LIKE ? ESCAPE '\\'",
+        )
+        .bind(&pattern)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al contar editoriales: {}", e).into())
+        })?;
+
+        Ok(PaginatedPublishers {
+            publishers: publishers
+                .iter()
+                .map(DbPublisherWithCount::to_domain)
+                .collect::<Result<_, _>>()?,
+            total: total as u64,
+            page,
+            page_size,
+        })
+    }
+
+    async fn find_publisher(&self, id: i64) -> Result<Option<Publisher>, BookError> {
+        let publisher = query_as::<_, DbPublisherWithCount>(
+            r#"
+            SELECT p.id, p.name, COUNT(b.id) AS book_count
+            FROM publishers p
+            LEFT JOIN books b ON b.publisher_id = p.id AND b.deleted_at IS NULL
+            WHERE p.id = ?
+            GROUP BY p.id
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            BookError::DatabaseError(format!("Error al buscar editorial: {}", e).into())
+        })?;
+
+        publisher
+            .as_ref()
+            .map(DbPublisherWithCount::to_domain)
+            .transpose()
+    }
+
+    async fn merge_publishers(&self, source: i64, target: i64) -> Result<(), BookError> {
+        let map_err = |e: sqlx::Error| {
+            BookError::DatabaseError(format!("Error al fusionar editoriales: {}", e).into())
+        };
+
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+
+        query("UPDATE books SET publisher_id = ? WHERE publisher_id = ?")
+            .bind(target)
+            .bind(source)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+        let result = query("DELETE FROM publishers WHERE id = ?")
+            .bind(source)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_err)?;
+
+        if result.rows_affected() == 0 {
+            return Err(BookError::PublisherNotFound);
+        }
+
+        BookSqliteRepository::touch_catalog(&mut tx).await?;
+
+        tx.commit().await.map_err(map_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use uuid::Uuid;
+
+    use crate::books::{
+        books_domain::{
+            Book, BookPublisher, BookRepository, DeletedFilter, Isbn, ReadBookCriteria,
+        },
+        books_infrastructure::books_sqlite_repository::tests::{create_test_book, setup_test_db},
+    };
+
+    #[tokio::test]
+    async fn test_publishers_are_resolved_and_merged() {
+        // Arrange
+        let repo = setup_test_db().await;
+        let publisher_repo = PublisherSqliteRepository::new(repo.pool.clone());
+        let book = create_test_book();
+        repo.create(book.clone())
+            .await
+            .expect("Failed to create book");
+        let other = Book {
+            id: Uuid::new_v4(),
+            publisher: BookPublisher::try_from("test publísher".to_string()).unwrap(),
+            isbn: Isbn::try_from("978-0-306-40615-7".to_string()).unwrap(),
+            ..book.clone()
+        };
+        repo.create(other).await.expect("Failed to create book");
+        let third = Book {
+            id: Uuid::new_v4(),
+            publisher: BookPublisher::try_from("Ed. Test".to_string()).unwrap(),
+            isbn: Isbn::try_from("978-84-376-0495-4".to_string()).unwrap(),
+            ..book.clone()
+        };
+        repo.create(third.clone())
+            .await
+            .expect("Failed to create book");
+
+        // Assert - Same publisher regardless of case and accents
+        let found = publisher_repo
+            .find_publishers(Some("PUBLISHER".to_string()), 1, 10)
+            .await
+            .expect("Failed to list publishers");
+        assert_eq!(found.total, 1);
+        let publisher = found.publishers[0].clone();
+        assert_eq!(publisher.name.as_str(), "Test Publisher");
+        assert_eq!(publisher.book_count, 2);
+
+        // Act - Merge
+        let duplicate = publisher_repo
+            .find_publishers(Some("ed. test".to_string()), 1, 10)
+            .await
+            .expect("Failed to list publishers")
+            .publishers[0]
+            .id;
+        publisher_repo
+            .merge_publishers(duplicate, publisher.id)
+            .await
+            .expect("Failed to merge publishers");
+
+        // Assert
+        assert!(
+            publisher_repo
+                .find_publisher(duplicate)
+                .await
+                .expect("Failed to find publisher")
+                .is_none()
+        );
+        let merged = publisher_repo
+            .find_publisher(publisher.id)
+            .await
+            .expect("Failed to find publisher")
+            .expect("Publisher should exist");
+        assert_eq!(merged.book_count, 3);
+        let found = repo
+            .find(ReadBookCriteria::ById(third.id), DeletedFilter::Exclude)
+            .await
+            .expect("Failed to find book")
+            .expect("Book should exist");
+        assert_eq!(found.publisher.as_str(), "Test Publisher");
+    }
+}
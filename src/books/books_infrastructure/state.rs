@@ -0,0 +1,199 @@
+use std::sync::Arc;
+
+use http::HeaderValue;
+use sqlx::SqlitePool;
+
+use crate::{
+    audit::audit_application::AuditLog,
+    books::{
+        books_application::{
+            browse_shelf_use_case::BrowseShelfUseCase, create_book_use_case::CreateBookUseCase,
+            create_location_use_case::CreateLocationUseCase,
+            create_subject_use_case::CreateSubjectUseCase, delete_book_use_case::DeleteBookUseCase,
+            get_authors_use_case::GetAuthorsUseCase,
+            get_book_availability_use_case::GetBookAvailabilityUseCase,
+            get_book_by_id_use_case::GetBookByIdUseCase,
+            get_book_history_use_case::GetBookHistoryUseCase, get_books_use_case::GetBooksUseCase,
+            get_catalog_version_use_case::GetCatalogVersionUseCase,
+            get_locations_use_case::GetLocationsUseCase,
+            get_publishers_use_case::GetPublishersUseCase,
+            get_stock_movements_use_case::GetStockMovementsUseCase,
+            get_subjects_use_case::GetSubjectsUseCase, get_tags_use_case::GetTagsUseCase,
+            merge_authors_use_case::MergeAuthorsUseCase,
+            merge_publishers_use_case::MergePublishersUseCase,
+            purge_books_use_case::PurgeBooksUseCase, rename_author_use_case::RenameAuthorUseCase,
+            restore_book_use_case::RestoreBookUseCase, revert_book_use_case::RevertBookUseCase,
+            search_books_use_case::SearchBooksUseCase, set_stock_use_case::SetStockUseCase,
+            transfer_stock_use_case::TransferStockUseCase, update_book_use_case::UpdateBookUseCase,
+        },
+        books_domain::{
+            AuthorRepository, BookRepository, LocationRepository, PublisherRepository,
+            TermRepository,
+        },
+    },
+};
+
+use super::{
+    AuthorSqliteRepository, BookSqliteRepository, HttpCacheMiddleware, LocationSqliteRepository,
+    PublisherSqliteRepository, TermSqliteRepository,
+};
+
+/// Repositorios del catálogo de libros
+#[derive(Clone)]
+pub struct BookRepositories {
+    pub books: Arc<dyn BookRepository>,
+    pub authors: Arc<dyn AuthorRepository>,
+    pub publishers: Arc<dyn PublisherRepository>,
+    pub terms: Arc<dyn TermRepository>,
+    pub locations: Arc<dyn LocationRepository>,
+}
+
+impl BookRepositories {
+    /// Repositorios SQLite sobre `pool`
+    pub fn sqlite(pool: SqlitePool) -> Self {
+        Self {
+            books: Arc::new(BookSqliteRepository::new(pool.clone())),
+            authors: Arc::new(AuthorSqliteRepository::new(pool.clone())),
+            publishers: Arc::new(PublisherSqliteRepository::new(pool.clone())),
+            terms: Arc::new(TermSqliteRepository::new(pool.clone())),
+            locations: Arc::new(LocationSqliteRepository::new(pool)),
+        }
+    }
+}
+
+/// Casos de uso de libros compartidos por los controladores
+#[derive(Clone)]
+pub struct BooksState {
+    pub create_book: Arc<CreateBookUseCase>,
+    pub get_books: Arc<GetBooksUseCase>,
+    pub get_book_by_id: Arc<GetBookByIdUseCase>,
+    pub update_book: Arc<UpdateBookUseCase>,
+    pub delete_book: Arc<DeleteBookUseCase>,
+    pub restore_book: Arc<RestoreBookUseCase>,
+    pub purge_books: Arc<PurgeBooksUseCase>,
+    pub book_history: Arc<GetBookHistoryUseCase>,
+    pub revert_book: Arc<RevertBookUseCase>,
+    pub get_authors: Arc<GetAuthorsUseCase>,
+    pub rename_author: Arc<RenameAuthorUseCase>,
+    pub merge_authors: Arc<MergeAuthorsUseCase>,
+    pub get_publishers: Arc<GetPublishersUseCase>,
+    pub merge_publishers: Arc<MergePublishersUseCase>,
+    pub get_subjects: Arc<GetSubjectsUseCase>,
+    pub create_subject: Arc<CreateSubjectUseCase>,
+    pub get_tags: Arc<GetTagsUseCase>,
+    pub search_books: Arc<SearchBooksUseCase>,
+    pub browse_shelf: Arc<BrowseShelfUseCase>,
+    pub get_locations: Arc<GetLocationsUseCase>,
+    pub create_location: Arc<CreateLocationUseCase>,
+    pub book_availability: Arc<GetBookAvailabilityUseCase>,
+    pub set_stock: Arc<SetStockUseCase>,
+    pub transfer_stock: Arc<TransferStockUseCase>,
+    pub stock_movements: Arc<GetStockMovementsUseCase>,
+    pub http_cache: Arc<HttpCacheMiddleware>,
+}
+
+impl BooksState {
+    /// Construye todos los casos de uso sobre los repositorios; los cambios se registran
+    /// en `audit`
+    pub fn new(repos: BookRepositories, audit: Arc<AuditLog>, cache_control: HeaderValue) -> Self {
+        let BookRepositories {
+            books: repo,
+            authors,
+            publishers,
+            terms,
+            locations,
+        } = repos;
+
+        let update_book = Arc::new(UpdateBookUseCase {
+            repo: repo.clone(),
+            locations: locations.clone(),
+            audit: audit.clone(),
+        });
+
+        Self {
+            create_book: Arc::new(CreateBookUseCase {
+                repo: repo.clone(),
+                audit: audit.clone(),
+            }),
+            get_books: Arc::new(GetBooksUseCase { repo: repo.clone() }),
+            get_book_by_id: Arc::new(GetBookByIdUseCase { repo: repo.clone() }),
+            update_book: update_book.clone(),
+            delete_book: Arc::new(DeleteBookUseCase {
+                repo: repo.clone(),
+                audit: audit.clone(),
+            }),
+            restore_book: Arc::new(RestoreBookUseCase {
+                repo: repo.clone(),
+                audit: audit.clone(),
+            }),
+            purge_books: Arc::new(PurgeBooksUseCase {
+                repo: repo.clone(),
+                audit: audit.clone(),
+            }),
+            book_history: Arc::new(GetBookHistoryUseCase { repo: repo.clone() }),
+            revert_book: Arc::new(RevertBookUseCase {
+                repo: repo.clone(),
+                update_book,
+            }),
+            get_authors: Arc::new(GetAuthorsUseCase {
+                repo: authors.clone(),
+            }),
+            rename_author: Arc::new(RenameAuthorUseCase {
+                repo: authors.clone(),
+                audit: audit.clone(),
+            }),
+            merge_authors: Arc::new(MergeAuthorsUseCase {
+                repo: authors.clone(),
+                audit: audit.clone(),
+            }),
+            get_publishers: Arc::new(GetPublishersUseCase {
+                repo: publishers.clone(),
+            }),
+            merge_publishers: Arc::new(MergePublishersUseCase {
+                repo: publishers.clone(),
+                audit: audit.clone(),
+            }),
+            get_subjects: Arc::new(GetSubjectsUseCase {
+                repo: terms.clone(),
+            }),
+            create_subject: Arc::new(CreateSubjectUseCase {
+                repo: terms.clone(),
+                audit: audit.clone(),
+            }),
+            get_tags: Arc::new(GetTagsUseCase {
+                repo: terms.clone(),
+            }),
+            search_books: Arc::new(SearchBooksUseCase { repo: repo.clone() }),
+            browse_shelf: Arc::new(BrowseShelfUseCase { repo: repo.clone() }),
+            get_locations: Arc::new(GetLocationsUseCase {
+                repo: locations.clone(),
+            }),
+            create_location: Arc::new(CreateLocationUseCase {
+                repo: locations.clone(),
+                audit: audit.clone(),
+            }),
+            book_availability: Arc::new(GetBookAvailabilityUseCase {
+                repo: repo.clone(),
+                locations: locations.clone(),
+            }),
+            set_stock: Arc::new(SetStockUseCase {
+                repo: repo.clone(),
+                locations: locations.clone(),
+                audit: audit.clone(),
+            }),
+            transfer_stock: Arc::new(TransferStockUseCase {
+                repo: repo.clone(),
+                locations: locations.clone(),
+                audit,
+            }),
+            stock_movements: Arc::new(GetStockMovementsUseCase {
+                repo: repo.clone(),
+                locations,
+            }),
+            http_cache: Arc::new(HttpCacheMiddleware {
+                use_case: Arc::new(GetCatalogVersionUseCase { repo }),
+                cache_control,
+            }),
+        }
+    }
+}
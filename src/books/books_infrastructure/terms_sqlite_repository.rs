@@ -0,0 +1,231 @@
+use std::cmp::max;
+
+use sqlx::{Pool, Sqlite, SqlitePool, query_as};
+
+use crate::books::{
+    BookError,
+    books_domain::{
+        BookSubject, PaginatedSubjects, PaginatedTags, Subject, TermRepository, fold_text,
+    },
+    books_infrastructure::db_dtos::DbTermWithCount,
+};
+
+use super::books_sqlite_repository::{BookSqliteRepository, SUBJECTS, TAGS, Terms, like_pattern};
+
+/// Temas y etiquetas guardados en sus tablas de términos
+#[derive(Clone)]
+pub struct TermSqliteRepository {
+    pub pool: Pool<Sqlite>,
+}
+
+impl TermSqliteRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Página de temas o etiquetas cuyo nombre contiene `name`, con sus libros vigentes
+    async fn find_terms(
+        &self,
+        terms: &Terms,
+        name: Option<String>,
+        page: u32,
+        page_size: u8,
+    ) -> Result<(Vec<DbTermWithCount>, u64, u32), BookError> {
+        let Terms {
+            table,
+            relation,
+            column,
+        } = terms;
+
+        let page = max(page, 1); // prevenimos un substract-overflow
+        let offset = (page - 1) as i64 * page_size as i64;
+        let limit = page_size as i64;
+        let pattern = like_pattern(&fold_text(&name.unwrap_or_default()));
+
+        let found = query_as::<_, DbTermWithCount>(&format!(
+            r#"
+            SELECT t.id, t.name, COUNT(b.id) AS book_count
+            FROM {table} t
+            LEFT JOIN {relation} r ON r.{column} = t.id
+            LEFT JOIN books b ON r.book_id = b.id AND b.deleted_at IS NULL
+            WHERE t.normalized_name LIKE ? ESCAPE '\'
+            GROUP BY t.id
+            ORDER BY t.normalized_name
+            LIMIT ? OFFSET ?
+            "#
+        ))
+        .bind(&pattern)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| BookError::DatabaseError(format!("Error al buscar {table}: {}", e).into()))?;
+
+        let (total,) = query_as::<_, (i64,)>(&format!(
+            "SELECT COUNT(*) FROM {table} WHERE normalized_name LIKE ? ESCAPE '\\'"
+        ))
+        .bind(&pattern)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| BookError::DatabaseError(format!("Error al contar {table}: {}", e).into()))?;
+
+        Ok((found, total as u64, page))
+    }
+}
+
+#[async_trait::async_trait]
+impl TermRepository for TermSqliteRepository {
+    async fn find_subjects(
+        &self,
+        name: Option<String>,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedSubjects, BookError> {
+        let (subjects, total, page) = self.find_terms(&SUBJECTS, name, page, page_size).await?;
+
+        Ok(PaginatedSubjects {
+            subjects: subjects
+                .iter()
+                .map(DbTermWithCount::to_subject)
+                .collect::<Result<_, _>>()?,
+            total,
+            page,
+            page_size,
+        })
+    }
+
+    async fn create_subject(&self, name: &BookSubject) -> Result<Subject, BookError> {
+        let map_err =
+            |e: sqlx::Error| BookError::DatabaseError(format!("Error al crear tema: {}", e).into());
+
+        let mut tx = self.pool.begin().await.map_err(map_err)?;
+
+        let result = query_as::<_, (i64,)>(
+            "INSERT OR IGNORE INTO subjects (name, normalized_name) VALUES (?, ?) RETURNING id",
+        )
+        .bind(name.as_str())
+        .bind(fold_text(name.as_str()))
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_err)?;
+
+        let Some((id,)) = result else {
+            return Err(BookError::SubjectAlreadyExists(name.to_string()));
+        };
+
+        BookSqliteRepository::touch_catalog(&mut tx).await?;
+        tx.commit().await.map_err(map_err)?;
+
+        Ok(Subject {
+            id,
+            name: name.clone(),
+            book_count: 0,
+        })
+    }
+
+    async fn find_tags(
+        &self,
+        name: Option<String>,
+        page: u32,
+        page_size: u8,
+    ) -> Result<PaginatedTags, BookError> {
+        let (tags, total, page) = self.find_terms(&TAGS, name, page, page_size).await?;
+
+        Ok(PaginatedTags {
+            tags: tags
+                .iter()
+                .map(DbTermWithCount::to_tag)
+                .collect::<Result<_, _>>()?,
+            total,
+            page,
+            page_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::books::{
+        books_domain::{Book, BookRepository, BookTag, DeletedFilter, ReadBookCriteria},
+        books_infrastructure::books_sqlite_repository::tests::{create_test_book, setup_test_db},
+    };
+
+    #[tokio::test]
+    async fn test_subjects_and_tags_are_saved() {
+        // Arrange
+        let repo = setup_test_db().await;
+        let term_repo = TermSqliteRepository::new(repo.pool.clone());
+        let subject = BookSubject::try_from("Ciencia ficción".to_string()).unwrap();
+        term_repo
+            .create_subject(&subject)
+            .await
+            .expect("Failed to create subject");
+        let book = Book {
+            subjects: vec![BookSubject::try_from("CIENCIA FICCION".to_string()).unwrap()],
+            tags: vec![
+                BookTag::try_from("clásico".to_string()).unwrap(),
+                BookTag::try_from("Clasico".to_string()).unwrap(),
+                BookTag::try_from("distopía".to_string()).unwrap(),
+            ],
+            ..create_test_book()
+        };
+
+        // Act
+        repo.create(book.clone())
+            .await
+            .expect("Failed to create book");
+
+        // Assert - Existing subject, tags unified regardless of case and accents
+        let found = repo
+            .find(ReadBookCriteria::ById(book.id), DeletedFilter::Exclude)
+            .await
+            .expect("Failed to find book")
+            .expect("Book should exist");
+        assert_eq!(found.subjects, vec![subject.clone()]);
+        assert_eq!(
+            found.tags.iter().map(BookTag::as_str).collect::<Vec<_>>(),
+            vec!["clásico", "distopía"]
+        );
+        let subjects = term_repo
+            .find_subjects(None, 1, 10)
+            .await
+            .expect("Failed to list subjects");
+        assert_eq!(subjects.subjects[0].book_count, 1);
+
+        // Act - Unknown subject and duplicated subject
+        let unknown = Book {
+            subjects: vec![BookSubject::try_from("Poesía".to_string()).unwrap()],
+            ..found.clone()
+        };
+        let result = repo.update(unknown).await;
+        let duplicate = term_repo
+            .create_subject(&BookSubject::try_from("ciencia  ficción".to_string()).unwrap())
+            .await;
+
+        // Assert
+        assert!(matches!(result, Err(BookError::UnknownSubject(name)) if name == "Poesía"));
+        assert!(matches!(duplicate, Err(BookError::SubjectAlreadyExists(_))));
+
+        // Act - Remove tags
+        repo.update(Book {
+            tags: Vec::new(),
+            ..found
+        })
+        .await
+        .expect("Failed to update book");
+
+        // Assert - Orphan tags are removed, subjects are kept
+        let tags = term_repo
+            .find_tags(None, 1, 10)
+            .await
+            .expect("Failed to list tags");
+        assert_eq!(tags.total, 0);
+        let subjects = term_repo
+            .find_subjects(Some("ficcion".to_string()), 1, 10)
+            .await
+            .expect("Failed to list subjects");
+        assert_eq!(subjects.total, 1);
+    }
+}
@@ -1,37 +1,156 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, fmt};
 
-use validator::ValidationErrors;
+use validator::{ValidationError, ValidationErrors};
 
-use crate::AppErrorKind;
+use crate::{
+    AppErrorKind,
+    i18n::{Language, Localize, default_language, request_language, tr_lang},
+};
 
 #[derive(Debug, Clone, thiserror::Error, derive_error_kind::ErrorKind)]
 #[error_kind(ApiKeyErrorKind)]
 pub enum BookError {
-    #[error("Libro no encontrado")]
     #[error_kind(AppErrorKind, NotFound)]
     NotFound,
 
-    #[error("Cadena de texto vacía")]
+    #[error_kind(AppErrorKind, NotFound)]
+    RevisionNotFound(u32),
+
+    #[error_kind(AppErrorKind, NotFound)]
+    AuthorNotFound,
+
+    #[error_kind(AppErrorKind, NotFound)]
+    PublisherNotFound,
+
+    #[error_kind(AppErrorKind, NotFound)]
+    LocationNotFound,
+
+    #[error_kind(AppErrorKind, InvalidData)]
+    InvalidId,
+
+    #[error_kind(AppErrorKind, InvalidData)]
+    InvalidRevision,
+
+    #[error_kind(AppErrorKind, InvalidData)]
+    InvalidAuthorId,
+
+    #[error_kind(AppErrorKind, InvalidData)]
+    InvalidPublisherId,
+
+    #[error_kind(AppErrorKind, InvalidData)]
+    InvalidLocationId,
+
+    #[error_kind(AppErrorKind, InvalidData)]
+    SelfMerge,
+
     #[error_kind(AppErrorKind, InvalidData)]
     EmptyString,
 
-    #[error("Caracteres inválidos")]
     #[error_kind(AppErrorKind, InvalidData)]
     InvalidCharacters,
 
-    #[error("ISBN inválido")]
     #[error_kind(AppErrorKind, InvalidData)]
     InvalidIsbn,
 
-    #[error("Validación fallida")]
     #[error_kind(AppErrorKind, InvalidData)]
-    Validation(#[from] ValidationErrors),
+    InvalidLanguage,
+
+    #[error_kind(AppErrorKind, InvalidData)]
+    InvalidClassification,
+
+    #[error_kind(AppErrorKind, InvalidData)]
+    UnknownSubject(String),
 
-    #[error("Libro con el ISBN: {0} ya existe")]
     #[error_kind(AppErrorKind, InvalidData)]
+    SameLocation,
+
+    #[error_kind(AppErrorKind, InvalidData)]
+    StockExceedsQuantity,
+
+    #[error_kind(AppErrorKind, InvalidData)]
+    InsufficientStock,
+
+    #[error_kind(AppErrorKind, InvalidData)]
+    Validation(#[from] ValidationErrors),
+
+    #[error_kind(AppErrorKind, Duplicate)]
     AlreadyExists(String),
 
-    #[error("Error de base de datos")]
+    #[error_kind(AppErrorKind, Duplicate)]
+    AuthorAlreadyExists(String),
+
+    #[error_kind(AppErrorKind, Duplicate)]
+    SubjectAlreadyExists(String),
+
+    #[error_kind(AppErrorKind, Duplicate)]
+    LocationAlreadyExists(String),
+
+    #[error_kind(AppErrorKind, Forbidden)]
+    LocationForbidden,
+
     #[error_kind(AppErrorKind, Infrastructure)]
     DatabaseError(Cow<'static, str>),
 }
+
+impl BookError {
+    /// Código estable que identifica el error, independiente del mensaje
+    pub fn code(&self) -> &'static str {
+        match self {
+            BookError::NotFound => "book.not_found",
+            BookError::RevisionNotFound(_) => "book.revision_not_found",
+            BookError::AuthorNotFound => "book.author_not_found",
+            BookError::PublisherNotFound => "book.publisher_not_found",
+            BookError::LocationNotFound => "book.location_not_found",
+            BookError::InvalidId => "book.invalid_id",
+            BookError::InvalidRevision => "book.invalid_revision",
+            BookError::InvalidAuthorId => "book.invalid_author_id",
+            BookError::InvalidPublisherId => "book.invalid_publisher_id",
+            BookError::InvalidLocationId => "book.invalid_location_id",
+            BookError::SelfMerge => "book.self_merge",
+            BookError::EmptyString => "book.empty_string",
+            BookError::InvalidCharacters => "book.invalid_characters",
+            BookError::InvalidIsbn => "book.invalid_isbn",
+            BookError::InvalidLanguage => "book.invalid_language",
+            BookError::InvalidClassification => "book.invalid_classification",
+            BookError::UnknownSubject(_) => "book.unknown_subject",
+            BookError::SameLocation => "book.same_location",
+            BookError::StockExceedsQuantity => "book.stock_exceeds_quantity",
+            BookError::InsufficientStock => "book.insufficient_stock",
+            BookError::Validation(_) => "book.validation",
+            BookError::AlreadyExists(_) => "book.already_exists",
+            BookError::AuthorAlreadyExists(_) => "book.author_already_exists",
+            BookError::SubjectAlreadyExists(_) => "book.subject_already_exists",
+            BookError::LocationAlreadyExists(_) => "book.location_already_exists",
+            BookError::LocationForbidden => "book.location_forbidden",
+            BookError::DatabaseError(_) => "book.database_error",
+        }
+    }
+
+    /// Convierte el error en un error de validación para asociarlo a un campo
+    pub fn to_validation_error(&self) -> ValidationError {
+        let code = self.code().trim_start_matches("book.");
+        ValidationError::new(code).with_message(self.localize(request_language()).into())
+    }
+}
+
+impl Localize for BookError {
+    fn localize(&self, lang: Language) -> String {
+        match self {
+            BookError::AlreadyExists(isbn) => tr_lang!(lang, self.code(), isbn = isbn),
+            BookError::AuthorAlreadyExists(name)
+            | BookError::SubjectAlreadyExists(name)
+            | BookError::LocationAlreadyExists(name)
+            | BookError::UnknownSubject(name) => tr_lang!(lang, self.code(), name = name),
+            BookError::RevisionNotFound(revision) => {
+                tr_lang!(lang, self.code(), revision = revision)
+            }
+            _ => tr_lang!(lang, self.code()),
+        }
+    }
+}
+
+impl fmt::Display for BookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.localize(default_language()))
+    }
+}
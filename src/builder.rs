@@ -0,0 +1,265 @@
+use std::{convert::Infallible, sync::Arc, time::Duration};
+
+use axum::{Router, extract::Request, response::IntoResponse, routing::Route};
+use http::HeaderValue;
+use sqlx::SqlitePool;
+use tower::{Layer, Service};
+
+use crate::{
+    AppError,
+    api_keys::{
+        api_keys_application::{API_KEY_CACHE_CAPACITY, ApiKeyCache},
+        api_keys_domain::ApiKeyRepository,
+        api_keys_infrastructure::ApiKeysState,
+    },
+    audit::{
+        audit_domain::AuditRepository,
+        audit_infrastructure::{AuditState, MemoryAuditRepository},
+    },
+    books::books_infrastructure::{BookRepositories, BooksState, DEFAULT_CACHE_CONTROL},
+    config::Config,
+    health::ReadinessCheck,
+    rate_limit::{RateLimitConfig, RateLimits},
+    server,
+    state::AppState,
+};
+
+/// Punto de entrada para integrar la API de la biblioteca en otro servicio axum
+///
+/// ```no_run
+/// # async fn example(pool: sqlx::SqlitePool) -> Result<(), library_api::AppError> {
+/// use library_api::LibraryApi;
+///
+/// let library = LibraryApi::builder()
+///     .pool(pool)
+///     .prefix("/library")
+///     .create_tables(true)
+///     .build()
+///     .await?;
+///
+/// let app = axum::Router::new().merge(library);
+/// # Ok(())
+/// # }
+/// ```
+pub struct LibraryApi;
+
+impl LibraryApi {
+    pub fn builder() -> LibraryApiBuilder {
+        LibraryApiBuilder::default()
+    }
+}
+
+/// Origen de los datos de la API
+enum Storage {
+    Pool(SqlitePool),
+    Repositories {
+        books: BookRepositories,
+        api_keys: Arc<dyn ApiKeyRepository>,
+    },
+    State(Box<AppState>),
+}
+
+type RouterLayer = Box<dyn FnOnce(Router) -> Router + Send>;
+
+/// Configura el `Router` de la API. Construir el router no tiene efectos secundarios
+/// salvo los indicados explícitamente (por ejemplo [`LibraryApiBuilder::create_tables`]).
+pub struct LibraryApiBuilder {
+    storage: Option<Storage>,
+    audit_repository: Option<Arc<dyn AuditRepository>>,
+    cache_control: HeaderValue,
+    prefix: String,
+    create_tables: bool,
+    readiness: Option<Arc<dyn ReadinessCheck>>,
+    rate_limits: Option<RateLimitConfig>,
+    api_key_cache_ttl: Option<Duration>,
+    layers: Vec<RouterLayer>,
+}
+
+impl Default for LibraryApiBuilder {
+    fn default() -> Self {
+        Self {
+            storage: None,
+            audit_repository: None,
+            cache_control: HeaderValue::from_static(DEFAULT_CACHE_CONTROL),
+            prefix: String::new(),
+            create_tables: false,
+            readiness: None,
+            rate_limits: None,
+            api_key_cache_ttl: None,
+            layers: Vec::new(),
+        }
+    }
+}
+
+impl LibraryApiBuilder {
+    /// Usa repositorios SQLite sobre `pool`
+    pub fn pool(mut self, pool: SqlitePool) -> Self {
+        self.storage = Some(Storage::Pool(pool));
+        self
+    }
+
+    /// Usa repositorios propios, por ejemplo otra base de datos o dobles de prueba
+    pub fn repositories(
+        mut self,
+        books: BookRepositories,
+        api_keys: Arc<dyn ApiKeyRepository>,
+    ) -> Self {
+        self.storage = Some(Storage::Repositories { books, api_keys });
+        self
+    }
+
+    /// Repositorio de la auditoría para [`LibraryApiBuilder::repositories`]; por defecto
+    /// las entradas se guardan en memoria
+    pub fn audit_repository(mut self, audit: Arc<dyn AuditRepository>) -> Self {
+        self.audit_repository = Some(audit);
+        self
+    }
+
+    /// Usa un estado ya construido; ignora [`LibraryApiBuilder::cache_control`]
+    pub fn state(mut self, state: AppState) -> Self {
+        self.storage = Some(Storage::State(Box::new(state)));
+        self
+    }
+
+    /// Toma de la configuración los valores que afectan a las rutas
+    /// (`HTTP_CACHE_CONTROL`, los límites de peticiones y la caché de ApiKeys)
+    pub fn config(self, config: &Config) -> Self {
+        self.cache_control(config.cache_control_header())
+            .rate_limits(config.rate_limits())
+            .api_key_cache_ttl(config.api_key_cache_ttl())
+    }
+
+    /// Valor de `Cache-Control` para las rutas públicas del catálogo
+    pub fn cache_control(mut self, cache_control: HeaderValue) -> Self {
+        self.cache_control = cache_control;
+        self
+    }
+
+    /// Ruta bajo la cual se sirven todas las rutas de la API, por ejemplo `/library`
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Crea las tablas si no existen al construir el router. Solo aplica con [`LibraryApiBuilder::pool`].
+    pub fn create_tables(mut self, create_tables: bool) -> Self {
+        self.create_tables = create_tables;
+        self
+    }
+
+    /// Comprobaciones de `/health/ready`. Con [`LibraryApiBuilder::pool`] se comprueba
+    /// la base de datos; con repositorios propios, por defecto no hay comprobaciones.
+    pub fn readiness(mut self, readiness: Arc<dyn ReadinessCheck>) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
+    /// Límites de peticiones por IP y por ApiKey; sin indicarlos se usan los valores por defecto
+    pub fn rate_limits(mut self, rate_limits: RateLimitConfig) -> Self {
+        self.rate_limits = Some(rate_limits);
+        self
+    }
+
+    /// Tiempo que se recuerda una ApiKey verificada; cero desactiva la caché
+    pub fn api_key_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.api_key_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Agrega una capa que envuelve todas las rutas de la API, incluyendo la documentación
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route> + Clone + Send + Sync + 'static,
+        L::Service: Service<Request> + Clone + Send + Sync + 'static,
+        <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        self.layers
+            .push(Box::new(move |router| router.layer(layer)));
+        self
+    }
+
+    /// Construye el router de la API, listo para anidarse o servirse
+    pub async fn build(self) -> Result<Router, AppError> {
+        let prefix = self.prefix.trim_end_matches('/');
+        if !prefix.is_empty() && !prefix.starts_with('/') {
+            return Err(AppError::InvalidPrefix(prefix.to_string()));
+        }
+
+        let state = match self.storage {
+            Some(Storage::Pool(pool)) => {
+                if self.create_tables {
+                    AppState::create_tables(&pool).await?;
+                }
+                AppState::sqlite(pool, self.cache_control)
+            }
+            Some(Storage::Repositories { books, api_keys }) => {
+                let audit = AuditState::new(
+                    self.audit_repository
+                        .unwrap_or_else(|| Arc::new(MemoryAuditRepository::default())),
+                );
+
+                AppState::new(
+                    BooksState::new(books, audit.log.clone(), self.cache_control),
+                    ApiKeysState::new(api_keys, audit.log.clone()),
+                    audit,
+                )
+            }
+            Some(Storage::State(state)) => *state,
+            None => {
+                return Err(AppError::MissingStorage);
+            }
+        };
+
+        let state = match self.readiness {
+            Some(readiness) => state.with_readiness(readiness),
+            None => state,
+        };
+
+        let state = match self.rate_limits {
+            Some(rate_limits) => state.with_rate_limits(RateLimits::new(&rate_limits)),
+            None => state,
+        };
+
+        let state = match self.api_key_cache_ttl {
+            Some(ttl) => state.with_api_key_cache(ApiKeyCache::new(ttl, API_KEY_CACHE_CAPACITY)),
+            None => state,
+        };
+
+        let router = self
+            .layers
+            .into_iter()
+            .fold(server::router(state, prefix), |router, layer| layer(router));
+
+        if prefix.is_empty() {
+            Ok(router)
+        } else {
+            Ok(Router::new().nest(prefix, router))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn builder_requires_storage() {
+        let result = LibraryApi::builder().build().await;
+
+        assert!(matches!(result, Err(AppError::MissingStorage)));
+    }
+
+    #[tokio::test]
+    async fn builder_rejects_relative_prefix() {
+        let pool = SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+        let result = LibraryApi::builder()
+            .pool(pool)
+            .prefix("library")
+            .build()
+            .await;
+
+        assert!(matches!(result, Err(AppError::InvalidPrefix(prefix)) if prefix == "library"));
+    }
+}
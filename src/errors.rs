@@ -1,39 +1,156 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::BTreeMap, fmt};
 
-use axum::{Json, response::IntoResponse};
-use http::StatusCode;
-use serde_json::json;
+use axum::{
+    Json,
+    response::{IntoResponse, Response},
+};
+use http::{StatusCode, header::CONTENT_TYPE};
+use parse_display::Display;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+use validator::{ValidationErrors, ValidationErrorsKind};
 
-use crate::{api_keys::ApiKeyError, books::BookError};
+use crate::{
+    api_keys::ApiKeyError,
+    audit::AuditError,
+    books::BookError,
+    i18n::{Language, Localize, default_language, request_language, tr_lang},
+    request_id::current_request_id,
+};
+
+/// Tipo de contenido para respuestas de error (RFC 7807)
+pub const PROBLEM_JSON: &str = "application/problem+json";
 
 #[derive(Debug, Clone, thiserror::Error, derive_error_kind::ErrorKind)]
 #[error_kind(AppErrorKind)]
 pub enum AppError {
-    #[error("Error: {0}")]
     #[error_kind(AppErrorKind, Unknown)]
     Custom(Cow<'static, str>),
 
-    #[error("Error con ApiKey: {0}")]
     #[error_kind(transparent)]
     ApiKey(#[from] ApiKeyError),
 
-    #[error("Error al manejar libro: {0}")]
     #[error_kind(transparent)]
     Book(#[from] BookError),
 
-    #[error("Error al cargar configuración: {0}")]
+    #[error_kind(transparent)]
+    Audit(#[from] AuditError),
+
     #[error_kind(AppErrorKind, Infrastructure)]
     ConfigLoad(String),
 
-    #[error("Error al cargar variable de entorno: {0}")]
     #[error_kind(AppErrorKind, Infrastructure)]
     EnvVarLoad(String),
+
+    #[error_kind(AppErrorKind, Infrastructure)]
+    InvalidConfig { key: String, detail: String },
+
+    #[error_kind(AppErrorKind, Infrastructure)]
+    Metrics(String),
+
+    #[error_kind(AppErrorKind, RateLimited)]
+    RateLimited { retry_after: u64 },
+
+    #[error_kind(AppErrorKind, NotFound)]
+    RouteNotFound,
+
+    #[error_kind(AppErrorKind, Infrastructure)]
+    UnsupportedLanguage(String),
+
+    #[error_kind(AppErrorKind, Infrastructure)]
+    UnsupportedLogFormat(String),
+
+    #[error_kind(AppErrorKind, Application)]
+    InvalidPrefix(String),
+
+    #[error_kind(AppErrorKind, Application)]
+    MissingStorage,
+
+    #[error_kind(AppErrorKind, Infrastructure)]
+    SchemaVersion(String),
+}
+
+impl AppError {
+    /// Código estable que identifica el error, independiente del mensaje
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Custom(_) => "app.custom",
+            AppError::ApiKey(e) => e.code(),
+            AppError::Book(e) => e.code(),
+            AppError::Audit(e) => e.code(),
+            AppError::ConfigLoad(_) => "app.config_load",
+            AppError::EnvVarLoad(_) => "app.env_var_load",
+            AppError::InvalidConfig { .. } => "app.invalid_config",
+            AppError::Metrics(_) => "app.metrics",
+            AppError::RateLimited { .. } => "app.rate_limited",
+            AppError::RouteNotFound => "app.route_not_found",
+            AppError::UnsupportedLanguage(_) => "app.unsupported_language",
+            AppError::UnsupportedLogFormat(_) => "app.unsupported_log_format",
+            AppError::InvalidPrefix(_) => "app.invalid_prefix",
+            AppError::MissingStorage => "app.missing_storage",
+            AppError::SchemaVersion(_) => "app.schema_version",
+        }
+    }
+
+    /// Detalle por campo de los errores de validación
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        match self {
+            AppError::Book(BookError::Validation(errors)) => FieldError::from_validation(errors),
+            _ => Vec::new(),
+        }
+    }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+impl Localize for AppError {
+    fn localize(&self, lang: Language) -> String {
+        match self {
+            AppError::Custom(detail) => tr_lang!(lang, "app.custom", detail = detail),
+            AppError::ApiKey(e) => tr_lang!(lang, "app.api_key", source = e.localize(lang)),
+            AppError::Book(e) => tr_lang!(lang, "app.book", source = e.localize(lang)),
+            AppError::Audit(e) => tr_lang!(lang, "app.audit", source = e.localize(lang)),
+            AppError::ConfigLoad(detail) => tr_lang!(lang, "app.config_load", detail = detail),
+            AppError::EnvVarLoad(detail) => tr_lang!(lang, "app.env_var_load", detail = detail),
+            AppError::InvalidConfig { key, detail } => {
+                tr_lang!(lang, "app.invalid_config", key = key, detail = detail)
+            }
+            AppError::Metrics(detail) => tr_lang!(lang, "app.metrics", detail = detail),
+            AppError::RateLimited { retry_after } => {
+                tr_lang!(lang, "app.rate_limited", seconds = retry_after)
+            }
+            AppError::RouteNotFound => tr_lang!(lang, "app.route_not_found"),
+            AppError::UnsupportedLanguage(language) => {
+                tr_lang!(lang, "app.unsupported_language", language = language)
+            }
+            AppError::UnsupportedLogFormat(format) => {
+                tr_lang!(lang, "app.unsupported_log_format", format = format)
+            }
+            AppError::InvalidPrefix(prefix) => {
+                tr_lang!(lang, "app.invalid_prefix", prefix = prefix)
+            }
+            AppError::MissingStorage => tr_lang!(lang, "app.missing_storage"),
+            AppError::SchemaVersion(detail) => {
+                tr_lang!(lang, "app.schema_version", detail = detail)
+            }
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.localize(default_language()))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display, Serialize, Deserialize, ToSchema)]
+#[display(style = "snake_case")]
+#[serde(rename_all = "snake_case")]
 pub enum AppErrorKind {
-    /// Se necesita autenticación para realizar la acción
-    Auhtentication,
+    /// Se necesita autenticación para realizar la acción, o las credenciales no son válidas
+    Authentication,
+
+    /// Las credenciales no permiten realizar la acción sobre el recurso
+    Forbidden,
 
     /// El recurso no existe
     NotFound,
@@ -44,6 +161,9 @@ pub enum AppErrorKind {
     /// Los datos ingresados no son válidos
     InvalidData,
 
+    /// Se superó el límite de peticiones
+    RateLimited,
+
     /// Fallo en capa de infraestructura
     Infrastructure,
 
@@ -57,23 +177,378 @@ pub enum AppErrorKind {
 impl From<AppErrorKind> for StatusCode {
     fn from(value: AppErrorKind) -> Self {
         match value {
-            AppErrorKind::Auhtentication => StatusCode::UNAUTHORIZED,
+            AppErrorKind::Authentication => StatusCode::UNAUTHORIZED,
+            AppErrorKind::Forbidden => StatusCode::FORBIDDEN,
             AppErrorKind::NotFound => StatusCode::NOT_FOUND,
             AppErrorKind::Duplicate => StatusCode::CONFLICT,
             AppErrorKind::InvalidData => StatusCode::BAD_REQUEST,
+            AppErrorKind::RateLimited => StatusCode::TOO_MANY_REQUESTS,
             AppErrorKind::Infrastructure | AppErrorKind::Application | AppErrorKind::Unknown => {
-                StatusCode::SERVICE_UNAVAILABLE
+                StatusCode::INTERNAL_SERVER_ERROR
             }
         }
     }
 }
 
 impl IntoResponse for AppError {
-    fn into_response(self) -> axum::response::Response {
-        let status = StatusCode::from(self.kind());
-        let body = Json(json!({
-            "message": self.to_string()
-        }));
-        (status, body).into_response()
+    fn into_response(self) -> Response {
+        let kind = self.kind();
+        let detail = self.localize(request_language());
+        ProblemDetails::new(StatusCode::from(kind), kind, self.code(), detail)
+            .with_errors(self.field_errors())
+            .into_response()
+    }
+}
+
+/// Cuerpo estándar de las respuestas de error, basado en RFC 7807 (problem+json)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    #[schema(example = "urn:library-api:error:book.not_found")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub kind: AppErrorKind,
+    #[schema(example = "book.not_found")]
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+impl ProblemDetails {
+    pub fn new(
+        status: StatusCode,
+        kind: AppErrorKind,
+        code: &str,
+        detail: impl Into<String>,
+    ) -> Self {
+        Self {
+            problem_type: format!("urn:library-api:error:{code}"),
+            title: status.canonical_reason().unwrap_or_default().to_string(),
+            status: status.as_u16(),
+            detail: detail.into(),
+            kind,
+            code: code.to_string(),
+            request_id: current_request_id(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn with_errors(mut self, errors: Vec<FieldError>) -> Self {
+        self.errors = errors;
+        self
+    }
+}
+
+impl IntoResponse for ProblemDetails {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+        (status, [(CONTENT_TYPE, PROBLEM_JSON)], Json(self)).into_response()
+    }
+}
+
+/// Error de validación asociado a un campo específico
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, Value>,
+}
+
+impl FieldError {
+    /// Aplana los errores de validación (incluyendo estructuras y listas anidadas),
+    /// ordenados por el nombre del campo
+    pub fn from_validation(errors: &ValidationErrors) -> Vec<FieldError> {
+        let mut result = Vec::new();
+        Self::collect(errors, "", &mut result);
+        result.sort_by(|a, b| a.field.cmp(&b.field));
+
+        result
+    }
+
+    fn collect(errors: &ValidationErrors, prefix: &str, result: &mut Vec<FieldError>) {
+        for (field, kind) in errors.errors() {
+            let path = if prefix.is_empty() {
+                field.to_string()
+            } else {
+                format!("{prefix}.{field}")
+            };
+
+            match kind {
+                ValidationErrorsKind::Field(field_errors) => {
+                    result.extend(field_errors.iter().map(|error| {
+                        FieldError {
+                            field: path.clone(),
+                            code: error.code.to_string(),
+                            message: error
+                                .message
+                                .as_ref()
+                                .map(|m| m.to_string())
+                                .or_else(|| default_validation_message(&error.code)),
+                            params: error
+                                .params
+                                .iter()
+                                .map(|(k, v)| (k.to_string(), v.clone()))
+                                .collect(),
+                        }
+                    }))
+                }
+                ValidationErrorsKind::Struct(nested) => Self::collect(nested, &path, result),
+                ValidationErrorsKind::List(items) => {
+                    for (index, nested) in items {
+                        Self::collect(nested, &format!("{path}[{index}]"), result);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Mensaje traducido para los códigos de validación sin mensaje propio
+fn default_validation_message(code: &str) -> Option<String> {
+    let key = format!("validation.{code}");
+    let message = tr_lang!(request_language(), &key);
+
+    (message != key).then_some(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use validator::ValidationError;
+
+    use super::*;
+
+    #[test]
+    fn field_errors_are_flattened() {
+        let mut nested = ValidationErrors::new();
+        nested.add("name", ValidationError::new("length"));
+
+        let mut errors = ValidationErrors::new();
+        errors.add("year", ValidationError::new("range"));
+        errors.add("authors", ValidationError::new("length"));
+        errors
+            .errors_mut()
+            .insert("publisher", ValidationErrorsKind::Struct(Box::new(nested)));
+
+        let fields: Vec<(String, String)> = FieldError::from_validation(&errors)
+            .into_iter()
+            .map(|e| (e.field, e.code))
+            .collect();
+
+        assert_eq!(
+            fields,
+            vec![
+                ("authors".to_string(), "length".to_string()),
+                ("publisher.name".to_string(), "length".to_string()),
+                ("year".to_string(), "range".to_string()),
+            ]
+        );
+    }
+
+    fn status(error: impl Into<AppError>) -> StatusCode {
+        StatusCode::from(error.into().kind())
+    }
+
+    #[test]
+    fn book_errors_status_mapping_works() {
+        let cases = [
+            (BookError::NotFound, StatusCode::NOT_FOUND),
+            (BookError::RevisionNotFound(3), StatusCode::NOT_FOUND),
+            (BookError::AuthorNotFound, StatusCode::NOT_FOUND),
+            (BookError::PublisherNotFound, StatusCode::NOT_FOUND),
+            (BookError::LocationNotFound, StatusCode::NOT_FOUND),
+            (BookError::InvalidId, StatusCode::BAD_REQUEST),
+            (BookError::InvalidRevision, StatusCode::BAD_REQUEST),
+            (BookError::InvalidAuthorId, StatusCode::BAD_REQUEST),
+            (BookError::InvalidPublisherId, StatusCode::BAD_REQUEST),
+            (BookError::InvalidLocationId, StatusCode::BAD_REQUEST),
+            (BookError::SelfMerge, StatusCode::BAD_REQUEST),
+            (BookError::EmptyString, StatusCode::BAD_REQUEST),
+            (BookError::InvalidCharacters, StatusCode::BAD_REQUEST),
+            (BookError::InvalidIsbn, StatusCode::BAD_REQUEST),
+            (BookError::InvalidLanguage, StatusCode::BAD_REQUEST),
+            (BookError::InvalidClassification, StatusCode::BAD_REQUEST),
+            (
+                BookError::UnknownSubject("Poesía".to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+            (BookError::SameLocation, StatusCode::BAD_REQUEST),
+            (BookError::StockExceedsQuantity, StatusCode::BAD_REQUEST),
+            (BookError::InsufficientStock, StatusCode::BAD_REQUEST),
+            (
+                BookError::Validation(ValidationErrors::new()),
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                BookError::AlreadyExists("9780306406157".to_string()),
+                StatusCode::CONFLICT,
+            ),
+            (
+                BookError::AuthorAlreadyExists("Julio Cortázar".to_string()),
+                StatusCode::CONFLICT,
+            ),
+            (
+                BookError::SubjectAlreadyExists("Poesía".to_string()),
+                StatusCode::CONFLICT,
+            ),
+            (
+                BookError::LocationAlreadyExists("Sucursal Centro".to_string()),
+                StatusCode::CONFLICT,
+            ),
+            (BookError::LocationForbidden, StatusCode::FORBIDDEN),
+            (
+                BookError::DatabaseError("db".into()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+
+        for (error, expected) in cases {
+            let code = error.code();
+            assert_eq!(status(error), expected, "{code}");
+        }
+    }
+
+    #[test]
+    fn api_key_errors_status_mapping_works() {
+        let cases = [
+            (
+                ApiKeyError::Encryption("encrypt".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                ApiKeyError::Configuration(prefixed_api_key::BuilderError::MissingPrefix),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                ApiKeyError::Generation("rng".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (ApiKeyError::NotFound, StatusCode::UNAUTHORIZED),
+            (
+                ApiKeyError::BadEncryption("hash".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                ApiKeyError::HashVerification("bcrypt".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (ApiKeyError::Invalid, StatusCode::UNAUTHORIZED),
+            (
+                ApiKeyError::DatabaseError("db".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+
+        for (error, expected) in cases {
+            let code = error.code();
+            assert_eq!(status(error), expected, "{code}");
+        }
+    }
+
+    #[test]
+    fn audit_errors_status_mapping_works() {
+        let cases = [
+            (
+                AuditError::DatabaseError("db".to_string()),
+                "audit.database_error",
+            ),
+            (
+                AuditError::Serialization("json".to_string()),
+                "audit.serialization",
+            ),
+        ];
+
+        for (error, code) in cases {
+            assert_eq!(error.code(), code);
+
+            // AppError::Audit conserva el tipo y el código del error de auditoría
+            let error = AppError::from(error);
+            assert_eq!(error.code(), code);
+            assert_eq!(error.kind(), AppErrorKind::Infrastructure, "{code}");
+            assert_eq!(status(error), StatusCode::INTERNAL_SERVER_ERROR, "{code}");
+        }
+    }
+
+    #[test]
+    fn app_errors_status_mapping_works() {
+        let cases = [
+            (
+                AppError::Custom("custom".into()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                AppError::ConfigLoad("config".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                AppError::EnvVarLoad("env".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                AppError::InvalidConfig {
+                    key: "API_PORT".to_string(),
+                    detail: "invalid digit".to_string(),
+                },
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                AppError::Metrics("encoder".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                AppError::RateLimited { retry_after: 30 },
+                StatusCode::TOO_MANY_REQUESTS,
+            ),
+            (AppError::RouteNotFound, StatusCode::NOT_FOUND),
+            (
+                AppError::UnsupportedLanguage("fr".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                AppError::UnsupportedLogFormat("xml".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                AppError::InvalidPrefix("library".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (AppError::MissingStorage, StatusCode::INTERNAL_SERVER_ERROR),
+            (
+                AppError::SchemaVersion("disk I/O error".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+
+        for (error, expected) in cases {
+            let code = error.code();
+            assert_eq!(status(error), expected, "{code}");
+        }
+    }
+
+    #[test]
+    fn problem_details_serialization_works() {
+        let error = AppError::from(BookError::NotFound);
+        let problem = ProblemDetails::new(
+            StatusCode::from(error.kind()),
+            error.kind(),
+            error.code(),
+            error.to_string(),
+        );
+
+        let value = serde_json::to_value(&problem).expect("Error al serializar");
+
+        assert_eq!(value["type"], "urn:library-api:error:book.not_found");
+        assert_eq!(value["status"], 404);
+        assert_eq!(value["kind"], "not_found");
+        assert_eq!(value["code"], "book.not_found");
+        assert!(value.get("errors").is_none());
+        assert!(value.get("request_id").is_none());
     }
 }
@@ -0,0 +1,232 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    Json,
+    extract::State,
+    response::{IntoResponse, Response},
+};
+use http::{HeaderValue, StatusCode, header::CACHE_CONTROL};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    i18n::{request_language, tr_lang},
+    openapi::{HEALTH_TAG, INFO_TAG},
+    state::{AppState, SCHEMA_VERSION},
+};
+
+/// Tiempo máximo que pueden tardar las comprobaciones de disponibilidad
+pub const READINESS_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Commit con el que se compiló la API, ver `build.rs`
+pub const GIT_HASH: &str = env!("LIBRARY_API_GIT_HASH");
+
+/// Features de cargo activadas al compilar
+pub const FEATURES: &[&str] = &[
+    #[cfg(feature = "cli")]
+    "cli",
+    #[cfg(feature = "integration-tests")]
+    "integration-tests",
+];
+
+/// Resultado de una comprobación de disponibilidad
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Check {
+    pub name: String,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl Check {
+    pub fn ok(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: true,
+            detail: None,
+        }
+    }
+
+    pub fn failed(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: false,
+            detail: Some(detail.into()),
+        }
+    }
+}
+
+/// Comprueba que las dependencias de la API pueden atender peticiones
+#[async_trait::async_trait]
+pub trait ReadinessCheck: Send + Sync {
+    async fn check(&self) -> Vec<Check>;
+}
+
+/// Para repositorios propios, que no tienen dependencias que comprobar
+pub struct NoReadinessChecks;
+
+#[async_trait::async_trait]
+impl ReadinessCheck for NoReadinessChecks {
+    async fn check(&self) -> Vec<Check> {
+        Vec::new()
+    }
+}
+
+/// Comprueba que la base de datos responde y que su esquema está al día
+pub struct SqliteReadiness {
+    pool: SqlitePool,
+}
+
+impl SqliteReadiness {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ReadinessCheck for SqliteReadiness {
+    async fn check(&self) -> Vec<Check> {
+        let version = sqlx::query_scalar::<_, i64>("PRAGMA user_version")
+            .fetch_one(&self.pool)
+            .await;
+
+        match version {
+            Ok(version) if version >= SCHEMA_VERSION => {
+                vec![Check::ok("database"), Check::ok("schema")]
+            }
+            Ok(version) => vec![
+                Check::ok("database"),
+                Check::failed(
+                    "schema",
+                    tr_lang!(
+                        request_language(),
+                        "health.schema_outdated",
+                        found = version,
+                        expected = SCHEMA_VERSION
+                    ),
+                ),
+            ],
+            Err(e) => vec![Check::failed("database", e.to_string())],
+        }
+    }
+}
+
+/// Dependencias de los endpoints de salud
+#[derive(Clone)]
+pub struct HealthState {
+    pub readiness: Arc<dyn ReadinessCheck>,
+    pub started_at: Instant,
+}
+
+impl HealthState {
+    pub fn new(readiness: Arc<dyn ReadinessCheck>) -> Self {
+        Self {
+            readiness,
+            started_at: Instant::now(),
+        }
+    }
+}
+
+/// Rutas de salud e información de compilación
+pub(crate) fn health_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(live))
+        .routes(routes!(ready))
+        .routes(routes!(info))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Liveness {
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Readiness {
+    pub ready: bool,
+    pub checks: Vec<Check>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BuildInfo {
+    pub version: String,
+    pub git_hash: String,
+    pub features: Vec<String>,
+    pub uptime_secs: u64,
+}
+
+/// El proceso está en ejecución
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = HEALTH_TAG,
+    responses((status = 200, description = "La API está en ejecución", body = Liveness))
+)]
+pub async fn live() -> Response {
+    no_store(Json(Liveness {
+        status: "ok".to_string(),
+    }))
+}
+
+/// La API puede atender peticiones: la base de datos responde y su esquema está al día
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = HEALTH_TAG,
+    responses(
+        (status = 200, description = "La API está lista", body = Readiness),
+        (status = 503, description = "Alguna comprobación falló", body = Readiness),
+    )
+)]
+pub async fn ready(State(health): State<HealthState>) -> Response {
+    let checks = tokio::time::timeout(READINESS_TIMEOUT, health.readiness.check())
+        .await
+        .unwrap_or_else(|_| {
+            vec![Check::failed(
+                "timeout",
+                tr_lang!(
+                    request_language(),
+                    "health.timeout",
+                    seconds = READINESS_TIMEOUT.as_secs()
+                ),
+            )]
+        });
+
+    let ready = checks.iter().all(|check| check.ok);
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    no_store((status, Json(Readiness { ready, checks })))
+}
+
+/// Versión, commit y features con los que se compiló la API, y tiempo en ejecución
+#[utoipa::path(
+    get,
+    path = "/info",
+    tag = INFO_TAG,
+    responses((status = 200, description = "Información de compilación", body = BuildInfo))
+)]
+pub async fn info(State(health): State<HealthState>) -> Response {
+    no_store(Json(BuildInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_hash: GIT_HASH.to_string(),
+        features: FEATURES.iter().map(|feature| feature.to_string()).collect(),
+        uptime_secs: health.started_at.elapsed().as_secs(),
+    }))
+}
+
+/// El estado cambia en cada consulta, no debe guardarse en caché
+fn no_store(response: impl IntoResponse) -> Response {
+    let mut response = response.into_response();
+    response
+        .headers_mut()
+        .insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    response
+}
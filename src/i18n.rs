@@ -0,0 +1,224 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    str::FromStr,
+    sync::{LazyLock, OnceLock},
+};
+
+use axum::{extract::Request, middleware::Next, response::Response};
+use http::{
+    HeaderValue,
+    header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::AppError;
+
+/// Idiomas soportados por los catálogos de mensajes
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    Es,
+    En,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::Es, Language::En];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Language::Es => "es",
+            Language::En => "en",
+        }
+    }
+
+    /// Interpreta una etiqueta de idioma (`es`, `en-US`, ...) usando solo la subetiqueta principal
+    pub fn from_tag(tag: &str) -> Option<Language> {
+        let primary = tag.trim().split(['-', '_']).next()?;
+
+        Language::ALL
+            .into_iter()
+            .find(|lang| lang.as_str().eq_ignore_ascii_case(primary))
+    }
+
+    /// Elige el idioma soportado con mayor preferencia en una cabecera `Accept-Language`
+    pub fn from_accept_language(header: &str) -> Option<Language> {
+        header
+            .split(',')
+            .enumerate()
+            .filter_map(|(position, item)| {
+                let mut parts = item.split(';');
+                let tag = parts.next()?.trim();
+                let quality = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .map(|q| q.trim().parse::<f32>().unwrap_or(0.0))
+                    .unwrap_or(1.0);
+
+                let lang = if tag == "*" {
+                    default_language()
+                } else {
+                    Language::from_tag(tag)?
+                };
+
+                (quality > 0.0).then_some((lang, quality, position))
+            })
+            // Mayor calidad primero; en empate se respeta el orden del cliente
+            .max_by(|(_, qa, pa), (_, qb, pb)| qa.total_cmp(qb).then(pb.cmp(pa)))
+            .map(|(lang, _, _)| lang)
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Language {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Language::from_tag(s).ok_or_else(|| AppError::UnsupportedLanguage(s.to_string()))
+    }
+}
+
+type Catalog = HashMap<String, String>;
+
+static CATALOGS: LazyLock<HashMap<Language, Catalog>> = LazyLock::new(|| {
+    let parse = |raw: &str| -> Catalog {
+        serde_json::from_str(raw).expect("Catálogo de mensajes mal formado")
+    };
+
+    HashMap::from([
+        (Language::Es, parse(include_str!("../locales/es.json"))),
+        (Language::En, parse(include_str!("../locales/en.json"))),
+    ])
+});
+
+static DEFAULT_LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+tokio::task_local! {
+    static REQUEST_LANGUAGE: Language;
+}
+
+/// Define el idioma usado fuera de una petición HTTP (logs, CLI). Solo tiene efecto la primera vez.
+pub fn set_default_language(lang: Language) {
+    let _ = DEFAULT_LANGUAGE.set(lang);
+}
+
+pub fn default_language() -> Language {
+    DEFAULT_LANGUAGE.get().copied().unwrap_or_default()
+}
+
+/// Idioma negociado para la petición en curso, o el idioma por defecto
+pub fn request_language() -> Language {
+    REQUEST_LANGUAGE
+        .try_with(|lang| *lang)
+        .unwrap_or_else(|_| default_language())
+}
+
+/// Busca el mensaje `key` en el catálogo del idioma y reemplaza los argumentos `{nombre}`.
+/// Si el mensaje no existe se usa el catálogo por defecto y, en último caso, la propia llave.
+pub fn translate(lang: Language, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let template = CATALOGS
+        .get(&lang)
+        .and_then(|catalog| catalog.get(key))
+        .or_else(|| CATALOGS.get(&Language::default())?.get(key))
+        .map(String::as_str)
+        .unwrap_or(key);
+
+    args.iter()
+        .fold(template.to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{name}}}"), &value.to_string())
+        })
+}
+
+/// Traduce un mensaje del catálogo en el idioma por defecto
+macro_rules! tr {
+    ($key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::tr_lang!($crate::i18n::default_language(), $key $(, $name = $value)*)
+    };
+}
+
+/// Traduce un mensaje del catálogo en el idioma indicado
+macro_rules! tr_lang {
+    ($lang:expr, $key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::translate(
+            $lang,
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),*],
+        )
+    };
+}
+
+pub(crate) use {tr, tr_lang};
+
+/// Tipos cuyos mensajes pueden mostrarse en distintos idiomas
+pub trait Localize {
+    fn localize(&self, lang: Language) -> String;
+}
+
+/// Negocia el idioma de la respuesta a partir de `Accept-Language`
+pub async fn language_middleware(req: Request, next: Next) -> Response {
+    let lang = req
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(Language::from_accept_language)
+        .unwrap_or_else(default_language);
+
+    let mut response = REQUEST_LANGUAGE.scope(lang, next.run(req)).await;
+    response
+        .headers_mut()
+        .insert(CONTENT_LANGUAGE, HeaderValue::from_static(lang.as_str()));
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn catalogs_have_the_same_keys() {
+        let keys = |lang: Language| CATALOGS[&lang].keys().cloned().collect::<BTreeSet<_>>();
+
+        for lang in Language::ALL {
+            assert_eq!(keys(lang), keys(Language::Es), "catálogo {lang}");
+        }
+    }
+
+    #[test]
+    fn translation_works() {
+        let isbn = "9780306406157";
+
+        assert_eq!(
+            tr_lang!(Language::En, "book.already_exists", isbn = isbn),
+            "A book with ISBN 9780306406157 already exists"
+        );
+        assert_eq!(
+            tr_lang!(Language::Es, "book.already_exists", isbn = isbn),
+            "Libro con el ISBN: 9780306406157 ya existe"
+        );
+        assert_eq!(tr_lang!(Language::En, "missing.key"), "missing.key");
+    }
+
+    #[test]
+    fn accept_language_negotiation_works() {
+        let negotiate = Language::from_accept_language;
+
+        assert_eq!(negotiate("en-US,en;q=0.9,es;q=0.8"), Some(Language::En));
+        assert_eq!(
+            negotiate("fr-CH, fr;q=0.9, es;q=0.5, en;q=0.4"),
+            Some(Language::Es)
+        );
+        assert_eq!(negotiate("es;q=0.2, en;q=0.8"), Some(Language::En));
+        assert_eq!(negotiate("en, es"), Some(Language::En));
+        assert_eq!(negotiate("en;q=0, es"), Some(Language::Es));
+        assert_eq!(negotiate("*"), Some(default_language()));
+        assert_eq!(negotiate("de, fr"), None);
+    }
+}
@@ -1,84 +1,240 @@
-use std::sync::OnceLock;
-use std::{io, net::SocketAddr};
-
+use std::{
+    fmt,
+    io::{self, IsTerminal},
+    net::{SocketAddr, TcpListener},
+    str::FromStr,
+    time::Duration,
+};
+
+use axum::Router;
+use axum_server::{Handle, tls_rustls::RustlsConfig};
+use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
+use tracing::{Subscriber, info};
+use tracing_subscriber::{EnvFilter, fmt::MakeWriter, util::SubscriberInitExt};
+
+use crate::{
+    AppError, LibraryApi,
+    config::Config,
+    i18n::tr,
+    state::AppState,
+    tls::{CERT_RELOAD_INTERVAL, redirect_router},
+};
+
+/// Abre el pool de conexiones indicado en la configuración
+pub async fn connect(config: &Config) -> SqlitePool {
+    SqlitePool::connect(&config.database_url)
+        .await
+        .expect("Error al conectar con la base de datos")
+}
+
+/// Construye el estado de la aplicación a partir de la configuración, creando las tablas si no existen
+pub async fn init_state(config: &Config) -> AppState {
+    let pool = connect(config).await;
 
-use crate::server::routes;
+    AppState::create_tables(&pool)
+        .await
+        .expect("Error al inicializar base de datos");
 
-// Singleton para el SqlitePool
-static DB_POOL: OnceLock<SqlitePool> = OnceLock::new();
+    AppState::sqlite(pool, config.cache_control_header())
+}
 
-pub async fn initialize_pool(connection_string: &str) {
-    let pool = SqlitePool::connect(connection_string).await.unwrap();
+/// Formato de los logs: texto para leerlos en consola o JSON para un recolector de logs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
 
-    DB_POOL.get_or_init(|| pool);
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogFormat::Text => f.write_str("text"),
+            LogFormat::Json => f.write_str("json"),
+        }
+    }
 }
 
-pub fn get_pool() -> SqlitePool {
-    let pool = DB_POOL
-        .get()
-        .expect("Pool no inicializado. Llama a initialize_pool primero.");
+impl FromStr for LogFormat {
+    type Err = AppError;
 
-    pool.clone()
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(AppError::UnsupportedLogFormat(s.to_string())),
+        }
+    }
 }
 
-#[inline]
-fn db_conn_str() -> String {
-    #[cfg(feature = "integration-tests")]
-    return "sqlite::memory:".to_string();
+/// Suscriptor de `tracing` que recibe tanto los logs de la aplicación como las trazas HTTP.
+/// `filter` usa la sintaxis de `RUST_LOG`, por ejemplo `info,sqlx=warn`.
+pub fn log_subscriber<W>(
+    filter: &str,
+    format: LogFormat,
+    writer: W,
+) -> Box<dyn Subscriber + Send + Sync>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let filter = EnvFilter::try_new(filter).unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer);
+
+    match format {
+        // Colores solo si la salida es una terminal, no al redirigirse a un archivo
+        LogFormat::Text => Box::new(builder.with_ansi(io::stdout().is_terminal()).finish()),
+        // Cada línea incluye los campos del span de la petición, entre ellos `request_id`
+        LogFormat::Json => Box::new(
+            builder
+                .json()
+                .flatten_event(true)
+                .with_current_span(true)
+                .with_span_list(false)
+                .finish(),
+        ),
+    }
+}
 
-    #[cfg(not(feature = "integration-tests"))]
-    return crate::config::Config::get_instance().database_url.clone();
+/// Instala el suscriptor global; los logs de dependencias que usan `log` también se reciben
+pub fn init_logger(level: &str, format: LogFormat) {
+    log_subscriber(level, format, io::stdout).init();
 }
 
-pub async fn init_db_services() {
-    // Inicializamos el pool de conexiones
-    initialize_pool(&db_conn_str()).await;
+/// Tiempo por defecto que se espera a las peticiones en curso al apagar el servidor
+pub const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 30;
 
-    // Inicializamos las tablas
-    let api_key_repo =
-        crate::api_keys::api_keys_infrastructure::ApiKeySqliteRepository::get_instance();
-    let book_repo = crate::books::books_infrastructure::BookSqliteRepository::get_instance();
+/// Inicia el servidor independiente en `{API_HOST}:{API_PORT}` (HTTPS si hay certificado
+/// configurado) y lo detiene de forma ordenada al recibir SIGINT o SIGTERM
+pub async fn run(config: &Config) -> io::Result<()> {
+    let pool = connect(config).await;
 
-    // Inicializamos las tablas en paralelo
-    let (result_api_key, result_book) =
-        tokio::join!(api_key_repo.init_table(), book_repo.init_tables());
+    let routes = LibraryApi::builder()
+        .pool(pool.clone())
+        .config(config)
+        .create_tables(true)
+        .build()
+        .await
+        .expect("Error al inicializar base de datos");
 
-    // Verificamos los resultados de las inicializaciones
-    result_api_key.expect("Error al inicializar base de datos de ApiKey");
-    result_book.expect("Error al inicializar base de datos de Book");
+    info!("{}", tr!("server.tables_ready"));
 
-    #[cfg(not(feature = "cli"))]
-    println!("Tablas inicializadas correctamente");
-}
+    let addr = SocketAddr::new(config.api_host, config.api_port);
+    let listener = TcpListener::bind(addr)?;
+
+    let handle = Handle::new();
+    let timeout = Duration::from_secs(config.shutdown_timeout_secs);
+    tokio::spawn(shutdown_on_signal(handle.clone(), timeout));
+
+    let tls = match config.tls() {
+        Some(paths) => {
+            let rustls = paths.load().await?;
+            tokio::spawn(paths.watch(rustls.clone(), CERT_RELOAD_INTERVAL));
+            Some(rustls)
+        }
+        None => None,
+    };
+
+    // Redirección opcional de HTTP a HTTPS, se apaga junto con el servidor principal
+    if let (Some(_), Some(redirect_port)) = (&tls, config.http_redirect_port) {
+        let redirect_addr = SocketAddr::new(config.api_host, redirect_port);
+        let redirect_listener = TcpListener::bind(redirect_addr)?;
+        tokio::spawn(serve(
+            redirect_listener,
+            redirect_router(config.api_port),
+            handle.clone(),
+        ));
+
+        info!(
+            "{}",
+            tr!(
+                "server.redirecting",
+                address = format!("http://{redirect_addr}")
+            )
+        );
+    }
+
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    info!(
+        "{}",
+        tr!(
+            "server.started",
+            version = env!("CARGO_PKG_VERSION"),
+            address = format!("{scheme}://{addr}")
+        )
+    );
 
-pub fn init_logger(level: &str) {
-    use log::LevelFilter;
-    // Inicializar el logger con el nivel especificado en la configuración
-    let log_level = match level.to_lowercase().as_str() {
-        "trace" => LevelFilter::Trace,
-        "debug" => LevelFilter::Debug,
-        "info" => LevelFilter::Info,
-        "warn" => LevelFilter::Warn,
-        "error" => LevelFilter::Error,
-        _ => LevelFilter::Info, // Valor por defecto
+    let result = match tls {
+        Some(rustls) => serve_tls(listener, routes, handle, rustls).await,
+        None => serve(listener, routes, handle).await,
     };
 
-    env_logger::Builder::new().filter_level(log_level).init();
+    // Las peticiones en curso ya terminaron, se pueden cerrar las conexiones a la base de datos
+    pool.close().await;
+    info!("{}", tr!("server.stopped"));
+
+    result
 }
 
-pub async fn run(port: u16) -> io::Result<()> {
-    let routes = routes().await;
+/// Atiende peticiones en `listener` hasta que `handle` indique el apagado
+pub async fn serve(listener: TcpListener, routes: Router, handle: Handle) -> io::Result<()> {
+    listener.set_nonblocking(true)?;
+
+    axum_server::from_tcp(listener)
+        .handle(handle)
+        .serve(routes.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+}
+
+/// Atiende peticiones HTTPS en `listener` hasta que `handle` indique el apagado
+pub async fn serve_tls(
+    listener: TcpListener,
+    routes: Router,
+    handle: Handle,
+    config: RustlsConfig,
+) -> io::Result<()> {
+    listener.set_nonblocking(true)?;
+
+    axum_server::from_tcp_rustls(listener, config)
+        .handle(handle)
+        .serve(routes.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+}
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+/// Espera SIGINT o SIGTERM y apaga el servidor, dando hasta `timeout` a las peticiones en curso
+pub async fn shutdown_on_signal(handle: Handle, timeout: Duration) {
+    shutdown_signal().await;
 
-    println!(
-        "Ejecutando Library API v{} en localhost:{}",
-        env!("CARGO_PKG_VERSION"),
-        port
+    info!(
+        "{}",
+        tr!("server.shutting_down", seconds = timeout.as_secs())
     );
+    handle.graceful_shutdown(Some(timeout));
+}
 
-    axum_server::bind(addr)
-        .serve(routes.into_make_service())
-        .await
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Error al escuchar SIGINT");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Error al escuchar SIGTERM")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
 }
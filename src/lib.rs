@@ -1,9 +1,21 @@
 pub mod api_keys;
+pub mod audit;
 pub mod books;
 pub mod config;
+pub mod health;
+pub mod i18n;
 pub mod init;
+pub mod metrics;
+pub mod openapi;
+pub mod rate_limit;
+pub mod request_id;
 
 pub mod server;
+pub mod state;
+pub mod tls;
 
+mod builder;
 mod errors;
+
+pub use builder::*;
 pub use errors::*;
@@ -0,0 +1,245 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http::{HeaderValue, StatusCode, header::CONTENT_TYPE};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use sqlx::SqlitePool;
+
+use crate::{AppError, openapi::HEALTH_TAG};
+
+/// Prefijo común de todas las métricas
+const NAMESPACE: &str = "library_api";
+
+/// Etiqueta `route` de las peticiones que no coinciden con ninguna ruta
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Métricas de la aplicación en formato Prometheus.
+/// Cada estado tiene su propio registro, por lo que dos aplicaciones no comparten contadores.
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_duration: HistogramVec,
+    auth_failures: IntCounterVec,
+    books_created: IntCounter,
+    books_deleted: IntCounter,
+    pool: Option<PoolMetrics>,
+}
+
+/// Uso del pool de conexiones, actualizado en cada consulta de `/metrics`
+#[derive(Clone)]
+struct PoolMetrics {
+    pool: SqlitePool,
+    size: IntGauge,
+    idle: IntGauge,
+    max: IntGauge,
+}
+
+impl Metrics {
+    /// Métricas sin base de datos, para repositorios propios
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "Peticiones HTTP atendidas").namespace(NAMESPACE),
+            &["method", "route", "status"],
+        )
+        .expect("Métrica válida");
+        let http_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Duración de las peticiones HTTP",
+            )
+            .namespace(NAMESPACE),
+            &["method", "route", "status"],
+        )
+        .expect("Métrica válida");
+        let auth_failures = IntCounterVec::new(
+            Opts::new(
+                "api_key_auth_failures_total",
+                "Autenticaciones rechazadas por motivo",
+            )
+            .namespace(NAMESPACE),
+            &["reason"],
+        )
+        .expect("Métrica válida");
+        let books_created = IntCounter::with_opts(
+            Opts::new("books_created_total", "Libros creados").namespace(NAMESPACE),
+        )
+        .expect("Métrica válida");
+        let books_deleted = IntCounter::with_opts(
+            Opts::new("books_deleted_total", "Libros eliminados").namespace(NAMESPACE),
+        )
+        .expect("Métrica válida");
+
+        let metrics = Self {
+            registry,
+            http_requests,
+            http_duration,
+            auth_failures,
+            books_created,
+            books_deleted,
+            pool: None,
+        };
+        metrics.register(metrics.http_requests.clone());
+        metrics.register(metrics.http_duration.clone());
+        metrics.register(metrics.auth_failures.clone());
+        metrics.register(metrics.books_created.clone());
+        metrics.register(metrics.books_deleted.clone());
+
+        metrics
+    }
+
+    /// Métricas que además reportan el uso del pool de SQLite
+    pub fn sqlite(pool: SqlitePool) -> Self {
+        let mut metrics = Self::new();
+
+        let gauge = |name: &str, help: &str| {
+            let gauge = IntGauge::with_opts(Opts::new(name, help).namespace(NAMESPACE))
+                .expect("Métrica válida");
+            metrics.register(gauge.clone());
+            gauge
+        };
+        let size = gauge("db_pool_connections", "Conexiones abiertas en el pool");
+        let idle = gauge("db_pool_idle_connections", "Conexiones abiertas sin usar");
+        let max = gauge("db_pool_max_connections", "Máximo de conexiones del pool");
+
+        metrics.pool = Some(PoolMetrics {
+            pool,
+            size,
+            idle,
+            max,
+        });
+        metrics
+    }
+
+    fn register<C: prometheus::core::Collector + 'static>(&self, collector: C) {
+        self.registry
+            .register(Box::new(collector))
+            .expect("Las métricas se registran una sola vez");
+    }
+
+    pub fn observe_request(&self, method: &str, route: &str, status: StatusCode, secs: f64) {
+        let labels = [method, route, status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_duration.with_label_values(&labels).observe(secs);
+    }
+
+    /// Registra una autenticación rechazada; `reason` es el código del error
+    pub fn auth_failed(&self, reason: &str) {
+        self.auth_failures.with_label_values(&[reason]).inc();
+    }
+
+    pub fn book_created(&self) {
+        self.books_created.inc();
+    }
+
+    pub fn book_deleted(&self) {
+        self.books_deleted.inc();
+    }
+
+    /// Métricas en el formato de texto de Prometheus
+    pub fn render(&self) -> Result<String, AppError> {
+        if let Some(pool) = &self.pool {
+            pool.size.set(i64::from(pool.pool.size()));
+            pool.idle.set(pool.pool.num_idle() as i64);
+            pool.max
+                .set(i64::from(pool.pool.options().get_max_connections()));
+        }
+
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(|e| AppError::Metrics(e.to_string()))?;
+
+        String::from_utf8(buffer).map_err(|e| AppError::Metrics(e.to_string()))
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cuenta las peticiones y su duración por método, ruta y estado.
+/// La ruta es el patrón registrado (`/books/{id}`), no la URI recibida.
+pub async fn metrics_middleware(
+    State(metrics): State<Metrics>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().clone();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+
+    let start = Instant::now();
+    let response = next.run(req).await;
+
+    metrics.observe_request(
+        method.as_str(),
+        &route,
+        response.status(),
+        start.elapsed().as_secs_f64(),
+    );
+
+    response
+}
+
+/// Métricas de la API en formato Prometheus
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = HEALTH_TAG,
+    responses(
+        (
+            status = 200, description = "Métricas en formato de texto de Prometheus",
+            body = String, content_type = "text/plain; version=0.0.4"
+        ),
+    )
+)]
+pub async fn metrics(State(metrics): State<Metrics>) -> Result<Response, AppError> {
+    let body = metrics.render()?;
+
+    let mut response = body.into_response();
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(prometheus::TEXT_FORMAT),
+    );
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_works() {
+        let metrics = Metrics::new();
+        metrics.observe_request("GET", "/books/{id}", StatusCode::OK, 0.01);
+        metrics.auth_failed("api_key.invalid");
+        metrics.book_created();
+
+        let text = metrics.render().unwrap();
+
+        assert!(text.contains(
+            r#"library_api_http_requests_total{method="GET",route="/books/{id}",status="200"} 1"#
+        ));
+        assert!(text.contains("library_api_http_request_duration_seconds_bucket"));
+        assert!(
+            text.contains(r#"library_api_api_key_auth_failures_total{reason="api_key.invalid"} 1"#)
+        );
+        assert!(text.contains("library_api_books_created_total 1"));
+        assert!(!text.contains("db_pool"));
+    }
+}
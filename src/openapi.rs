@@ -0,0 +1,193 @@
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{ApiKey, ApiKeyValue, SecurityScheme},
+};
+
+use crate::{
+    FieldError, ProblemDetails,
+    audit::audit_infrastructure::controllers::AUDIT_TAG,
+    books::books_infrastructure::controllers::{
+        AUTHORS_TAG, BOOKS_TAG, LOCATIONS_TAG, PUBLISHERS_TAG, SUBJECTS_TAG, TAGS_TAG,
+    },
+};
+
+/// Ruta donde se publica el documento OpenAPI generado
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+/// Ruta de la interfaz interactiva (Swagger UI)
+pub const DOCS_PATH: &str = "/docs";
+
+/// Etiqueta para las operaciones informativas
+pub const INFO_TAG: &str = "info";
+
+/// Etiqueta para las comprobaciones de salud
+pub const HEALTH_TAG: &str = "health";
+
+/// Datos generales del documento OpenAPI. Las rutas se agregan al construir el router
+/// (ver [`crate::server::routes`]), por lo que siempre coinciden con las registradas.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Library API",
+        description = "Api para gestionar libros y usuarios de una biblioteca",
+        license(name = "GPL-3.0", url = "https://www.gnu.org/licenses/gpl-3.0.html")
+    ),
+    tags(
+        (name = BOOKS_TAG, description = "Catálogo de libros"),
+        (name = AUTHORS_TAG, description = "Autores del catálogo"),
+        (name = PUBLISHERS_TAG, description = "Editoriales del catálogo"),
+        (name = SUBJECTS_TAG, description = "Vocabulario controlado de temas"),
+        (name = TAGS_TAG, description = "Etiquetas libres de los libros"),
+        (name = LOCATIONS_TAG, description = "Sucursales de la biblioteca"),
+        (name = INFO_TAG, description = "Información sobre la API"),
+        (name = HEALTH_TAG, description = "Estado de la API para orquestadores y balanceadores"),
+        (name = AUDIT_TAG, description = "Historial de cambios del catálogo y las ApiKeys"),
+    ),
+    components(schemas(ProblemDetails, FieldError)),
+    modifiers(&ApiKeySecurity)
+)]
+pub struct ApiDoc;
+
+/// Esquema de autenticación `Authorization: ApiKey {token}`
+struct ApiKeySecurity;
+
+impl Modify for ApiKeySecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "Authorization",
+                "Usar el prefijo `ApiKey` de la forma `ApiKey LibraryApi_{id}_{token}`",
+            ))),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use http::HeaderValue;
+    use sqlx::SqlitePool;
+
+    use crate::{
+        books::books_infrastructure::DEFAULT_CACHE_CONTROL, server::openapi, state::AppState,
+    };
+
+    const METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+    fn spec() -> serde_json::Value {
+        // El documento no depende de la base de datos, basta con un pool sin conexiones
+        let pool = SqlitePool::connect_lazy("sqlite::memory:")
+            .expect("Failed to create in-memory SQLite database");
+        let state = AppState::sqlite(pool, HeaderValue::from_static(DEFAULT_CACHE_CONTROL));
+
+        serde_json::to_value(openapi(&state)).unwrap()
+    }
+
+    /// Operaciones documentadas, expresadas como `METODO /ruta`
+    fn operations(spec: &serde_json::Value) -> BTreeSet<String> {
+        spec["paths"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .flat_map(|(path, item)| {
+                item.as_object()
+                    .unwrap()
+                    .keys()
+                    .filter(|method| METHODS.contains(&method.as_str()))
+                    .map(move |method| format!("{} {path}", method.to_uppercase()))
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn spec_documents_every_route() {
+        let expected = [
+            "GET /",
+            "GET /info",
+            "GET /health/live",
+            "GET /health/ready",
+            "GET /metrics",
+            "GET /books",
+            "POST /books",
+            "GET /books/search",
+            "GET /books/shelf",
+            "GET /books/{id}",
+            "PUT /books/{id}",
+            "DELETE /books/{id}",
+            "POST /books/{id}/restore",
+            "GET /books/deleted",
+            "GET /books/{id}/history",
+            "POST /books/{id}/revert/{rev}",
+            "GET /books/{id}/availability",
+            "PUT /books/{id}/locations/{location_id}",
+            "POST /books/{id}/transfers",
+            "GET /books/{id}/movements",
+            "GET /authors",
+            "GET /authors/{id}",
+            "PUT /authors/{id}",
+            "POST /authors/{id}/merge",
+            "GET /publishers",
+            "POST /publishers/{id}/merge",
+            "GET /subjects",
+            "POST /subjects",
+            "GET /tags",
+            "GET /locations",
+            "POST /locations",
+            "GET /audit",
+        ]
+        .map(String::from);
+
+        assert_eq!(operations(&spec()), BTreeSet::from(expected));
+    }
+
+    #[tokio::test]
+    async fn spec_schemas_follow_dtos() {
+        let spec = spec();
+        let schemas = &spec["components"]["schemas"];
+
+        for name in [
+            "Book",
+            "BookId",
+            "BookContributor",
+            "ContributorRole",
+            "CreateBookDto",
+            "ContributorDto",
+            "PaginatedBooks",
+            "PaginatedAuthors",
+            "AuthorWithBooks",
+            "PaginatedPublishers",
+            "BookSearchResults",
+            "BookFacets",
+            "ShelfBrowse",
+            "Classification",
+            "PaginatedSubjects",
+            "PaginatedTags",
+            "PaginatedLocations",
+            "BookAvailability",
+            "StockMovement",
+            "ProblemDetails",
+            "AuditEntry",
+            "PaginatedAuditEntries",
+        ] {
+            assert!(schemas.get(name).is_some(), "falta el esquema {name}");
+        }
+
+        let page_size = spec["paths"]["/books"]["get"]["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|param| param["name"] == "page_size")
+            .expect("falta el parámetro page_size");
+        assert_eq!(page_size["schema"]["maximum"], u8::MAX);
+
+        assert!(spec["components"]["securitySchemes"]["api_key"].is_object());
+        assert_eq!(
+            spec["paths"]["/books"]["post"]["security"][0]["api_key"],
+            serde_json::json!([])
+        );
+    }
+}
@@ -0,0 +1,502 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    hash::Hash,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{ConnectInfo, Request, State, connect_info::MockConnectInfo},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http::{HeaderMap, HeaderName, HeaderValue, header::RETRY_AFTER};
+use serde::{Deserialize, Serialize};
+
+use crate::{AppError, i18n::tr};
+
+pub static RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+pub static RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+pub static RATELIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+/// Cabecera con la IP del cliente cuando la API está detrás de un proxy
+static X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+
+/// Cantidad de claves a partir de la cual se descartan las que ya recuperaron todas sus fichas
+const MAX_TRACKED_KEYS: usize = 10_000;
+
+/// Periodo en el que se reponen las peticiones de un límite
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Per {
+    Second,
+    Minute,
+    Hour,
+}
+
+impl Per {
+    fn duration(self) -> Duration {
+        match self {
+            Per::Second => Duration::from_secs(1),
+            Per::Minute => Duration::from_secs(60),
+            Per::Hour => Duration::from_secs(3600),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Per::Second => "s",
+            Per::Minute => "min",
+            Per::Hour => "h",
+        }
+    }
+}
+
+/// Límite de peticiones, escrito como `60/min`, `5/s`, `1000/h` u `off`.
+/// Se admiten ráfagas de hasta `requests` peticiones, que se reponen de forma continua.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum RateLimit {
+    Off,
+    Limit { requests: u32, per: Per },
+}
+
+impl RateLimit {
+    pub const fn new(requests: u32, per: Per) -> Self {
+        RateLimit::Limit { requests, per }
+    }
+}
+
+impl fmt::Display for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RateLimit::Off => f.write_str("off"),
+            RateLimit::Limit { requests, per } => write!(f, "{requests}/{}", per.as_str()),
+        }
+    }
+}
+
+impl FromStr for RateLimit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("off") {
+            return Ok(RateLimit::Off);
+        }
+
+        let invalid = || tr!("config.invalid_rate_limit", value = s);
+        let (requests, per) = s.split_once('/').ok_or_else(invalid)?;
+        let requests: u32 = requests.trim().parse().map_err(|_| invalid())?;
+        let per = match per.trim() {
+            "s" | "sec" => Per::Second,
+            "m" | "min" => Per::Minute,
+            "h" | "hour" => Per::Hour,
+            _ => return Err(invalid()),
+        };
+
+        if requests == 0 {
+            return Err(invalid());
+        }
+        Ok(RateLimit::new(requests, per))
+    }
+}
+
+impl From<RateLimit> for String {
+    fn from(value: RateLimit) -> Self {
+        value.to_string()
+    }
+}
+
+impl TryFrom<String> for RateLimit {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Límites propios de algunas ApiKeys, escritos como `id1=1000/min,id2=off`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct ApiKeyRateLimits(pub BTreeMap<String, RateLimit>);
+
+impl fmt::Display for ApiKeyRateLimits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limits: Vec<_> = self
+            .0
+            .iter()
+            .map(|(id, limit)| format!("{id}={limit}"))
+            .collect();
+        f.write_str(&limits.join(","))
+    }
+}
+
+impl FromStr for ApiKeyRateLimits {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| {
+                let (id, limit) = item
+                    .split_once('=')
+                    .ok_or_else(|| tr!("config.expected_api_key_limit", item = item))?;
+                Ok((id.trim().to_string(), limit.parse()?))
+            })
+            .collect::<Result<_, String>>()
+            .map(ApiKeyRateLimits)
+    }
+}
+
+impl From<ApiKeyRateLimits> for String {
+    fn from(value: ApiKeyRateLimits) -> Self {
+        value.to_string()
+    }
+}
+
+impl TryFrom<String> for ApiKeyRateLimits {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Límites de peticiones por grupo de rutas
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// Rutas públicas del catálogo, por IP del cliente
+    pub public: RateLimit,
+
+    /// Rutas protegidas, por ApiKey
+    pub api_key: RateLimit,
+
+    /// Límites de ApiKeys concretas, reemplazan a `api_key`
+    pub api_key_overrides: ApiKeyRateLimits,
+
+    /// Intentos de autenticación fallidos, por IP del cliente
+    pub auth_failures: RateLimit,
+
+    /// Tomar la IP del cliente de `X-Forwarded-For`; solo es seguro detrás de un proxy de confianza
+    pub trust_forwarded_for: bool,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            public: RateLimit::new(120, Per::Minute),
+            api_key: RateLimit::new(600, Per::Minute),
+            api_key_overrides: ApiKeyRateLimits::default(),
+            auth_failures: RateLimit::new(10, Per::Minute),
+            trust_forwarded_for: false,
+        }
+    }
+}
+
+/// Resultado de consultar un límite
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Tiempo hasta recuperar todas las peticiones
+    pub reset: Duration,
+    /// Tiempo hasta que se admita la siguiente petición
+    pub retry_after: Duration,
+}
+
+impl Decision {
+    /// Agrega las cabeceras `RateLimit-*` a una respuesta
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        headers.insert(RATELIMIT_LIMIT.clone(), HeaderValue::from(self.limit));
+        headers.insert(
+            RATELIMIT_REMAINING.clone(),
+            HeaderValue::from(self.remaining),
+        );
+        headers.insert(
+            RATELIMIT_RESET.clone(),
+            HeaderValue::from(ceil_secs(self.reset)),
+        );
+    }
+
+    /// Respuesta `429` con `Retry-After` y las cabeceras `RateLimit-*`
+    pub fn reject(&self) -> Response {
+        let retry_after = ceil_secs(self.retry_after).max(1);
+        let mut response = AppError::RateLimited { retry_after }.into_response();
+
+        self.apply(response.headers_mut());
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+        response
+    }
+}
+
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token bucket independiente para cada clave
+pub struct KeyedLimiter<K> {
+    limit: u32,
+    refill_per_sec: f64,
+    buckets: Mutex<HashMap<K, Bucket>>,
+}
+
+impl<K: Eq + Hash + Clone> KeyedLimiter<K> {
+    /// `None` si el límite está desactivado
+    pub fn new(limit: RateLimit) -> Option<Self> {
+        match limit {
+            RateLimit::Off => None,
+            RateLimit::Limit { requests, per } => Some(Self {
+                limit: requests,
+                refill_per_sec: f64::from(requests) / per.duration().as_secs_f64(),
+                buckets: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Consume una petición de `key` si hay disponibles
+    pub fn check(&self, key: &K) -> Decision {
+        self.check_at(key, Instant::now(), true)
+    }
+
+    /// Consulta el estado de `key` sin consumir peticiones
+    pub fn peek(&self, key: &K) -> Decision {
+        self.check_at(key, Instant::now(), false)
+    }
+
+    fn check_at(&self, key: &K, now: Instant, consume: bool) -> Decision {
+        let capacity = f64::from(self.limit);
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() >= MAX_TRACKED_KEYS {
+            let refill = self.refill_per_sec;
+            buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * refill < capacity
+            });
+        }
+
+        let bucket = buckets.entry(key.clone()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(capacity);
+        bucket.updated = now;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed && consume {
+            bucket.tokens -= 1.0;
+        }
+
+        let tokens = bucket.tokens;
+        Decision {
+            allowed,
+            limit: self.limit,
+            remaining: tokens.floor() as u32,
+            reset: Duration::from_secs_f64((capacity - tokens) / self.refill_per_sec),
+            retry_after: Duration::from_secs_f64((1.0 - tokens).max(0.0) / self.refill_per_sec),
+        }
+    }
+}
+
+/// Límites de peticiones de la aplicación, compartidos por todas las rutas
+#[derive(Clone)]
+pub struct RateLimits {
+    inner: Arc<RateLimitsInner>,
+}
+
+struct RateLimitsInner {
+    public: Option<KeyedLimiter<IpAddr>>,
+    api_key: Option<KeyedLimiter<String>>,
+    api_key_overrides: HashMap<String, Option<KeyedLimiter<String>>>,
+    auth_failures: Option<KeyedLimiter<IpAddr>>,
+    trust_forwarded_for: bool,
+}
+
+impl RateLimits {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        let api_key_overrides = config
+            .api_key_overrides
+            .0
+            .iter()
+            .map(|(id, limit)| (id.clone(), KeyedLimiter::new(*limit)))
+            .collect();
+
+        Self {
+            inner: Arc::new(RateLimitsInner {
+                public: KeyedLimiter::new(config.public),
+                api_key: KeyedLimiter::new(config.api_key),
+                api_key_overrides,
+                auth_failures: KeyedLimiter::new(config.auth_failures),
+                trust_forwarded_for: config.trust_forwarded_for,
+            }),
+        }
+    }
+
+    /// IP del cliente, de la conexión o de `X-Forwarded-For` si se configuró así.
+    /// Si el servidor no expone la dirección de la conexión no se limita por IP.
+    pub fn client_ip(&self, req: &Request) -> Option<IpAddr> {
+        let forwarded = self
+            .inner
+            .trust_forwarded_for
+            .then(|| req.headers().get(&X_FORWARDED_FOR))
+            .flatten()
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .and_then(|ip| ip.trim().parse().ok());
+
+        let extensions = req.extensions();
+        forwarded
+            .or_else(|| {
+                extensions
+                    .get::<ConnectInfo<SocketAddr>>()
+                    .map(|info| info.0.ip())
+            })
+            .or_else(|| {
+                extensions
+                    .get::<MockConnectInfo<SocketAddr>>()
+                    .map(|info| info.0.ip())
+            })
+    }
+
+    /// Consume una petición pública de `ip`
+    pub fn check_public(&self, ip: IpAddr) -> Option<Decision> {
+        self.inner.public.as_ref().map(|limiter| limiter.check(&ip))
+    }
+
+    /// Consume una petición de la ApiKey `id`
+    pub fn check_api_key(&self, id: &str) -> Option<Decision> {
+        let limiter = match self.inner.api_key_overrides.get(id) {
+            Some(limiter) => limiter.as_ref(),
+            None => self.inner.api_key.as_ref(),
+        };
+        limiter.map(|limiter| limiter.check(&id.to_string()))
+    }
+
+    /// Si `ip` agotó sus intentos fallidos de autenticación, cuándo puede reintentar
+    pub fn auth_blocked(&self, ip: IpAddr) -> Option<Decision> {
+        self.inner
+            .auth_failures
+            .as_ref()
+            .map(|limiter| limiter.peek(&ip))
+            .filter(|decision| !decision.allowed)
+    }
+
+    /// Registra un intento fallido de autenticación de `ip`
+    pub fn auth_failed(&self, ip: IpAddr) {
+        if let Some(limiter) = &self.inner.auth_failures {
+            limiter.check(&ip);
+        }
+    }
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self::new(&RateLimitConfig::default())
+    }
+}
+
+/// Limita las peticiones a las rutas públicas por IP del cliente
+pub async fn public_rate_limit_middleware(
+    State(limits): State<RateLimits>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(decision) = limits
+        .client_ip(&req)
+        .and_then(|ip| limits.check_public(ip))
+    else {
+        return next.run(req).await;
+    };
+
+    if !decision.allowed {
+        return decision.reject();
+    }
+
+    let mut response = next.run(req).await;
+    decision.apply(response.headers_mut());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limit_parsing_works() {
+        assert_eq!("60/min".parse(), Ok(RateLimit::new(60, Per::Minute)));
+        assert_eq!(" 5/s ".parse(), Ok(RateLimit::new(5, Per::Second)));
+        assert_eq!("OFF".parse(), Ok(RateLimit::Off));
+        assert!("0/min".parse::<RateLimit>().is_err());
+        assert!("60/day".parse::<RateLimit>().is_err());
+        assert!("60".parse::<RateLimit>().is_err());
+        assert_eq!(RateLimit::new(1000, Per::Hour).to_string(), "1000/h");
+
+        let overrides: ApiKeyRateLimits = "abc=1000/min, def=off".parse().unwrap();
+        assert_eq!(overrides.0["abc"], RateLimit::new(1000, Per::Minute));
+        assert_eq!(overrides.0["def"], RateLimit::Off);
+        assert_eq!(overrides.to_string(), "abc=1000/min,def=off");
+        assert_eq!("".parse(), Ok(ApiKeyRateLimits::default()));
+        assert!("abc".parse::<ApiKeyRateLimits>().is_err());
+    }
+
+    #[test]
+    fn token_bucket_works() {
+        let limiter = KeyedLimiter::new(RateLimit::new(2, Per::Minute)).unwrap();
+        let start = Instant::now();
+
+        let first = limiter.check_at(&"a", start, true);
+        assert!(first.allowed);
+        assert_eq!(first.remaining, 1);
+        assert_eq!(first.reset, Duration::from_secs(30));
+
+        assert!(limiter.check_at(&"a", start, true).allowed);
+        let rejected = limiter.check_at(&"a", start, true);
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.remaining, 0);
+        assert_eq!(rejected.retry_after, Duration::from_secs(30));
+
+        // Cada clave tiene su propio límite
+        assert!(limiter.check_at(&"b", start, true).allowed);
+
+        // Se repone una petición cada 30 segundos
+        let later = start + Duration::from_secs(30);
+        assert!(limiter.check_at(&"a", later, true).allowed);
+        assert!(!limiter.check_at(&"a", later, true).allowed);
+    }
+
+    #[test]
+    fn peek_does_not_consume() {
+        let limiter = KeyedLimiter::new(RateLimit::new(1, Per::Second)).unwrap();
+        let now = Instant::now();
+
+        assert!(limiter.check_at(&"a", now, false).allowed);
+        assert!(limiter.check_at(&"a", now, false).allowed);
+        assert!(limiter.check_at(&"a", now, true).allowed);
+        assert!(!limiter.check_at(&"a", now, false).allowed);
+    }
+
+    #[test]
+    fn disabled_limits_allow_everything() {
+        let limits = RateLimits::new(&RateLimitConfig {
+            public: RateLimit::Off,
+            api_key_overrides: "vip=off".parse().unwrap(),
+            ..Default::default()
+        });
+
+        assert!(limits.check_public(IpAddr::from([10, 0, 0, 1])).is_none());
+        assert!(limits.check_api_key("vip").is_none());
+        assert!(limits.check_api_key("otra").is_some());
+    }
+}
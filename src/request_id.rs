@@ -0,0 +1,94 @@
+use axum::{extract::Request, middleware::Next, response::Response};
+use http::{HeaderName, HeaderValue};
+use tracing::Span;
+use uuid::Uuid;
+
+/// Cabecera usada para recibir y devolver el identificador de la petición
+pub static X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longitud máxima aceptada para un identificador enviado por el cliente
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Devuelve el identificador de la petición en curso, si existe
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Reutiliza el `X-Request-Id` enviado por el cliente o genera uno nuevo,
+/// y lo deja disponible durante todo el procesamiento de la petición
+pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
+    let request_id = req
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let header_value =
+        HeaderValue::from_str(&request_id).expect("El request id siempre es un valor válido");
+    req.headers_mut()
+        .insert(X_REQUEST_ID.clone(), header_value.clone());
+
+    let mut response = REQUEST_ID.scope(request_id, next.run(req)).await;
+    response
+        .headers_mut()
+        .insert(X_REQUEST_ID.clone(), header_value);
+
+    response
+}
+
+/// Span de cada petición HTTP. Incluye el identificador de la petición, por lo que
+/// aparece en todos los logs emitidos mientras se atiende (controladores, casos de uso, ...).
+pub fn request_span(req: &Request) -> Span {
+    let request_id = req
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    tracing::info_span!(
+        "request",
+        method = %req.method(),
+        uri = %req.uri(),
+        request_id,
+    )
+}
+
+#[inline]
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_id_validation_works() {
+        assert!(is_valid_request_id("3f1c2a9e-1b7d-4c55-9d0e-7f7a1d2c3b4a"));
+        assert!(is_valid_request_id("kiosk-01:42"));
+        assert!(!is_valid_request_id(""));
+        assert!(!is_valid_request_id("con espacios"));
+        assert!(!is_valid_request_id(&"a".repeat(MAX_REQUEST_ID_LEN + 1)));
+    }
+
+    #[tokio::test]
+    async fn current_request_id_is_scoped() {
+        assert_eq!(current_request_id(), None);
+
+        let inside = REQUEST_ID
+            .scope("abc".to_string(), async { current_request_id() })
+            .await;
+
+        assert_eq!(inside.as_deref(), Some("abc"));
+    }
+}
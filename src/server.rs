@@ -1,72 +1,282 @@
-use axum::{
-    Json, Router, middleware,
-    routing::{get, post},
-};
-use http::StatusCode;
-
-use serde_json::{Value, json};
+use axum::{Json, Router, middleware};
+use serde::Serialize;
 use tower_http::trace::{self, TraceLayer};
-use tracing_core::Level;
+use tracing::Level;
+use utoipa::{
+    OpenApi, ToSchema,
+    openapi::{self, Server},
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_swagger_ui::{Config as SwaggerConfig, SwaggerUi};
 
+use crate::health::health_routes;
+use crate::i18n::{language_middleware, request_language, tr_lang};
+use crate::metrics::{self, metrics_middleware};
+use crate::openapi::{ApiDoc, DOCS_PATH, INFO_TAG, OPENAPI_PATH};
+use crate::rate_limit::public_rate_limit_middleware;
+use crate::request_id::{request_id_middleware, request_span};
+use crate::state::AppState;
 use crate::{
-    api_keys::api_keys_infrastructure::api_key_middleware,
-    books::books_infrastructure::controllers::{
-        create_book_controller, delete_book_controller, get_all_books_controller,
-        get_book_controller, update_book_controller,
-    },
+    AppError,
+    api_keys::api_keys_infrastructure::{api_key_middleware, location_api_key_middleware},
+    audit::audit_infrastructure::controllers as audit_controllers,
+    books::books_infrastructure::{controllers, http_cache_middleware},
 };
-use crate::{books::books_infrastructure::controllers::search_books_controller, init};
 
-async fn fallback() -> (StatusCode, &'static str) {
-    (StatusCode::NOT_FOUND, "Ruta no encontrada")
+async fn fallback() -> AppError {
+    AppError::RouteNotFound
+}
+
+/// Construye la aplicación completa sobre el estado indicado
+pub fn routes(state: AppState) -> Router {
+    router(state, "")
 }
 
-pub async fn routes() -> Router {
-    // Asegurar que el pool esté inicializado y las tablas creadas
-    init::init_db_services().await;
+/// Construye la aplicación; `prefix` es la ruta donde se anidará el router resultante,
+/// necesaria para que la documentación apunte a las rutas correctas
+pub(crate) fn router(state: AppState, prefix: &str) -> Router {
+    let (router, mut api) = api_routes(&state).split_for_parts();
+    let metrics = state.metrics.clone();
+    if !prefix.is_empty() {
+        api.servers = Some(vec![Server::new(prefix)]);
+    }
 
-    let books_router = books_routes();
+    let docs = SwaggerUi::new(DOCS_PATH)
+        .url(OPENAPI_PATH, api)
+        .config(SwaggerConfig::from(format!("{prefix}{OPENAPI_PATH}")));
 
-    Router::new()
-        .route("/", get(index))
-        .nest("/books", books_router)
+    router
+        .with_state(state)
+        // Documentación generada a partir de las mismas rutas
+        .merge(docs)
         .fallback(fallback)
         // Añadir TraceLayer para logging de peticiones HTTP
         .layer(
             TraceLayer::new_for_http()
-                .make_span_with(trace::DefaultMakeSpan::new().level(Level::INFO))
+                .make_span_with(request_span)
                 .on_request(trace::DefaultOnRequest::new().level(Level::INFO))
                 .on_response(trace::DefaultOnResponse::new().level(Level::INFO)),
         )
+        // Cantidad y duración de las peticiones por ruta y estado
+        .layer(middleware::from_fn_with_state(metrics, metrics_middleware))
+        // Idioma de los mensajes según la cabecera Accept-Language
+        .layer(middleware::from_fn(language_middleware))
+        // Identificador de petición, disponible en las respuestas de error
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+/// Documento OpenAPI de la API, generado desde los controladores registrados
+pub fn openapi(state: &AppState) -> openapi::OpenApi {
+    api_routes(state).into_openapi()
+}
+
+/// Rutas documentadas de la API; cada ruta registrada aquí se agrega al documento OpenAPI
+fn api_routes(state: &AppState) -> OpenApiRouter<AppState> {
+    OpenApiRouter::with_openapi(ApiDoc::openapi())
+        .routes(routes!(index))
+        .merge(health_routes())
+        .routes(routes!(metrics::metrics))
+        .nest("/books", books_routes(state))
+        .nest("/authors", authors_routes(state))
+        .nest("/publishers", publishers_routes(state))
+        .nest("/subjects", subjects_routes(state))
+        .nest("/tags", tags_routes(state))
+        .nest("/locations", locations_routes(state))
+        .merge(audit_routes(state))
 }
 
-fn books_routes() -> Router {
-    // Rutas públicas que no requieren autenticación
-    let public_routes = Router::new()
-        .route("/", get(get_all_books_controller))
-        .route("/search", get(search_books_controller));
+/// Consulta de la auditoría, solo para clientes autenticados
+fn audit_routes(state: &AppState) -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(audit_controllers::list_audit_entries_controller))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_key_middleware,
+        ))
+}
+
+fn books_routes(state: &AppState) -> OpenApiRouter<AppState> {
+    // Rutas públicas que no requieren autenticación, cacheables por los clientes
+    let public_routes = OpenApiRouter::new()
+        .routes(routes!(controllers::get_all_books_controller))
+        .routes(routes!(controllers::search_books_controller))
+        .routes(routes!(controllers::browse_shelf_controller))
+        .routes(routes!(controllers::get_book_availability_controller))
+        .layer(middleware::from_fn_with_state(
+            state.books.http_cache.clone(),
+            http_cache_middleware,
+        ))
+        // Límite de peticiones por IP del cliente
+        .layer(middleware::from_fn_with_state(
+            state.rate_limits.clone(),
+            public_rate_limit_middleware,
+        ));
 
     // Rutas protegidas que requieren autenticación
-    let protected_routes = Router::new()
-        .route("/", post(create_book_controller))
-        .route(
-            "/{id}",
-            get(get_book_controller)
-                .put(update_book_controller)
-                .delete(delete_book_controller),
-        )
-        .layer(middleware::from_fn(api_key_middleware));
+    let protected_routes = OpenApiRouter::new()
+        .routes(routes!(controllers::create_book_controller))
+        .routes(routes!(
+            controllers::get_book_controller,
+            controllers::update_book_controller,
+            controllers::delete_book_controller
+        ))
+        .routes(routes!(controllers::restore_book_controller))
+        .routes(routes!(controllers::get_deleted_books_controller))
+        .routes(routes!(controllers::get_book_history_controller))
+        .routes(routes!(controllers::revert_book_controller))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_key_middleware,
+        ));
+
+    // Inventario por sucursal, también para ApiKeys limitadas a una sucursal
+    let inventory_routes = OpenApiRouter::new()
+        .routes(routes!(controllers::set_stock_controller))
+        .routes(routes!(controllers::transfer_stock_controller))
+        .routes(routes!(controllers::get_stock_movements_controller))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            location_api_key_middleware,
+        ));
 
     // Combinar las rutas públicas y protegidas
+    public_routes
+        .merge(protected_routes)
+        .merge(inventory_routes)
+}
+
+fn authors_routes(state: &AppState) -> OpenApiRouter<AppState> {
+    // Consultas públicas, cacheables igual que el catálogo de libros
+    let public_routes = OpenApiRouter::new()
+        .routes(routes!(controllers::get_authors_controller))
+        .routes(routes!(controllers::get_author_controller))
+        .layer(middleware::from_fn_with_state(
+            state.books.http_cache.clone(),
+            http_cache_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.rate_limits.clone(),
+            public_rate_limit_middleware,
+        ));
+
+    // Cambios en los autores, solo para clientes autenticados
+    let protected_routes = OpenApiRouter::new()
+        .routes(routes!(controllers::rename_author_controller))
+        .routes(routes!(controllers::merge_authors_controller))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_key_middleware,
+        ));
+
+    public_routes.merge(protected_routes)
+}
+
+fn publishers_routes(state: &AppState) -> OpenApiRouter<AppState> {
+    // Consultas públicas, cacheables igual que el catálogo de libros
+    let public_routes = OpenApiRouter::new()
+        .routes(routes!(controllers::get_publishers_controller))
+        .layer(middleware::from_fn_with_state(
+            state.books.http_cache.clone(),
+            http_cache_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.rate_limits.clone(),
+            public_rate_limit_middleware,
+        ));
+
+    // Cambios en las editoriales, solo para clientes autenticados
+    let protected_routes = OpenApiRouter::new()
+        .routes(routes!(controllers::merge_publishers_controller))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_key_middleware,
+        ));
+
+    public_routes.merge(protected_routes)
+}
+
+fn subjects_routes(state: &AppState) -> OpenApiRouter<AppState> {
+    // Consultas públicas, cacheables igual que el catálogo de libros
+    let public_routes = OpenApiRouter::new()
+        .routes(routes!(controllers::get_subjects_controller))
+        .layer(middleware::from_fn_with_state(
+            state.books.http_cache.clone(),
+            http_cache_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.rate_limits.clone(),
+            public_rate_limit_middleware,
+        ));
+
+    // Cambios en el vocabulario, solo para clientes autenticados
+    let protected_routes = OpenApiRouter::new()
+        .routes(routes!(controllers::create_subject_controller))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_key_middleware,
+        ));
+
     public_routes.merge(protected_routes)
 }
 
-async fn index() -> Json<Value> {
+fn locations_routes(state: &AppState) -> OpenApiRouter<AppState> {
+    // Consultas públicas, cacheables igual que el catálogo de libros
+    let public_routes = OpenApiRouter::new()
+        .routes(routes!(controllers::get_locations_controller))
+        .layer(middleware::from_fn_with_state(
+            state.books.http_cache.clone(),
+            http_cache_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.rate_limits.clone(),
+            public_rate_limit_middleware,
+        ));
+
+    // Alta de sucursales, solo para clientes autenticados
+    let protected_routes = OpenApiRouter::new()
+        .routes(routes!(controllers::create_location_controller))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_key_middleware,
+        ));
+
+    public_routes.merge(protected_routes)
+}
+
+/// Las etiquetas se crean y eliminan junto con los libros, por lo que solo se consultan
+fn tags_routes(state: &AppState) -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(controllers::get_tags_controller))
+        .layer(middleware::from_fn_with_state(
+            state.books.http_cache.clone(),
+            http_cache_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.rate_limits.clone(),
+            public_rate_limit_middleware,
+        ))
+}
+
+/// Información general de la API
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiInfo {
+    pub version: String,
+    pub description: String,
+}
+
+/// Versión y descripción de la API
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = INFO_TAG,
+    responses((status = 200, description = "Información sobre la API", body = ApiInfo))
+)]
+async fn index() -> Json<ApiInfo> {
     let version = env!("CARGO_PKG_VERSION");
-    let response = json! ({
-        "version": version,
-        "description": "API Rest para gestión de biblioteca"
-    });
+    let response = ApiInfo {
+        version: version.to_string(),
+        description: tr_lang!(request_language(), "index.description"),
+    };
 
     Json(response)
 }
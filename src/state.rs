@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use axum::extract::FromRef;
+use http::HeaderValue;
+use sqlx::SqlitePool;
+
+use crate::{
+    AppError,
+    api_keys::{
+        api_keys_application::ApiKeyCache,
+        api_keys_infrastructure::{ApiKeySqliteRepository, ApiKeysState},
+    },
+    audit::audit_infrastructure::{AuditSqliteRepository, AuditState},
+    books::books_infrastructure::{BookRepositories, BookSqliteRepository, BooksState},
+    health::{HealthState, NoReadinessChecks, ReadinessCheck, SqliteReadiness},
+    metrics::Metrics,
+    rate_limit::RateLimits,
+};
+
+/// Versión del esquema de la base de datos que crea [`AppState::create_tables`].
+/// Se guarda en `PRAGMA user_version` y debe incrementarse con cada cambio en las tablas.
+pub const SCHEMA_VERSION: i64 = 10;
+
+/// Dependencias de la aplicación, compartidas por las rutas mediante `State`
+#[derive(Clone)]
+pub struct AppState {
+    pub books: BooksState,
+    pub api_keys: ApiKeysState,
+    pub audit: AuditState,
+    pub health: HealthState,
+    pub metrics: Metrics,
+    pub rate_limits: RateLimits,
+}
+
+impl AppState {
+    pub fn new(books: BooksState, api_keys: ApiKeysState, audit: AuditState) -> Self {
+        Self {
+            books,
+            api_keys,
+            audit,
+            health: HealthState::new(Arc::new(NoReadinessChecks)),
+            metrics: Metrics::new(),
+            rate_limits: RateLimits::default(),
+        }
+    }
+
+    /// Reemplaza las comprobaciones de `/health/ready`
+    pub fn with_readiness(mut self, readiness: Arc<dyn ReadinessCheck>) -> Self {
+        self.health = HealthState::new(readiness);
+        self
+    }
+
+    /// Reemplaza el registro de métricas
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Reemplaza los límites de peticiones
+    pub fn with_rate_limits(mut self, rate_limits: RateLimits) -> Self {
+        self.rate_limits = rate_limits;
+        self
+    }
+
+    /// Reemplaza la caché de ApiKeys verificadas
+    pub fn with_api_key_cache(mut self, cache: ApiKeyCache) -> Self {
+        self.api_keys = ApiKeysState::with_cache(
+            self.api_keys.repo.clone(),
+            self.api_keys.audit.clone(),
+            cache,
+        );
+        self
+    }
+
+    /// Construye el estado con repositorios SQLite sobre `pool`. Las tablas deben existir,
+    /// ver [`AppState::create_tables`].
+    pub fn sqlite(pool: SqlitePool, cache_control: HeaderValue) -> Self {
+        let audit = AuditState::new(Arc::new(AuditSqliteRepository::new(pool.clone())));
+
+        Self::new(
+            BooksState::new(
+                BookRepositories::sqlite(pool.clone()),
+                audit.log.clone(),
+                cache_control,
+            ),
+            ApiKeysState::new(
+                Arc::new(ApiKeySqliteRepository::new(pool.clone())),
+                audit.log.clone(),
+            ),
+            audit,
+        )
+        .with_readiness(Arc::new(SqliteReadiness::new(pool.clone())))
+        .with_metrics(Metrics::sqlite(pool))
+    }
+
+    /// Crea las tablas de la aplicación en `pool` si no existen
+    pub async fn create_tables(pool: &SqlitePool) -> Result<(), AppError> {
+        let api_key_repo = ApiKeySqliteRepository::new(pool.clone());
+        let book_repo = BookSqliteRepository::new(pool.clone());
+        let audit_repo = AuditSqliteRepository::new(pool.clone());
+
+        // Inicializamos las tablas en paralelo
+        let (result_api_key, result_book, result_audit) = tokio::join!(
+            api_key_repo.init_table(),
+            book_repo.init_tables(),
+            audit_repo.init_table()
+        );
+        result_api_key?;
+        result_book?;
+        result_audit?;
+
+        sqlx::query(&format!("PRAGMA user_version = {SCHEMA_VERSION}"))
+            .execute(pool)
+            .await
+            .map_err(|e| AppError::SchemaVersion(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+impl FromRef<AppState> for BooksState {
+    fn from_ref(state: &AppState) -> Self {
+        state.books.clone()
+    }
+}
+
+impl FromRef<AppState> for HealthState {
+    fn from_ref(state: &AppState) -> Self {
+        state.health.clone()
+    }
+}
+
+impl FromRef<AppState> for Metrics {
+    fn from_ref(state: &AppState) -> Self {
+        state.metrics.clone()
+    }
+}
+
+impl FromRef<AppState> for RateLimits {
+    fn from_ref(state: &AppState) -> Self {
+        state.rate_limits.clone()
+    }
+}
+
+impl FromRef<AppState> for AuditState {
+    fn from_ref(state: &AppState) -> Self {
+        state.audit.clone()
+    }
+}
+
+impl FromRef<AppState> for ApiKeysState {
+    fn from_ref(state: &AppState) -> Self {
+        state.api_keys.clone()
+    }
+}
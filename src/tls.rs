@@ -0,0 +1,154 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use axum::{
+    Router,
+    extract::Request,
+    response::{IntoResponse, Redirect},
+};
+use axum_server::tls_rustls::RustlsConfig;
+use http::{StatusCode, Uri, header::HOST, uri::Authority};
+use tracing::{info, warn};
+
+use crate::{AppError, i18n::tr};
+
+/// Cada cuánto se revisa si los certificados cambiaron en disco
+pub const CERT_RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Rutas del certificado y la llave privada en formato PEM
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsPaths {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
+
+impl TlsPaths {
+    /// Carga el certificado y la llave para servir HTTPS
+    pub async fn load(&self) -> io::Result<RustlsConfig> {
+        // rustls necesita un proveedor criptográfico; si ya hay uno instalado se conserva
+        let _ = rustls::crypto::ring::default_provider().install_default();
+
+        RustlsConfig::from_pem_file(&self.cert, &self.key).await
+    }
+
+    /// Recarga `config` cada vez que el certificado o la llave cambian en disco.
+    /// Si los archivos nuevos no son válidos se conserva el certificado anterior.
+    pub async fn watch(self, config: RustlsConfig, interval: Duration) {
+        let mut last_modified = self.modified();
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            let modified = self.modified();
+            if modified == last_modified {
+                continue;
+            }
+
+            match config.reload_from_pem_file(&self.cert, &self.key).await {
+                Ok(()) => {
+                    info!("{}", tr!("log.tls_reloaded", path = self.cert.display()));
+                    last_modified = modified;
+                }
+                // Se reintenta en el siguiente ciclo, por ejemplo si solo se ha escrito uno de los archivos
+                Err(e) => warn!("{}", tr!("log.tls_reload_failed", error = e)),
+            }
+        }
+    }
+
+    fn modified(&self) -> Option<(SystemTime, SystemTime)> {
+        let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+
+        Some((modified(&self.cert)?, modified(&self.key)?))
+    }
+}
+
+/// Valida que el certificado y la llave se configuren juntos
+pub fn tls_paths(
+    cert: Option<PathBuf>,
+    key: Option<PathBuf>,
+) -> Result<Option<TlsPaths>, AppError> {
+    match (cert, key) {
+        (Some(cert), Some(key)) => Ok(Some(TlsPaths { cert, key })),
+        (None, None) => Ok(None),
+        _ => Err(AppError::EnvVarLoad(
+            "TLS_CERT_PATH y TLS_KEY_PATH deben definirse juntos".to_string(),
+        )),
+    }
+}
+
+/// Router que redirige cualquier petición HTTP a la misma ruta en HTTPS en `https_port`
+pub fn redirect_router(https_port: u16) -> Router {
+    Router::new().fallback(move |req: Request| async move {
+        match https_uri(&req, https_port) {
+            Some(uri) => Redirect::permanent(&uri.to_string()).into_response(),
+            None => StatusCode::BAD_REQUEST.into_response(),
+        }
+    })
+}
+
+fn https_uri(req: &Request, https_port: u16) -> Option<Uri> {
+    let host = req
+        .headers()
+        .get(HOST)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<Authority>().ok())
+        .or_else(|| req.uri().authority().cloned())?;
+
+    let authority = if https_port == 443 {
+        host.host().to_string()
+    } else {
+        format!("{}:{https_port}", host.host())
+    };
+
+    Uri::builder()
+        .scheme("https")
+        .authority(authority)
+        .path_and_query(
+            req.uri()
+                .path_and_query()
+                .map(|pq| pq.as_str())
+                .unwrap_or("/"),
+        )
+        .build()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+
+    use super::*;
+
+    fn request(host: &str, uri: &str) -> Request {
+        Request::builder()
+            .uri(uri)
+            .header(HOST, host)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[test]
+    fn https_uri_works() {
+        let uri = https_uri(&request("example.com:8080", "/books?page=2"), 8443);
+        assert_eq!(
+            uri.unwrap().to_string(),
+            "https://example.com:8443/books?page=2"
+        );
+
+        let uri = https_uri(&request("example.com", "/"), 443);
+        assert_eq!(uri.unwrap().to_string(), "https://example.com/");
+
+        assert!(https_uri(&request("no valid host", "/"), 443).is_none());
+    }
+
+    #[test]
+    fn tls_paths_must_be_paired() {
+        assert_eq!(tls_paths(None, None).unwrap(), None);
+        assert!(tls_paths(Some("cert.pem".into()), Some("key.pem".into())).is_ok());
+        assert!(tls_paths(Some("cert.pem".into()), None).is_err());
+    }
+}
@@ -1,15 +1,149 @@
 #[cfg(feature = "integration-tests")]
 mod tests {
+    use std::{
+        io,
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use axum::{
+        Router,
+        extract::{Request, connect_info::MockConnectInfo},
+        middleware::{self, Next},
+        routing::get,
+    };
+    use axum_server::Handle;
     use axum_test::TestServer;
-    use http::header::AUTHORIZATION;
+    use http::{HeaderValue, header::AUTHORIZATION};
     use library_api::{
-        api_keys::api_keys_application::ApiKeyCreationService,
-        books::books_infrastructure::controllers::BookId, server::routes,
+        LibraryApi, ProblemDetails,
+        api_keys::api_keys_domain::ApiKeyScope,
+        audit::audit_domain::{Actor, AuditAction, PaginatedAuditEntries},
+        books::{
+            BookError,
+            books_domain::{
+                Author, AuthorWithBooks, Book, BookAvailability, BookFilters, BookHistory,
+                BookRepository, BookRevision, BookSearchResults, CatalogVersion, DeletedFilter,
+                Location, PaginatedAuthors, PaginatedBooks, PaginatedLocations,
+                PaginatedPublishers, PaginatedSubjects, PaginatedTags, Publisher, ReadBookCriteria,
+                ReadMultipleBooksCriteria, StockMovement,
+            },
+            books_infrastructure::{
+                BookRepositories, BooksState, DEFAULT_CACHE_CONTROL, controllers::BookId,
+            },
+        },
+        health::{BuildInfo, Readiness},
+        init::{LogFormat, log_subscriber, serve, serve_tls},
+        rate_limit::{RateLimit, RateLimitConfig, RateLimits},
+        server::{openapi, routes},
+        state::AppState,
+        tls::{TlsPaths, redirect_router},
     };
+    use sqlx::SqlitePool;
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    /// Estado aislado sobre una base de datos en memoria propia de cada prueba
+    async fn test_state() -> AppState {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .expect("Error al crear base de datos en memoria");
+
+        AppState::create_tables(&pool)
+            .await
+            .expect("Error al crear las tablas");
+
+        AppState::sqlite(pool, HeaderValue::from_static(DEFAULT_CACHE_CONTROL))
+    }
+
+    /// Repositorio que siempre falla, para simular una base de datos no disponible
+    struct UnavailableBookRepository;
+
+    #[async_trait::async_trait]
+    impl BookRepository for UnavailableBookRepository {
+        async fn find(
+            &self,
+            _: ReadBookCriteria,
+            _: DeletedFilter,
+        ) -> Result<Option<Book>, BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+
+        async fn find_multiple(
+            &self,
+            _: ReadMultipleBooksCriteria,
+            _: DeletedFilter,
+            _: u32,
+            _: u8,
+        ) -> Result<PaginatedBooks, BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+
+        async fn create(&self, _: Book) -> Result<(), BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+
+        async fn create_with_revision(
+            &self,
+            _: Book,
+            _: &Actor,
+            _: OffsetDateTime,
+        ) -> Result<u32, BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+
+        async fn update(&self, _: Book) -> Result<(), BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+
+        async fn update_with_revision(
+            &self,
+            _: Book,
+            _: &Actor,
+            _: OffsetDateTime,
+        ) -> Result<u32, BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+
+        async fn delete(&self, _: &Uuid, _: OffsetDateTime) -> Result<(), BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+
+        async fn restore(&self, _: &Uuid) -> Result<(), BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+
+        async fn purge(&self, _: OffsetDateTime) -> Result<Vec<Book>, BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+
+        async fn revisions(&self, _: &Uuid) -> Result<Vec<BookRevision>, BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+
+        async fn find_revision(&self, _: &Uuid, _: u32) -> Result<Option<BookRevision>, BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+
+        async fn search(
+            &self,
+            _: ReadMultipleBooksCriteria,
+            _: BookFilters,
+            _: u32,
+            _: u8,
+        ) -> Result<BookSearchResults, BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+
+        async fn catalog_version(&self) -> Result<CatalogVersion, BookError> {
+            Err(BookError::DatabaseError("no disponible".into()))
+        }
+    }
 
     #[tokio::test]
     async fn home_works() {
-        let app = routes().await;
+        let app = routes(test_state().await);
 
         let server = TestServer::new(app).expect("Error al crear servidor de prueba");
 
@@ -17,9 +151,132 @@ mod tests {
         assert_eq!(response.status_code(), http::StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn health_endpoints_work() {
+        let server =
+            TestServer::new(routes(test_state().await)).expect("Error al crear servidor de prueba");
+
+        let response = server.get("/health/live").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        assert_eq!(response.header(http::header::CACHE_CONTROL), "no-store");
+
+        let response = server.get("/health/ready").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        let readiness = response.json::<Readiness>();
+        assert!(readiness.ready);
+        assert!(readiness.checks.iter().any(|check| check.name == "schema"));
+
+        let info = server.get("/info").await.json::<BuildInfo>();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(!info.git_hash.is_empty());
+        assert!(info.features.contains(&"integration-tests".to_string()));
+    }
+
+    #[tokio::test]
+    async fn readiness_fails_without_database() {
+        // Base de datos sin tablas: el esquema no está al día
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .expect("Error al crear base de datos en memoria");
+        let state = AppState::sqlite(
+            pool.clone(),
+            HeaderValue::from_static(DEFAULT_CACHE_CONTROL),
+        );
+        let server = TestServer::new(routes(state)).expect("Error al crear servidor de prueba");
+
+        let response = server.get("/health/ready").await;
+        assert_eq!(
+            response.status_code(),
+            http::StatusCode::SERVICE_UNAVAILABLE
+        );
+        let readiness = response.json::<Readiness>();
+        assert!(!readiness.ready);
+        assert!(
+            readiness
+                .checks
+                .iter()
+                .any(|check| check.name == "schema" && !check.ok)
+        );
+
+        // Base de datos inaccesible
+        pool.close().await;
+
+        let response = server.get("/health/ready").await;
+        assert_eq!(
+            response.status_code(),
+            http::StatusCode::SERVICE_UNAVAILABLE
+        );
+        let readiness = response.json::<Readiness>();
+        assert!(
+            readiness
+                .checks
+                .iter()
+                .any(|check| check.name == "database" && !check.ok)
+        );
+
+        // El proceso sigue vivo aunque no esté listo
+        let response = server.get("/health/live").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+    }
+
+    /// Destino de logs en memoria, para inspeccionarlos en las pruebas
+    #[derive(Clone, Default)]
+    struct LogBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for LogBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl LogBuffer {
+        fn lines(&self) -> Vec<serde_json::Value> {
+            String::from_utf8(self.0.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).expect("Cada línea debe ser JSON"))
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn logs_include_request_id() {
+        let buffer = LogBuffer::default();
+        let writer = buffer.clone();
+        let subscriber = log_subscriber("info", LogFormat::Json, move || writer.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let server =
+            TestServer::new(routes(test_state().await)).expect("Error al crear servidor de prueba");
+        let response = server
+            .get("/books/search?title=Rayuela&page=1&page_size=10")
+            .add_header(http::HeaderName::from_static("x-request-id"), "busqueda-42")
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+
+        let lines = buffer.lines();
+        // Log del caso de uso y trazas de inicio y fin de la petición
+        let request_lines: Vec<_> = lines
+            .iter()
+            .filter(|line| line["span"]["request_id"] == "busqueda-42")
+            .collect();
+        assert!(request_lines.len() >= 3, "{lines:?}");
+        assert!(
+            request_lines
+                .iter()
+                .any(|line| line["message"].as_str().unwrap().contains("Rayuela")),
+            "{lines:?}"
+        );
+    }
+
     #[tokio::test]
     async fn books_works() {
-        let app = routes().await;
+        let app = routes(test_state().await);
 
         let server = TestServer::new(app).expect("Error al crear servidor de prueba");
 
@@ -29,7 +286,7 @@ mod tests {
 
     #[tokio::test]
     async fn books_pagination_works() {
-        let app = routes().await;
+        let app = routes(test_state().await);
 
         let server = TestServer::new(app).expect("Error al crear servidor de prueba");
 
@@ -37,13 +294,245 @@ mod tests {
         assert_eq!(response.status_code(), http::StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn books_conditional_get_works() {
+        let app = routes(test_state().await);
+
+        let server = TestServer::new(app).expect("Error al crear servidor de prueba");
+
+        let response = server.get("/books").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        assert!(response.headers().contains_key(http::header::CACHE_CONTROL));
+        assert!(response.headers().contains_key(http::header::LAST_MODIFIED));
+
+        let etag = response.header(http::header::ETAG);
+
+        let response = server
+            .get("/books/search?title=Quijote&page=1&page_size=10")
+            .add_header(http::header::IF_NONE_MATCH, etag)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::NOT_MODIFIED);
+    }
+
+    #[tokio::test]
+    async fn openapi_matches_routes() {
+        let state = test_state().await;
+        let app = routes(state.clone());
+
+        let server = TestServer::new(app).expect("Error al crear servidor de prueba");
+
+        let response = server.get("/openapi.json").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+
+        let spec = response.json::<serde_json::Value>();
+        assert_eq!(spec, serde_json::to_value(openapi(&state)).unwrap());
+
+        // Cada operación documentada debe llegar a un controlador
+        let paths = spec["paths"]
+            .as_object()
+            .expect("El documento no tiene rutas");
+        for (path, item) in paths {
+            let uri = path.replace("{id}", &Uuid::new_v4().to_string());
+
+            for method in item.as_object().unwrap().keys() {
+                let Ok(method) = http::Method::from_bytes(method.to_uppercase().as_bytes()) else {
+                    continue;
+                };
+
+                let response = server.method(method.clone(), &uri).await;
+                let status = response.status_code();
+
+                assert_ne!(
+                    status,
+                    http::StatusCode::METHOD_NOT_ALLOWED,
+                    "{method} {path}"
+                );
+                if status == http::StatusCode::NOT_FOUND {
+                    let problem = response.json::<ProblemDetails>();
+                    assert_ne!(problem.code, "app.route_not_found", "{method} {path}");
+                }
+            }
+        }
+
+        let response = server.get("/docs/").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        assert!(response.text().contains("swagger-ui"));
+    }
+
+    #[tokio::test]
+    async fn error_responses_are_problem_details() {
+        let state = test_state().await;
+        let app = routes(state.clone());
+
+        let server = TestServer::new(app).expect("Error al crear servidor de prueba");
+
+        let response = server
+            .get("/books/not-a-book")
+            .add_header("x-request-id", "kiosk-01")
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            response.header(http::header::CONTENT_TYPE),
+            "application/problem+json"
+        );
+        assert_eq!(response.header("x-request-id"), "kiosk-01");
+
+        let problem = response.json::<ProblemDetails>();
+        assert_eq!(problem.code, "auth.missing_credentials");
+        assert_eq!(problem.request_id.as_deref(), Some("kiosk-01"));
+
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+
+        let response = server
+            .post("/books")
+            .add_header(AUTHORIZATION, format!("ApiKey {}", raw_api_key))
+            .json(&serde_json::json!({
+                "title": "",
+                "authors": ["Miguel de Cervantes"],
+                "publisher": "Fondo de Cultura Económica",
+                "year": 2000,
+                "isbn": "no-isbn",
+                "stored_quantity": 2
+            }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+
+        let problem = response.json::<ProblemDetails>();
+        assert_eq!(problem.code, "book.validation");
+        assert!(problem.request_id.is_some());
+
+        let fields: Vec<&str> = problem.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["isbn", "title"]);
+    }
+
+    #[tokio::test]
+    async fn error_messages_follow_accept_language() {
+        let app = routes(test_state().await);
+
+        let server = TestServer::new(app).expect("Error al crear servidor de prueba");
+
+        let response = server
+            .get("/unknown")
+            .add_header(http::header::ACCEPT_LANGUAGE, "en-US,en;q=0.9,es;q=0.8")
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::NOT_FOUND);
+        assert_eq!(response.header(http::header::CONTENT_LANGUAGE), "en");
+
+        let problem = response.json::<ProblemDetails>();
+        assert_eq!(problem.code, "app.route_not_found");
+        assert_eq!(problem.detail, "Route not found");
+
+        let response = server
+            .get("/unknown")
+            .add_header(http::header::ACCEPT_LANGUAGE, "es")
+            .await;
+        let problem = response.json::<ProblemDetails>();
+        assert_eq!(problem.code, "app.route_not_found");
+        assert_eq!(problem.detail, "Ruta no encontrada");
+    }
+
+    #[tokio::test]
+    async fn wrong_api_key_is_unauthorized() {
+        let state = test_state().await;
+        let app = routes(state.clone());
+
+        let server = TestServer::new(app).expect("Error al crear servidor de prueba");
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let wrong_api_key = format!("LibraryApi_{}_{}", raw_api_key.id(), "x".repeat(25));
+
+        let response = server
+            .get("/books/00000000-0000-0000-0000-000000000000")
+            .add_header(AUTHORIZATION, format!("ApiKey {wrong_api_key}"))
+            .await;
+
+        assert_eq!(response.status_code(), http::StatusCode::UNAUTHORIZED);
+        assert_eq!(response.json::<ProblemDetails>().code, "api_key.invalid");
+        assert_eq!(response.header(http::header::WWW_AUTHENTICATE), "ApiKey");
+    }
+
+    #[tokio::test]
+    async fn metrics_are_exported() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let api_key = format!(
+            "ApiKey {}",
+            state
+                .api_keys
+                .creation
+                .create(&Actor::cli())
+                .await
+                .expect("Error al genera API Key")
+        );
+
+        let response = server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&serde_json::json!({
+                "title": "Rayuela",
+                "authors": ["Julio Cortázar"],
+                "publisher": "Sudamericana",
+                "year": 1963,
+                "isbn": "978-84-376-0494-7",
+                "stored_quantity": 1
+            }))
+            .await;
+        let book_id = response.json::<BookId>();
+        server
+            .get(&format!("/books/{}", book_id.id))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await;
+        server.get(&format!("/books/{}", book_id.id)).await;
+        server.get("/no-existe").await;
+
+        let response = server.get("/metrics").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        assert!(
+            response
+                .header(http::header::CONTENT_TYPE)
+                .to_str()
+                .unwrap()
+                .starts_with("text/plain")
+        );
+
+        let text = response.text();
+        for expected in [
+            r#"library_api_http_requests_total{method="POST",route="/books",status="201"} 1"#,
+            r#"library_api_http_requests_total{method="GET",route="/books/{id}",status="200"} 1"#,
+            r#"library_api_http_requests_total{method="GET",route="/books/{id}",status="401"} 1"#,
+            r#"library_api_http_requests_total{method="GET",route="unmatched",status="404"} 1"#,
+            r#"library_api_api_key_auth_failures_total{reason="auth.missing_credentials"} 1"#,
+            "library_api_books_created_total 1",
+            "library_api_books_deleted_total 0",
+            "library_api_db_pool_max_connections",
+        ] {
+            assert!(text.contains(expected), "falta {expected} en:\n{text}");
+        }
+        assert!(text.contains("library_api_http_request_duration_seconds_bucket"));
+        // La ruta es el patrón, no la URI con el identificador
+        assert!(!text.contains(&book_id.id.to_string()));
+    }
+
     #[tokio::test]
     async fn book_lifecycle_works() {
-        let app = routes().await;
+        let state = test_state().await;
+        let app = routes(state.clone());
 
         let server = TestServer::new(app).expect("Error al crear servidor de prueba");
-        let raw_api_key = ApiKeyCreationService::get_instance()
-            .create()
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
             .await
             .expect("Error al genera API Key");
         let api_key = format!("ApiKey {}", raw_api_key);
@@ -65,6 +554,21 @@ mod tests {
 
         let book_id = response.json::<BookId>();
 
+        let response = server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&serde_json::json!({
+                "title": "El Quijote",
+                "authors": ["Miguel de Cervantes"],
+                "publisher": "Fondo de Cultura Económica",
+                "year": 2000,
+                "isbn": "978-84-376-0000-0",
+                "stored_quantity": 1
+            }))
+            .await;
+
+        assert_eq!(response.status_code(), http::StatusCode::CONFLICT);
+
         let response = server
             .get(&format!("/books/{}", book_id.id))
             .add_header(AUTHORIZATION, api_key.clone())
@@ -94,4 +598,1544 @@ mod tests {
 
         assert_eq!(response.status_code(), http::StatusCode::NO_CONTENT);
     }
+
+    #[tokio::test]
+    async fn book_changes_are_audited() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let api_key = format!("ApiKey {raw_api_key}");
+        let book = |stored_quantity: u32| {
+            serde_json::json!({
+                "title": "Rayuela",
+                "authors": ["Julio Cortázar"],
+                "publisher": "Sudamericana",
+                "year": 1963,
+                "isbn": "978-84-376-0494-7",
+                "stored_quantity": stored_quantity
+            })
+        };
+
+        let book_id = server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&book(1))
+            .await
+            .json::<BookId>()
+            .id;
+        let path = format!("/books/{book_id}");
+        server
+            .put(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&book(4))
+            .await
+            .assert_status(http::StatusCode::NO_CONTENT);
+        server
+            .delete(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await
+            .assert_status(http::StatusCode::NO_CONTENT);
+
+        // La consulta de la auditoría requiere autenticación
+        let response = server.get("/audit").await;
+        assert_eq!(response.status_code(), http::StatusCode::UNAUTHORIZED);
+
+        let response = server
+            .get(&format!("/audit?entity_id={book_id}"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+
+        let log = response.json::<PaginatedAuditEntries>();
+        let actions: Vec<_> = log.entries.iter().map(|entry| entry.action).collect();
+        assert_eq!(
+            actions,
+            [
+                AuditAction::BookDeleted,
+                AuditAction::BookUpdated,
+                AuditAction::BookCreated
+            ]
+        );
+        let actor = Actor::api_key(raw_api_key.id());
+        assert!(log.entries.iter().all(|entry| entry.actor == actor));
+
+        let (deleted, updated, created) = (&log.entries[0], &log.entries[1], &log.entries[2]);
+        assert_eq!(created.before, None);
+        assert_eq!(created.after.as_ref().unwrap()["stored_quantity"], 1);
+        assert_eq!(updated.before.as_ref().unwrap()["stored_quantity"], 1);
+        assert_eq!(updated.after.as_ref().unwrap()["stored_quantity"], 4);
+        assert_eq!(deleted.before.as_ref().unwrap()["stored_quantity"], 4);
+        assert!(deleted.after.as_ref().unwrap()["deleted_at"].is_string());
+        assert!(created.request_id.is_some());
+
+        // La creación de la ApiKey también quedó registrada
+        let response = server
+            .get("/audit?action=api_key_created")
+            .add_header(AUTHORIZATION, api_key)
+            .await;
+        let log = response.json::<PaginatedAuditEntries>();
+        assert_eq!(log.total, 1);
+        assert_eq!(log.entries[0].actor, Actor::cli());
+    }
+
+    #[tokio::test]
+    async fn deleted_books_can_be_restored() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let api_key = format!("ApiKey {raw_api_key}");
+        let book = serde_json::json!({
+            "title": "Pedro Páramo",
+            "authors": ["Juan Rulfo"],
+            "publisher": "Fondo de Cultura Económica",
+            "year": 1955,
+            "isbn": "978-84-376-0495-4",
+            "stored_quantity": 2
+        });
+
+        let book_id = server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&book)
+            .await
+            .json::<BookId>()
+            .id;
+        let path = format!("/books/{book_id}");
+        server
+            .delete(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await
+            .assert_status(http::StatusCode::NO_CONTENT);
+
+        // El libro eliminado no aparece en el catálogo ni puede eliminarse otra vez
+        let response = server.get("/books").await;
+        assert_eq!(response.json::<PaginatedBooks>().total, 0);
+        let response = server
+            .get(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::NOT_FOUND);
+        let response = server
+            .delete(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::NOT_FOUND);
+
+        // Su ISBN sigue reservado hasta que se purgue
+        let response = server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&book)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::CONFLICT);
+
+        let response = server
+            .get(&format!("{path}?include_deleted=true"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        assert!(response.json::<Book>().deleted_at.is_some());
+
+        let response = server
+            .get("/books/deleted")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await;
+        assert_eq!(response.json::<PaginatedBooks>().books[0].id, book_id);
+
+        let response = server.post(&format!("{path}/restore")).await;
+        assert_eq!(response.status_code(), http::StatusCode::UNAUTHORIZED);
+
+        let response = server
+            .post(&format!("{path}/restore"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        assert_eq!(response.json::<Book>().deleted_at, None);
+
+        let response = server.get("/books").await;
+        assert_eq!(response.json::<PaginatedBooks>().total, 1);
+
+        let response = server
+            .get(&format!("/audit?entity_id={book_id}&action=book_restored"))
+            .add_header(AUTHORIZATION, api_key)
+            .await;
+        assert_eq!(response.json::<PaginatedAuditEntries>().total, 1);
+    }
+
+    #[tokio::test]
+    async fn book_history_can_be_reverted() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let api_key = format!("ApiKey {raw_api_key}");
+        let mut book = serde_json::json!({
+            "title": "Pedro Páramo",
+            "authors": ["Juan Rulfo"],
+            "publisher": "Fondo de Cultura Económica",
+            "year": 1955,
+            "isbn": "978-84-376-0495-4",
+            "stored_quantity": 2
+        });
+
+        let book_id = server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&book)
+            .await
+            .json::<BookId>()
+            .id;
+        let path = format!("/books/{book_id}");
+
+        book["stored_quantity"] = 5.into();
+        server
+            .put(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&book)
+            .await
+            .assert_status(http::StatusCode::NO_CONTENT);
+        book["title"] = "El llano en llamas".into();
+        book["isbn"] = "978-0-306-40615-7".into();
+        server
+            .put(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&book)
+            .await
+            .assert_status(http::StatusCode::NO_CONTENT);
+
+        let response = server.get(&format!("{path}/history")).await;
+        assert_eq!(response.status_code(), http::StatusCode::UNAUTHORIZED);
+
+        let response = server
+            .get(&format!("{path}/history"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        let history = response.json::<BookHistory>();
+        assert_eq!(history.book_id, book_id);
+        assert_eq!(history.revisions.len(), 3);
+        assert!(history.revisions[0].changes.is_empty());
+        let fields = |index: usize| {
+            history.revisions[index]
+                .changes
+                .iter()
+                .map(|change| change.field.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(fields(1), ["stored_quantity"]);
+        assert_eq!(fields(2), ["isbn", "title"]);
+
+        // Revertir guarda una nueva revisión con los datos de la primera
+        let response = server
+            .post(&format!("{path}/revert/1"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        let reverted = response.json::<Book>();
+        assert_eq!(reverted.title.to_string(), "Pedro Páramo");
+        assert_eq!(reverted.stored_quantity, 2);
+
+        let history = server
+            .get(&format!("{path}/history"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await
+            .json::<BookHistory>();
+        assert_eq!(history.revisions.len(), 4);
+        assert_eq!(history.revisions[3].revision.revision, 4);
+        assert_eq!(history.revisions[3].changes.len(), 3);
+
+        let response = server
+            .post(&format!("{path}/revert/9"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::NOT_FOUND);
+
+        let response = server
+            .post(&format!("{path}/revert/latest"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+        let problem = response.json::<ProblemDetails>();
+        assert_eq!(problem.code, "book.invalid_revision");
+
+        // La revisión 3 usa un ISBN que ahora pertenece a otro libro
+        book["title"] = "Ficciones".into();
+        server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&book)
+            .await
+            .assert_status(http::StatusCode::CREATED);
+        let response = server
+            .post(&format!("{path}/revert/3"))
+            .add_header(AUTHORIZATION, api_key)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn authors_can_be_renamed_and_merged() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let api_key = format!("ApiKey {raw_api_key}");
+
+        for (title, authors, isbn) in [
+            (
+                "Cien años de soledad",
+                vec!["G. G. Márquez"],
+                "978-84-376-0495-4",
+            ),
+            (
+                "El amor en los tiempos del cólera",
+                vec!["Gabriel García Márquez"],
+                "978-0-306-40615-7",
+            ),
+            (
+                "Crónica de una muerte anunciada",
+                vec!["G. G. Márquez", "Gabriel García Márquez"],
+                "978-3-16-148410-0",
+            ),
+        ] {
+            server
+                .post("/books")
+                .add_header(AUTHORIZATION, api_key.clone())
+                .json(&serde_json::json!({
+                    "title": title,
+                    "authors": authors,
+                    "publisher": "Sudamericana",
+                    "year": 1967,
+                    "isbn": isbn,
+                    "stored_quantity": 1
+                }))
+                .await
+                .assert_status(http::StatusCode::CREATED);
+        }
+
+        let response = server.get("/authors?name=rquez").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        let authors = response.json::<PaginatedAuthors>();
+        assert_eq!(authors.total, 2);
+        let duplicate = authors.authors[0].clone();
+        let target = authors.authors[1].clone();
+        assert_eq!(duplicate.name.as_str(), "G. G. Márquez");
+        assert_eq!((duplicate.book_count, target.book_count), (2, 2));
+
+        // Renombrar a un nombre existente obliga a fusionar
+        let path = format!("/authors/{}", duplicate.id);
+        let response = server
+            .put(&path)
+            .json(&serde_json::json!({ "name": "Gabriel García Márquez" }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::UNAUTHORIZED);
+        let response = server
+            .put(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&serde_json::json!({ "name": "Gabriel García Márquez" }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::CONFLICT);
+
+        let response = server
+            .put(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&serde_json::json!({ "name": "Gabo" }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        assert_eq!(response.json::<Author>().name.as_str(), "Gabo");
+
+        let response = server
+            .get("/books/search?author=Gabo&page=1&page_size=10")
+            .await;
+        assert_eq!(response.json::<PaginatedBooks>().total, 2);
+
+        let response = server
+            .post(&format!("{path}/merge"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&serde_json::json!({ "into": duplicate.id }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+
+        let response = server
+            .post(&format!("{path}/merge"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&serde_json::json!({ "into": target.id }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        let merged = response.json::<Author>();
+        assert_eq!(merged.id, target.id);
+        assert_eq!(merged.book_count, 3);
+
+        let response = server.get(&path).await;
+        assert_eq!(response.status_code(), http::StatusCode::NOT_FOUND);
+
+        let response = server.get(&format!("/authors/{}", target.id)).await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        let author = response.json::<AuthorWithBooks>();
+        assert_eq!(author.books.len(), 3);
+        assert!(
+            author
+                .books
+                .iter()
+                .all(|book| book.authors == [target.name.clone().into()])
+        );
+
+        let response = server.get("/authors/abc").await;
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+
+        let response = server
+            .get(&format!(
+                "/audit?entity_id={}&entity_type=author",
+                target.id
+            ))
+            .add_header(AUTHORIZATION, api_key)
+            .await;
+        assert_eq!(response.json::<PaginatedAuditEntries>().total, 1);
+    }
+
+    #[tokio::test]
+    async fn publishers_are_normalized_and_merged() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let api_key = format!("ApiKey {raw_api_key}");
+
+        for (publisher, isbn) in [
+            ("Editorial Planeta", "978-84-376-0495-4"),
+            ("EDITORIAL PLANETA", "978-0-306-40615-7"),
+            ("Ed. Planeta", "978-3-16-148410-0"),
+        ] {
+            server
+                .post("/books")
+                .add_header(AUTHORIZATION, api_key.clone())
+                .json(&serde_json::json!({
+                    "title": format!("Libro {isbn}"),
+                    "authors": ["Autor"],
+                    "publisher": publisher,
+                    "year": 2001,
+                    "isbn": isbn,
+                    "stored_quantity": 1
+                }))
+                .await
+                .assert_status(http::StatusCode::CREATED);
+        }
+
+        let response = server.get("/publishers?name=PLANETA").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        let publishers = response.json::<PaginatedPublishers>().publishers;
+        let names = publishers
+            .iter()
+            .map(|publisher| (publisher.name.as_str(), publisher.book_count))
+            .collect::<Vec<_>>();
+        assert_eq!(names, [("Ed. Planeta", 1), ("Editorial Planeta", 2)]);
+
+        let path = format!("/publishers/{}/merge", publishers[0].id);
+        let body = serde_json::json!({ "into": publishers[1].id });
+        let response = server.post(&path).json(&body).await;
+        assert_eq!(response.status_code(), http::StatusCode::UNAUTHORIZED);
+
+        let response = server
+            .post(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&body)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        assert_eq!(response.json::<Publisher>().book_count, 3);
+
+        let response = server
+            .post(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&body)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::NOT_FOUND);
+
+        let books = server.get("/books").await.json::<PaginatedBooks>().books;
+        assert!(
+            books
+                .iter()
+                .all(|book| book.publisher.as_str() == "Editorial Planeta")
+        );
+
+        let response = server
+            .get("/audit?action=publishers_merged")
+            .add_header(AUTHORIZATION, api_key)
+            .await;
+        assert_eq!(response.json::<PaginatedAuditEntries>().total, 1);
+    }
+
+    #[tokio::test]
+    async fn contributors_keep_order_and_roles() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+
+        let response = server
+            .post("/books")
+            .add_header(AUTHORIZATION, format!("ApiKey {raw_api_key}"))
+            .json(&serde_json::json!({
+                "title": "Ficciones",
+                "authors": [
+                    "Jorge Luis Borges",
+                    { "name": "Anthony Kerrigan", "role": "translator" },
+                    { "name": "Alastair Reid", "role": "translator" }
+                ],
+                "publisher": "Grove Press",
+                "year": 1962,
+                "isbn": "978-0-306-40615-7",
+                "stored_quantity": 1
+            }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::CREATED);
+        let book_id = response.json::<BookId>();
+
+        let response = server
+            .get(&format!("/books/{}", book_id.id))
+            .add_header(AUTHORIZATION, format!("ApiKey {raw_api_key}"))
+            .await;
+        assert_eq!(
+            response.json::<serde_json::Value>()["authors"],
+            serde_json::json!([
+                { "name": "Jorge Luis Borges", "role": "author" },
+                { "name": "Anthony Kerrigan", "role": "translator" },
+                { "name": "Alastair Reid", "role": "translator" }
+            ])
+        );
+
+        for (query, expected) in [
+            ("author=Reid", 1),
+            ("author=Reid&role=translator", 1),
+            ("author=Reid&role=author", 0),
+            ("role=translator", 1),
+            ("role=illustrator", 0),
+        ] {
+            let response = server
+                .get(&format!("/books/search?{query}&page=1&page_size=10"))
+                .await;
+            assert_eq!(response.status_code(), http::StatusCode::OK);
+            assert_eq!(response.json::<PaginatedBooks>().total, expected, "{query}");
+        }
+
+        let response = server
+            .get("/books/search?role=narrator&page=1&page_size=10")
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn bibliographic_details_are_optional_and_validated() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let api_key = format!("ApiKey {raw_api_key}");
+        let book = serde_json::json!({
+            "title": "Pedro Páramo",
+            "authors": ["Juan Rulfo"],
+            "publisher": "Fondo de Cultura Económica",
+            "year": 1955,
+            "isbn": "978-0-306-40615-7",
+            "stored_quantity": 1
+        });
+
+        let mut invalid = book.clone();
+        invalid["language"] = "castellano".into();
+        invalid["cover_url"] = "ftp://example.com/portada.jpg".into();
+        let response = server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&invalid)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+        let fields = response
+            .json::<ProblemDetails>()
+            .errors
+            .into_iter()
+            .map(|error| error.field)
+            .collect::<Vec<_>>();
+        assert_eq!(fields, ["language"]);
+
+        let mut details = book.clone();
+        details["subtitle"] = "Novela".into();
+        details["language"] = "ES".into();
+        details["page_count"] = 124.into();
+        details["format"] = "paperback".into();
+        details["cover_url"] = "https://example.com/portada.jpg".into();
+        let response = server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&details)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::CREATED);
+        let book_id = response.json::<BookId>();
+
+        let path = format!("/books/{}", book_id.id);
+        let found = server
+            .get(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await
+            .json::<serde_json::Value>();
+        assert_eq!(found["language"], "es");
+        assert_eq!(found["page_count"], 124);
+        assert_eq!(found["format"], "paperback");
+        assert!(found.get("series").is_none());
+
+        // Un PUT sin los campos opcionales los deja vacíos
+        let response = server
+            .put(&path)
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&book)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::NO_CONTENT);
+        let updated = server
+            .get(&path)
+            .add_header(AUTHORIZATION, api_key)
+            .await
+            .json::<Book>();
+        assert!(updated.subtitle.is_none() && updated.format.is_none());
+    }
+
+    #[tokio::test]
+    async fn subjects_tags_and_facets_work() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let api_key = format!("ApiKey {raw_api_key}");
+        let book = serde_json::json!({
+            "title": "Fundación",
+            "authors": ["Isaac Asimov"],
+            "publisher": "Gnome Press",
+            "year": 1951,
+            "isbn": "978-0-306-40615-7",
+            "stored_quantity": 1,
+            "language": "en",
+            "subjects": ["Ciencia ficción"],
+            "tags": ["imperio galáctico"]
+        });
+
+        let response = server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&book)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+
+        let subject = serde_json::json!({ "name": "Ciencia ficción" });
+        let response = server.post("/subjects").json(&subject).await;
+        assert_eq!(response.status_code(), http::StatusCode::UNAUTHORIZED);
+        let response = server
+            .post("/subjects")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&subject)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::CREATED);
+        let response = server
+            .post("/subjects")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&serde_json::json!({ "name": "CIENCIA FICCION" }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::CONFLICT);
+
+        for (isbn, year) in [("978-0-306-40615-7", 1951), ("978-3-16-148410-0", 1982)] {
+            let mut book = book.clone();
+            book["isbn"] = isbn.into();
+            book["year"] = year.into();
+            server
+                .post("/books")
+                .add_header(AUTHORIZATION, api_key.clone())
+                .json(&book)
+                .await
+                .assert_status(http::StatusCode::CREATED);
+        }
+
+        let subjects = server
+            .get("/subjects?name=ficcion")
+            .await
+            .json::<PaginatedSubjects>();
+        assert_eq!(subjects.subjects[0].book_count, 2);
+        let tags = server.get("/tags").await.json::<PaginatedTags>();
+        assert_eq!(tags.tags[0].name.as_str(), "imperio galáctico");
+
+        let response = server
+            .get("/books/search?page=1&page_size=10&subject=ciencia%20ficcion&decade=1950")
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        let results = response.json::<BookSearchResults>();
+        assert_eq!(results.results.total, 1);
+        assert_eq!(results.results.books[0].tags.len(), 1);
+        assert_eq!(results.facets.decades[0].value, "1950");
+        assert_eq!(results.facets.languages[0].count, 1);
+
+        // Una década fuera de rango se rechaza antes de llegar a la consulta
+        let response = server
+            .get("/books/search?page=1&page_size=10&decade=65535")
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+        let problem = response.json::<ProblemDetails>();
+        assert_eq!(problem.code, "book.validation");
+        assert_eq!(problem.errors[0].field, "decade");
+
+        let response = server
+            .get("/audit?action=subject_created")
+            .add_header(AUTHORIZATION, api_key)
+            .await;
+        assert_eq!(response.json::<PaginatedAuditEntries>().total, 1);
+    }
+
+    #[tokio::test]
+    async fn shelf_is_browsed_in_classification_order() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let api_key = format!("ApiKey {raw_api_key}");
+
+        let invalid = serde_json::json!({
+            "title": "Libro",
+            "authors": ["Autor"],
+            "publisher": "Editorial",
+            "year": 2001,
+            "isbn": "978-0-306-40615-7",
+            "stored_quantity": 1,
+            "classification": { "scheme": "dewey", "number": "QA76" }
+        });
+        let response = server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&invalid)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.json::<ProblemDetails>().errors[0].field,
+            "classification"
+        );
+
+        for (isbn, number, call_number) in [
+            ("978-84-376-0495-4", "863.64", "863.64 G216c"),
+            ("978-0-306-40615-7", "100", "100 A1"),
+            ("978-3-16-148410-0", "82", "82 B12"),
+            ("978-1-4028-9462-6", "900", "900 H6"),
+        ] {
+            server
+                .post("/books")
+                .add_header(AUTHORIZATION, api_key.clone())
+                .json(&serde_json::json!({
+                    "title": format!("Libro {call_number}"),
+                    "authors": ["Autor"],
+                    "publisher": "Editorial",
+                    "year": 2001,
+                    "isbn": isbn,
+                    "stored_quantity": 1,
+                    "classification": { "scheme": "dewey", "number": number },
+                    "call_number": call_number
+                }))
+                .await
+                .assert_status(http::StatusCode::CREATED);
+        }
+
+        let response = server.get("/books/shelf?call_number=200&count=2").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        let shelf = response.json::<serde_json::Value>();
+        let call_numbers = |side: &str| {
+            shelf[side]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|book| book["call_number"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(call_numbers("before"), ["82 B12", "100 A1"]);
+        assert_eq!(call_numbers("after"), ["863.64 G216c", "900 H6"]);
+        assert_eq!(shelf["after"][0]["classification"]["number"], "863.64");
+
+        let response = server.get("/books/shelf?call_number=%20").await;
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn stock_is_tracked_per_location() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let api_key = format!("ApiKey {raw_api_key}");
+
+        let mut locations = Vec::new();
+        for name in ["Centro", "Norte"] {
+            let response = server
+                .post("/locations")
+                .add_header(AUTHORIZATION, api_key.clone())
+                .json(&serde_json::json!({ "name": name }))
+                .await;
+            assert_eq!(response.status_code(), http::StatusCode::CREATED);
+            locations.push(response.json::<Location>().id);
+        }
+        let (centro, norte) = (locations[0], locations[1]);
+
+        let response = server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&serde_json::json!({
+                "title": "Rayuela",
+                "authors": ["Julio Cortázar"],
+                "publisher": "Sudamericana",
+                "year": 1963,
+                "isbn": "978-84-376-0495-4",
+                "stored_quantity": 4
+            }))
+            .await;
+        let id = response.json::<BookId>().id;
+
+        let response = server
+            .put(&format!("/books/{id}/locations/{centro}"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&serde_json::json!({ "quantity": 5 }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+
+        let response = server
+            .put(&format!("/books/{id}/locations/{centro}"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&serde_json::json!({ "quantity": 3 }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        assert_eq!(response.json::<BookAvailability>().unassigned, 1);
+
+        let transfer = serde_json::json!({ "from": centro, "to": norte, "quantity": 2 });
+        let response = server
+            .post(&format!("/books/{id}/transfers"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&transfer)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::CREATED);
+
+        let response = server
+            .post(&format!("/books/{id}/transfers"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&transfer)
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+
+        let availability = server
+            .get(&format!("/books/{id}/availability"))
+            .await
+            .json::<BookAvailability>();
+        assert_eq!(availability.quantity_at(centro), 1);
+        assert_eq!(availability.quantity_at(norte), 2);
+
+        let movements = server
+            .get(&format!("/books/{id}/movements"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await
+            .json::<Vec<StockMovement>>();
+        assert_eq!(movements.len(), 1);
+
+        let books = server
+            .get(&format!("/books?location={norte}"))
+            .await
+            .json::<PaginatedBooks>();
+        assert_eq!(books.total, 1);
+        let results = server
+            .get(&format!(
+                "/books/search?page=1&page_size=10&location={norte}&decade=1970"
+            ))
+            .await
+            .json::<BookSearchResults>();
+        assert_eq!(results.results.total, 0);
+
+        // La cantidad guardada no puede quedar por debajo de los ejemplares asignados
+        let response = server
+            .put(&format!("/books/{id}"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&serde_json::json!({
+                "title": "Rayuela",
+                "authors": ["Julio Cortázar"],
+                "publisher": "Sudamericana",
+                "year": 1963,
+                "isbn": "978-84-376-0495-4",
+                "stored_quantity": 2
+            }))
+            .await;
+        assert_eq!(
+            response.json::<ProblemDetails>().code,
+            "book.stock_exceeds_quantity"
+        );
+        assert_eq!(response.status_code(), http::StatusCode::BAD_REQUEST);
+
+        // Una ApiKey de la sucursal Norte solo puede enviar ejemplares desde ella
+        let scoped_key = state
+            .api_keys
+            .creation
+            .create_scoped(ApiKeyScope::Location(norte), &Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let scoped_key = format!("ApiKey {scoped_key}");
+        let response = server
+            .post(&format!("/books/{id}/transfers"))
+            .add_header(AUTHORIZATION, scoped_key.clone())
+            .json(&serde_json::json!({ "from": centro, "to": norte, "quantity": 1 }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::FORBIDDEN);
+        assert_eq!(
+            response.json::<ProblemDetails>().code,
+            "book.location_forbidden"
+        );
+        let response = server
+            .post(&format!("/books/{id}/transfers"))
+            .add_header(AUTHORIZATION, scoped_key.clone())
+            .json(&serde_json::json!({ "from": norte, "to": centro, "quantity": 1 }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::CREATED);
+
+        let response = server.get("/locations").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        let copies = response
+            .json::<PaginatedLocations>()
+            .locations
+            .iter()
+            .map(|location| location.copies)
+            .collect::<Vec<_>>();
+        assert_eq!(copies, [2, 1]);
+
+        let response = server
+            .get("/audit?action=stock_transferred")
+            .add_header(AUTHORIZATION, api_key)
+            .await;
+        assert_eq!(response.json::<PaginatedAuditEntries>().total, 2);
+    }
+
+    #[tokio::test]
+    async fn scoped_api_keys_cannot_change_the_catalog() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let api_key = format!("ApiKey {raw_api_key}");
+
+        let mut locations = Vec::new();
+        for name in ["Centro", "Norte", "Sur"] {
+            let response = server
+                .post("/locations")
+                .add_header(AUTHORIZATION, api_key.clone())
+                .json(&serde_json::json!({ "name": name }))
+                .await;
+            locations.push(response.json::<Location>().id);
+        }
+        let (centro, norte, sur) = (locations[0], locations[1], locations[2]);
+
+        let book = serde_json::json!({
+            "title": "Rayuela",
+            "authors": ["Julio Cortázar", "Julio Cortazar"],
+            "publisher": "Sudamericana",
+            "year": 1963,
+            "isbn": "978-84-376-0495-4",
+            "stored_quantity": 2
+        });
+        let response = server
+            .post("/books")
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&book)
+            .await;
+        let id = response.json::<BookId>().id;
+        server
+            .put(&format!("/books/{id}/locations/{centro}"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .json(&serde_json::json!({ "quantity": 2 }))
+            .await
+            .assert_status_ok();
+        for to in [norte, sur] {
+            server
+                .post(&format!("/books/{id}/transfers"))
+                .add_header(AUTHORIZATION, api_key.clone())
+                .json(&serde_json::json!({ "from": centro, "to": to, "quantity": 1 }))
+                .await
+                .assert_status(http::StatusCode::CREATED);
+        }
+        let authors = server
+            .get("/authors?name=Julio")
+            .await
+            .json::<PaginatedAuthors>()
+            .authors;
+
+        let scoped_key = state
+            .api_keys
+            .creation
+            .create_scoped(ApiKeyScope::Location(norte), &Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let scoped_key = format!("ApiKey {scoped_key}");
+
+        // Los cambios que afectan a todo el catálogo y la auditoría requieren una ApiKey sin límite
+        let requests = [
+            server.put(&format!("/books/{id}")).json(&book),
+            server.delete(&format!("/books/{id}")),
+            server
+                .post(&format!("/authors/{}/merge", authors[0].id))
+                .json(&serde_json::json!({ "into": authors[1].id })),
+            server.get("/audit"),
+            server
+                .post("/locations")
+                .json(&serde_json::json!({ "name": "Este" })),
+        ];
+        for request in requests {
+            let response = request.add_header(AUTHORIZATION, scoped_key.clone()).await;
+            assert_eq!(response.status_code(), http::StatusCode::FORBIDDEN);
+            assert_eq!(
+                response.json::<ProblemDetails>().code,
+                "auth.scope_forbidden"
+            );
+        }
+        server
+            .get(&format!("/books/{id}"))
+            .add_header(AUTHORIZATION, api_key.clone())
+            .await
+            .assert_status_ok();
+        assert_eq!(
+            server
+                .get("/authors?name=Julio")
+                .await
+                .json::<PaginatedAuthors>()
+                .total,
+            2
+        );
+
+        // Solo ve los traslados de su sucursal
+        let movements = server
+            .get(&format!("/books/{id}/movements"))
+            .add_header(AUTHORIZATION, scoped_key)
+            .await
+            .json::<Vec<StockMovement>>();
+        assert_eq!(movements.len(), 1);
+        assert_eq!(movements[0].to_location_id, norte);
+        let movements = server
+            .get(&format!("/books/{id}/movements"))
+            .add_header(AUTHORIZATION, api_key)
+            .await
+            .json::<Vec<StockMovement>>();
+        assert_eq!(movements.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn apps_are_isolated() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+        let other_server =
+            TestServer::new(routes(test_state().await)).expect("Error al crear servidor de prueba");
+
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+
+        let response = server
+            .post("/books")
+            .add_header(AUTHORIZATION, format!("ApiKey {raw_api_key}"))
+            .json(&serde_json::json!({
+                "title": "Pedro Páramo",
+                "authors": ["Juan Rulfo"],
+                "publisher": "Fondo de Cultura Económica",
+                "year": 1955,
+                "isbn": "978-0-306-40615-7",
+                "stored_quantity": 1
+            }))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::CREATED);
+
+        let books = server.get("/books").await.json::<PaginatedBooks>();
+        assert_eq!(books.total, 1);
+
+        let books = other_server.get("/books").await.json::<PaginatedBooks>();
+        assert_eq!(books.total, 0);
+
+        // La ApiKey solo existe en la base de datos donde se creó
+        let response = other_server
+            .get(&format!("/books/{}", Uuid::new_v4()))
+            .add_header(AUTHORIZATION, format!("ApiKey {raw_api_key}"))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn revoked_api_keys_are_rejected_immediately() {
+        let state = test_state().await;
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let path = format!("/books/{}", Uuid::new_v4());
+
+        // La primera petición deja la ApiKey verificada en la caché
+        let response = server
+            .get(&path)
+            .add_header(AUTHORIZATION, format!("ApiKey {raw_api_key}"))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::NOT_FOUND);
+
+        state
+            .api_keys
+            .deletion
+            .delete(raw_api_key.id(), &Actor::cli())
+            .await
+            .expect("Error al eliminar API Key");
+
+        let response = server
+            .get(&path)
+            .add_header(AUTHORIZATION, format!("ApiKey {raw_api_key}"))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn api_keys_revoked_from_another_process_are_rejected() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .expect("Error al crear base de datos en memoria");
+        AppState::create_tables(&pool)
+            .await
+            .expect("Error al crear las tablas");
+        let state = AppState::sqlite(
+            pool.clone(),
+            HeaderValue::from_static(DEFAULT_CACHE_CONTROL),
+        );
+        // La CLI usa la misma base de datos pero su propia caché
+        let cli = AppState::sqlite(pool, HeaderValue::from_static(DEFAULT_CACHE_CONTROL));
+        let server =
+            TestServer::new(routes(state.clone())).expect("Error al crear servidor de prueba");
+
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let path = format!("/books/{}", Uuid::new_v4());
+
+        let response = server
+            .get(&path)
+            .add_header(AUTHORIZATION, format!("ApiKey {raw_api_key}"))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::NOT_FOUND);
+
+        cli.api_keys
+            .deletion
+            .delete(raw_api_key.id(), &Actor::cli())
+            .await
+            .expect("Error al eliminar API Key");
+
+        let response = server
+            .get(&path)
+            .add_header(AUTHORIZATION, format!("ApiKey {raw_api_key}"))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn repository_failures_are_internal_errors() {
+        let working = test_state().await;
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .expect("Error al crear base de datos en memoria");
+        let books = BooksState::new(
+            BookRepositories {
+                books: Arc::new(UnavailableBookRepository),
+                ..BookRepositories::sqlite(pool)
+            },
+            working.audit.log.clone(),
+            HeaderValue::from_static(DEFAULT_CACHE_CONTROL),
+        );
+        let state = AppState::new(books, working.api_keys, working.audit);
+
+        let server = TestServer::new(routes(state)).expect("Error al crear servidor de prueba");
+
+        let response = server.get("/books").await;
+        assert_eq!(
+            response.status_code(),
+            http::StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            response.json::<ProblemDetails>().code,
+            "book.database_error"
+        );
+        assert!(!response.headers().contains_key(http::header::ETAG));
+    }
+
+    /// Servidor de prueba cuyas peticiones llegan desde `ip`
+    fn server_from(state: AppState, ip: [u8; 4]) -> TestServer {
+        let app = routes(state).layer(MockConnectInfo(SocketAddr::from((ip, 40000))));
+        TestServer::new(app).expect("Error al crear servidor de prueba")
+    }
+
+    #[tokio::test]
+    async fn public_routes_are_rate_limited_per_ip() {
+        let state = test_state()
+            .await
+            .with_rate_limits(RateLimits::new(&RateLimitConfig {
+                public: "2/min".parse().unwrap(),
+                ..Default::default()
+            }));
+        let server = server_from(state.clone(), [10, 0, 0, 1]);
+
+        let response = server.get("/books").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        assert_eq!(response.header("ratelimit-limit"), "2");
+        assert_eq!(response.header("ratelimit-remaining"), "1");
+
+        server.get("/books").await.assert_status_ok();
+
+        let response = server.get("/books").await;
+        assert_eq!(response.status_code(), http::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.header("ratelimit-remaining"), "0");
+        let retry_after: u64 = response
+            .header(http::header::RETRY_AFTER)
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=30).contains(&retry_after));
+        assert_eq!(response.json::<ProblemDetails>().code, "app.rate_limited");
+
+        // Otra IP tiene su propio límite
+        let other_server = server_from(state, [10, 0, 0, 2]);
+        other_server.get("/books").await.assert_status_ok();
+    }
+
+    #[tokio::test]
+    async fn api_keys_are_rate_limited() {
+        let state = test_state().await;
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let key_id = raw_api_key.id().clone();
+
+        let state = state.with_rate_limits(RateLimits::new(&RateLimitConfig {
+            api_key: RateLimit::Off,
+            api_key_overrides: format!("{key_id}=1/min").parse().unwrap(),
+            ..Default::default()
+        }));
+        let server = server_from(state, [10, 0, 0, 1]);
+        let path = format!("/books/{}", Uuid::new_v4());
+
+        let response = server
+            .get(&path)
+            .add_header(AUTHORIZATION, format!("ApiKey {raw_api_key}"))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::NOT_FOUND);
+        assert_eq!(response.header("ratelimit-remaining"), "0");
+
+        let response = server
+            .get(&path)
+            .add_header(AUTHORIZATION, format!("ApiKey {raw_api_key}"))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response
+            .header(http::header::RETRY_AFTER)
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=60).contains(&retry_after));
+    }
+
+    #[tokio::test]
+    async fn failed_auth_is_rate_limited_per_ip() {
+        let state = test_state()
+            .await
+            .with_rate_limits(RateLimits::new(&RateLimitConfig {
+                auth_failures: "2/min".parse().unwrap(),
+                ..Default::default()
+            }));
+        let raw_api_key = state
+            .api_keys
+            .creation
+            .create(&Actor::cli())
+            .await
+            .expect("Error al genera API Key");
+        let server = server_from(state.clone(), [10, 0, 0, 1]);
+        let path = format!("/books/{}", Uuid::new_v4());
+
+        for _ in 0..2 {
+            let response = server
+                .get(&path)
+                .add_header(AUTHORIZATION, "ApiKey LibraryApi_falsa_clave")
+                .await;
+            assert_eq!(response.status_code(), http::StatusCode::UNAUTHORIZED);
+        }
+
+        // Agotados los intentos, ni siquiera una ApiKey válida se verifica
+        let response = server
+            .get(&path)
+            .add_header(AUTHORIZATION, format!("ApiKey {raw_api_key}"))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::TOO_MANY_REQUESTS);
+        assert!(response.headers().contains_key(http::header::RETRY_AFTER));
+
+        let other_server = server_from(state, [10, 0, 0, 2]);
+        let response = other_server
+            .get(&path)
+            .add_header(AUTHORIZATION, format!("ApiKey {raw_api_key}"))
+            .await;
+        assert_eq!(response.status_code(), http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn library_api_can_be_nested() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .expect("Error al crear base de datos en memoria");
+
+        let library = LibraryApi::builder()
+            .pool(pool)
+            .prefix("/library")
+            .create_tables(true)
+            .layer(middleware::from_fn(|req: Request, next: Next| async move {
+                let mut response = next.run(req).await;
+                response
+                    .headers_mut()
+                    .insert("x-embedded", HeaderValue::from_static("true"));
+                response
+            }))
+            .build()
+            .await
+            .expect("Error al construir la API");
+
+        let app = Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .merge(library);
+        let server = TestServer::new(app).expect("Error al crear servidor de prueba");
+
+        let response = server.get("/health").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        assert!(!response.headers().contains_key("x-embedded"));
+
+        let response = server.get("/library/books").await;
+        assert_eq!(response.status_code(), http::StatusCode::OK);
+        assert_eq!(response.header("x-embedded"), "true");
+
+        let spec = server
+            .get("/library/openapi.json")
+            .await
+            .json::<serde_json::Value>();
+        assert_eq!(spec["servers"][0]["url"], "/library");
+
+        let response = server.get("/books").await;
+        assert_eq!(response.status_code(), http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn graceful_shutdown_finishes_in_flight_requests() {
+        let library = LibraryApi::builder()
+            .state(test_state().await)
+            .build()
+            .await
+            .expect("Error al construir la API");
+        let app = Router::new()
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+                    "done"
+                }),
+            )
+            .merge(library);
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Error al abrir puerto");
+        let addr = listener.local_addr().unwrap();
+        let handle = Handle::new();
+        let server = tokio::spawn(serve(listener, app, handle.clone()));
+        handle.listening().await.expect("El servidor no inició");
+
+        let in_flight = tokio::spawn(reqwest::get(format!("http://{addr}/slow")));
+
+        // Esperar a que la petición esté en curso antes de pedir el apagado
+        while handle.connection_count() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        handle.graceful_shutdown(Some(Duration::from_secs(5)));
+
+        let response = in_flight
+            .await
+            .unwrap()
+            .expect("La petición en curso fue interrumpida");
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "done");
+
+        server
+            .await
+            .unwrap()
+            .expect("El servidor terminó con error");
+
+        // Después del apagado ya no se aceptan conexiones nuevas
+        assert!(reqwest::get(format!("http://{addr}/books")).await.is_err());
+    }
+
+    /// Certificado de servidor para `localhost` firmado por una CA generada al momento.
+    /// Devuelve el PEM de la CA, el del certificado y el de la llave.
+    fn generate_certificate() -> (String, String, String) {
+        use rcgen::{BasicConstraints, CertificateParams, DnType, IsCa, KeyPair};
+
+        let mut ca_params = CertificateParams::new(Vec::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params
+            .distinguished_name
+            .push(DnType::CommonName, "Library API test CA");
+        let ca_key = KeyPair::generate().unwrap();
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(vec!["localhost".to_string()]).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "localhost");
+        let cert = params.signed_by(&key, &ca, &ca_key).unwrap();
+
+        (ca.pem(), cert.pem(), key.serialize_pem())
+    }
+
+    fn https_client(ca_pem: &str) -> reqwest::Client {
+        reqwest::Client::builder()
+            .add_root_certificate(reqwest::Certificate::from_pem(ca_pem.as_bytes()).unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn tls_serves_and_reloads_certificates() {
+        let dir = tempfile::tempdir().expect("Error al crear directorio temporal");
+        let paths = TlsPaths {
+            cert: dir.path().join("cert.pem"),
+            key: dir.path().join("key.pem"),
+        };
+
+        let (ca, cert, key) = generate_certificate();
+        std::fs::write(&paths.cert, cert).unwrap();
+        std::fs::write(&paths.key, key).unwrap();
+
+        let rustls = paths.load().await.expect("Error al cargar el certificado");
+        tokio::spawn(
+            paths
+                .clone()
+                .watch(rustls.clone(), Duration::from_millis(50)),
+        );
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Error al abrir puerto");
+        let port = listener.local_addr().unwrap().port();
+        let handle = Handle::new();
+        tokio::spawn(serve_tls(
+            listener,
+            routes(test_state().await),
+            handle.clone(),
+            rustls,
+        ));
+        handle.listening().await.expect("El servidor no inició");
+
+        let url = format!("https://localhost:{port}/books");
+        let response = https_client(&ca).get(&url).send().await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        // Un certificado nuevo en disco se empieza a usar sin reiniciar el servidor
+        let (new_ca, new_cert, new_key) = generate_certificate();
+        assert!(https_client(&new_ca).get(&url).send().await.is_err());
+
+        std::fs::write(&paths.key, new_key).unwrap();
+        std::fs::write(&paths.cert, new_cert).unwrap();
+
+        let mut reloaded = false;
+        for _ in 0..50 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            if https_client(&new_ca).get(&url).send().await.is_ok() {
+                reloaded = true;
+                break;
+            }
+        }
+        assert!(reloaded, "El certificado no se recargó");
+
+        handle.graceful_shutdown(Some(Duration::from_secs(1)));
+    }
+
+    #[tokio::test]
+    async fn http_redirects_to_https() {
+        let server =
+            TestServer::new(redirect_router(8443)).expect("Error al crear servidor de prueba");
+
+        let response = server
+            .get("/books/search?title=Quijote")
+            .add_header(http::header::HOST, "biblioteca.local:8080")
+            .await;
+
+        assert_eq!(response.status_code(), http::StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.header(http::header::LOCATION),
+            "https://biblioteca.local:8443/books/search?title=Quijote"
+        );
+    }
 }